@@ -242,6 +242,23 @@ builtin_macros_test_case_non_item = `#[test_case]` attribute is only allowed on
 builtin_macros_test_runner_invalid = `test_runner` argument must be a path
 builtin_macros_test_runner_nargs = `#![test_runner(..)]` accepts exactly 1 argument
 
+builtin_macros_test_timeout_invalid = `#[test_timeout]` expects a positive whole number of seconds
+    .label = expected a value like `"30"`
+
+builtin_macros_test_with_arg_count = this test case has {$found} {$found ->
+    [one] argument
+    *[other] arguments
+    } but the test function takes {$expected}
+    .label = expected {$expected} {$expected ->
+    [one] argument
+    *[other] arguments
+    }
+
+builtin_macros_test_with_args = `#[test_with]` expects a parenthesized list of arguments
+    .label = expected `#[test_with(arg1, arg2, ...)]`
+
+builtin_macros_test_with_bench = `#[test_with]` cannot be used on benchmarks
+
 builtin_macros_tests_not_support = building tests with panic=abort is not supported without `-Zpanic_abort_tests`
 
 builtin_macros_trace_macros = trace_macros! accepts only `true` or `false`
@@ -666,6 +666,39 @@ pub(crate) struct TestCaseNonItem {
     pub(crate) span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(builtin_macros_test_timeout_invalid)]
+pub(crate) struct TestTimeoutInvalid {
+    #[primary_span]
+    #[label]
+    pub(crate) span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(builtin_macros_test_with_arg_count)]
+pub(crate) struct TestWithArgCount {
+    #[primary_span]
+    #[label]
+    pub(crate) span: Span,
+    pub(crate) expected: usize,
+    pub(crate) found: usize,
+}
+
+#[derive(Diagnostic)]
+#[diag(builtin_macros_test_with_args)]
+pub(crate) struct TestWithArgs {
+    #[primary_span]
+    #[label]
+    pub(crate) span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(builtin_macros_test_with_bench)]
+pub(crate) struct TestWithBench {
+    #[primary_span]
+    pub(crate) span: Span,
+}
+
 #[derive(Diagnostic)]
 #[diag(builtin_macros_test_bad_fn)]
 pub(crate) struct TestBadFn {
@@ -1,8 +1,9 @@
 use crate::errors;
 /// The expansion from a test function to the appropriate test struct for libtest
 /// Ideally, this code would be in libtest but for efficiency and error messages it lives here.
-use crate::util::{check_builtin_macro_attribute, warn_on_duplicate_attribute};
+use crate::util::{check_builtin_macro_attribute, parse_expr, warn_on_duplicate_attribute};
 use rustc_ast::ptr::P;
+use rustc_ast::token;
 use rustc_ast::{self as ast, attr, GenericParamKind};
 use rustc_ast_pretty::pprust;
 use rustc_errors::{Applicability, Diag, Level};
@@ -107,7 +108,7 @@ pub(crate) fn expand_test_or_bench(
         return vec![];
     }
 
-    let (item, is_stmt) = match item {
+    let (mut item, is_stmt) = match item {
         Annotatable::Item(i) => (i, false),
         Annotatable::Stmt(stmt) if matches!(stmt.kind, ast::StmtKind::Item(_)) => {
             // FIXME: Use an 'if let' guard once they are implemented
@@ -135,18 +136,19 @@ pub(crate) fn expand_test_or_bench(
     // check_*_signature will report any errors in the type so compilation
     // will fail. We shouldn't try to expand in this case because the errors
     // would be spurious.
-    let check_result = if is_bench {
-        check_bench_signature(cx, &item, fn_)
-    } else {
-        check_test_signature(cx, &item, fn_)
+    let check_result = match test_cases(cx, &item, is_bench) {
+        Ok(cases) if is_bench => check_bench_signature(cx, &item, fn_).map(|()| cases),
+        Ok(cases) => check_test_signature(cx, &item, fn_, &cases).map(|()| cases),
+        Err(guar) => Err(guar),
     };
-    if check_result.is_err() {
+    let Ok(cases) = check_result else {
+        strip_test_with(&mut item);
         return if is_stmt {
             vec![Annotatable::Stmt(P(cx.stmt_item(item.span, item)))]
         } else {
             vec![Annotatable::Item(item)]
         };
-    }
+    };
 
     let sp = cx.with_def_site_ctxt(item.span);
     let ret_ty_sp = cx.with_def_site_ctxt(fn_.sig.decl.output.span());
@@ -194,59 +196,62 @@ pub(crate) fn expand_test_or_bench(
         expr
     };
 
-    let test_fn = if is_bench {
-        // A simple ident for a lambda
-        let b = Ident::from_str_and_span("b", attr_sp);
-
-        cx.expr_call(
-            sp,
-            cx.expr_path(test_path("StaticBenchFn")),
-            thin_vec![
-                // #[coverage(off)]
-                // |b| self::test::assert_test_result(
-                coverage_off(cx.lambda1(
-                    sp,
-                    cx.expr_call(
+    // `args` are those of a `#[test_with]` case; benchmarks and plain tests have none.
+    let test_fn = |args: ThinVec<P<ast::Expr>>| {
+        if is_bench {
+            // A simple ident for a lambda
+            let b = Ident::from_str_and_span("b", attr_sp);
+
+            cx.expr_call(
+                sp,
+                cx.expr_path(test_path("StaticBenchFn")),
+                thin_vec![
+                    // #[coverage(off)]
+                    // |b| self::test::assert_test_result(
+                    coverage_off(cx.lambda1(
                         sp,
-                        cx.expr_path(test_path("assert_test_result")),
-                        thin_vec![
-                            // super::$test_fn(b)
-                            cx.expr_call(
-                                ret_ty_sp,
-                                cx.expr_path(cx.path(sp, vec![item.ident])),
-                                thin_vec![cx.expr_ident(sp, b)],
-                            ),
-                        ],
-                    ),
-                    b,
-                )), // )
-            ],
-        )
-    } else {
-        cx.expr_call(
-            sp,
-            cx.expr_path(test_path("StaticTestFn")),
-            thin_vec![
-                // #[coverage(off)]
-                // || {
-                coverage_off(cx.lambda0(
-                    sp,
-                    // test::assert_test_result(
-                    cx.expr_call(
+                        cx.expr_call(
+                            sp,
+                            cx.expr_path(test_path("assert_test_result")),
+                            thin_vec![
+                                // super::$test_fn(b)
+                                cx.expr_call(
+                                    ret_ty_sp,
+                                    cx.expr_path(cx.path(sp, vec![item.ident])),
+                                    thin_vec![cx.expr_ident(sp, b)],
+                                ),
+                            ],
+                        ),
+                        b,
+                    )), // )
+                ],
+            )
+        } else {
+            cx.expr_call(
+                sp,
+                cx.expr_path(test_path("StaticTestFn")),
+                thin_vec![
+                    // #[coverage(off)]
+                    // || {
+                    coverage_off(cx.lambda0(
                         sp,
-                        cx.expr_path(test_path("assert_test_result")),
-                        thin_vec![
-                            // $test_fn()
-                            cx.expr_call(
-                                ret_ty_sp,
-                                cx.expr_path(cx.path(sp, vec![item.ident])),
-                                ThinVec::new(),
-                            ), // )
-                        ],
-                    ), // }
-                )), // )
-            ],
-        )
+                        // test::assert_test_result(
+                        cx.expr_call(
+                            sp,
+                            cx.expr_path(test_path("assert_test_result")),
+                            thin_vec![
+                                // $test_fn($args)
+                                cx.expr_call(
+                                    ret_ty_sp,
+                                    cx.expr_path(cx.path(sp, vec![item.ident])),
+                                    args,
+                                ), // )
+                            ],
+                        ), // }
+                    )), // )
+                ],
+            )
+        }
     };
 
     let test_path_symbol = Symbol::intern(&item_path(
@@ -255,12 +260,33 @@ pub(crate) fn expand_test_or_bench(
         &item.ident,
     ));
 
-    let location_info = get_location_info(cx, &item);
+    let should_panic = should_panic(cx, &item);
+    let timeout = test_timeout(cx, &item);
 
-    let mut test_const =
-        cx.item(
+    // A single test, or one test named `$test_fn::case_$n` per `#[test_with]` case.
+    let tests: Vec<_> = if cases.is_empty() {
+        let location_info = get_location_info(cx, item.ident.span);
+        vec![(Ident::new(item.ident.name, sp), test_path_symbol, location_info, ThinVec::new())]
+    } else {
+        cases
+            .into_iter()
+            .enumerate()
+            .map(|(n, case)| {
+                (
+                    Ident::from_str_and_span(&format!("{}_case_{n}", item.ident), sp),
+                    Symbol::intern(&format!("{test_path_symbol}::case_{n}")),
+                    get_location_info(cx, case.span),
+                    case.args,
+                )
+            })
+            .collect()
+    };
+
+    let mut test_consts = Vec::with_capacity(tests.len());
+    for (ident, test_path_symbol, location_info, args) in tests {
+        let mut test_const = cx.item(
             sp,
-            Ident::new(item.ident.name, sp),
+            ident,
             thin_vec![
                 // #[cfg(test)]
                 cx.attr_nested_word(sym::cfg, sym::test, attr_sp),
@@ -323,7 +349,7 @@ pub(crate) fn expand_test_or_bench(
                                     // should_panic: ...
                                     field(
                                         "should_panic",
-                                        match should_panic(cx, &item) {
+                                        match should_panic {
                                             // test::ShouldPanic::No
                                             ShouldPanic::No => {
                                                 cx.expr_path(should_panic_path("No"))
@@ -340,6 +366,15 @@ pub(crate) fn expand_test_or_bench(
                                             ),
                                         },
                                     ),
+                                    // timeout: Some(seconds) | None
+                                    field(
+                                        "timeout",
+                                        if let Some(secs) = timeout {
+                                            cx.expr_some(sp, cx.expr_u64(sp, secs))
+                                        } else {
+                                            cx.expr_none(sp)
+                                        },
+                                    ),
                                     // test_type: ...
                                     field(
                                         "test_type",
@@ -363,7 +398,7 @@ pub(crate) fn expand_test_or_bench(
                             ),
                         ),
                         // testfn: test::StaticTestFn(...) | test::StaticBenchFn(...)
-                        field("testfn", test_fn), // }
+                        field("testfn", test_fn(args)), // }
                     ],
                         ), // }
                     ),
@@ -371,34 +406,28 @@ pub(crate) fn expand_test_or_bench(
                 .into(),
             ),
         );
-    test_const = test_const.map(|mut tc| {
-        tc.vis.kind = ast::VisibilityKind::Public;
-        tc
-    });
+        test_const = test_const.map(|mut tc| {
+            tc.vis.kind = ast::VisibilityKind::Public;
+            tc
+        });
+        test_consts.push(test_const);
+    }
 
     // extern crate test
     let test_extern = cx.item(sp, test_id, ast::AttrVec::new(), ast::ItemKind::ExternCrate(None));
 
-    debug!("synthetic test item:\n{}\n", pprust::item_to_string(&test_const));
+    for test_const in &test_consts {
+        debug!("synthetic test item:\n{}\n", pprust::item_to_string(test_const));
+    }
+
+    strip_test_with(&mut item);
 
+    // Access to libtest under a hygienic name, the generated tests and the original item
+    let items = iter::once(test_extern).chain(test_consts).chain(iter::once(item));
     if is_stmt {
-        vec![
-            // Access to libtest under a hygienic name
-            Annotatable::Stmt(P(cx.stmt_item(sp, test_extern))),
-            // The generated test case
-            Annotatable::Stmt(P(cx.stmt_item(sp, test_const))),
-            // The original item
-            Annotatable::Stmt(P(cx.stmt_item(sp, item))),
-        ]
+        items.map(|item| Annotatable::Stmt(P(cx.stmt_item(sp, item)))).collect()
     } else {
-        vec![
-            // Access to libtest under a hygienic name
-            Annotatable::Item(test_extern),
-            // The generated test case
-            Annotatable::Item(test_const),
-            // The original item
-            Annotatable::Item(item),
-        ]
+        items.map(Annotatable::Item).collect()
     }
 }
 
@@ -431,8 +460,7 @@ fn not_testable_error(cx: &ExtCtxt<'_>, attr_sp: Span, item: Option<&ast::Item>)
         .emit();
 }
 
-fn get_location_info(cx: &ExtCtxt<'_>, item: &ast::Item) -> (Symbol, usize, usize, usize, usize) {
-    let span = item.ident.span;
+fn get_location_info(cx: &ExtCtxt<'_>, span: Span) -> (Symbol, usize, usize, usize, usize) {
     let (source_file, lo_line, lo_col, hi_line, hi_col) =
         cx.sess.source_map().span_to_location_info(span);
 
@@ -453,6 +481,7 @@ fn item_path(mod_path: &[Ident], item_ident: &Ident) -> String {
         .join("::")
 }
 
+#[derive(Clone, Copy)]
 enum ShouldPanic {
     No,
     Yes(Option<Symbol>),
@@ -513,6 +542,58 @@ fn should_panic(cx: &ExtCtxt<'_>, i: &ast::Item) -> ShouldPanic {
     }
 }
 
+/// Returns the hard time limit set with `#[test_timeout = "seconds"]`, if any.
+fn test_timeout(cx: &ExtCtxt<'_>, i: &ast::Item) -> Option<u64> {
+    let attr = attr::find_by_name(&i.attrs, sym::test_timeout)?;
+    match attr.value_str().and_then(|secs| secs.as_str().parse::<u64>().ok()) {
+        Some(secs) if secs > 0 => Some(secs),
+        _ => {
+            cx.dcx().emit_err(errors::TestTimeoutInvalid { span: attr.span });
+            None
+        }
+    }
+}
+
+/// The arguments of one `#[test_with(arg1, arg2, ...)]` case of a test.
+struct TestCase {
+    span: Span,
+    args: ThinVec<P<ast::Expr>>,
+}
+
+/// Parses the `#[test_with]` cases of a test, in the order they're written.
+fn test_cases(
+    cx: &ExtCtxt<'_>,
+    i: &ast::Item,
+    is_bench: bool,
+) -> Result<Vec<TestCase>, ErrorGuaranteed> {
+    let mut cases = Vec::new();
+    for attr in attr::filter_by_name(&i.attrs, sym::test_with) {
+        if is_bench {
+            return Err(cx.dcx().emit_err(errors::TestWithBench { span: attr.span }));
+        }
+        let ast::AttrArgs::Delimited(delim) = &attr.get_normal_item().args else {
+            return Err(cx.dcx().emit_err(errors::TestWithArgs { span: attr.span }));
+        };
+        let mut args = ThinVec::new();
+        let mut p = cx.new_parser_from_tts(delim.tokens.clone());
+        while p.token != token::Eof {
+            args.push(parse_expr(&mut p)?);
+            if !p.eat(&token::Comma) && p.token != token::Eof {
+                let span = p.token.span;
+                return Err(cx.dcx().emit_err(errors::ExpectedCommaInList { span }));
+            }
+        }
+        cases.push(TestCase { span: attr.span, args });
+    }
+    Ok(cases)
+}
+
+/// Removes the `#[test_with]` cases from a test once they're expanded, so that attribute
+/// checking can reject the ones left on items that aren't tests.
+fn strip_test_with(i: &mut ast::Item) {
+    i.attrs.retain(|attr| !attr.has_name(sym::test_with));
+}
+
 enum TestType {
     UnitTest,
     IntegrationTest,
@@ -545,6 +626,7 @@ fn check_test_signature(
     cx: &ExtCtxt<'_>,
     i: &ast::Item,
     f: &ast::Fn,
+    cases: &[TestCase],
 ) -> Result<(), ErrorGuaranteed> {
     let has_should_panic_attr = attr::contains_name(&i.attrs, sym::should_panic);
     let dcx = cx.dcx();
@@ -587,10 +669,16 @@ fn check_test_signature(
         _ => true,
     };
 
-    if !f.sig.decl.inputs.is_empty() {
+    if cases.is_empty() && !f.sig.decl.inputs.is_empty() {
         return Err(dcx.span_err(i.span, "functions used as tests can not have any arguments"));
     }
 
+    let expected = f.sig.decl.inputs.len();
+    if let Some(case) = cases.iter().find(|case| case.args.len() != expected) {
+        let found = case.args.len();
+        return Err(dcx.emit_err(errors::TestWithArgCount { span: case.span, expected, found }));
+    }
+
     if has_should_panic_attr && has_output {
         return Err(dcx.span_err(i.span, "functions using `#[should_panic]` must return `()`"));
     }
@@ -399,6 +399,38 @@ fn codegen_intrinsic_call(
                 }
             }
 
+            sym::carrying_mul_add => {
+                let (size, signed) = fn_args.type_at(0).int_size_and_signed(self.tcx);
+
+                // The full result cannot overflow an integer of twice the width,
+                // so this lowers to a single widening multiplication.
+                let wide_llty = self.type_ix(size.bits() * 2);
+                let [a, b, c, d] =
+                    [0, 1, 2, 3].map(|i| self.intcast(args[i].immediate(), wide_llty, signed));
+
+                let wide = if signed {
+                    let prod = self.unchecked_smul(a, b);
+                    let acc = self.unchecked_sadd(prod, c);
+                    self.unchecked_sadd(acc, d)
+                } else {
+                    let prod = self.unchecked_umul(a, b);
+                    let acc = self.unchecked_uadd(prod, c);
+                    self.unchecked_uadd(acc, d)
+                };
+
+                let narrow_llty = self.type_ix(size.bits());
+                let low = self.trunc(wide, narrow_llty);
+                let bits = self.const_uint(wide_llty, size.bits());
+                // The shifted-in bits are truncated away, so no `ashr` is needed for signed values.
+                let high = self.lshr(wide, bits);
+                let high = self.trunc(high, narrow_llty);
+
+                let pair_llty = self.type_struct(&[narrow_llty, narrow_llty], false);
+                let pair = self.const_poison(pair_llty);
+                let pair = self.insert_value(pair, low, 0);
+                self.insert_value(pair, high, 1)
+            }
+
             sym::raw_eq => {
                 use abi::Abi::*;
                 let tp_ty = fn_args.type_at(0);
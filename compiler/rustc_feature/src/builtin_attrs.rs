@@ -332,6 +332,14 @@ pub struct BuiltinAttribute {
         template!(Word, List: r#"expected = "reason""#, NameValueStr: "reason"), FutureWarnFollowing,
         EncodeCrossCrate::No,
     ),
+    gated!(
+        test_timeout, Normal, template!(NameValueStr: "seconds"), ErrorFollowing,
+        EncodeCrossCrate::No, experimental!(test_timeout)
+    ),
+    gated!(
+        test_with, Normal, template!(List: "arg1, arg2, ..."), DuplicatesOk,
+        EncodeCrossCrate::No, experimental!(test_with)
+    ),
     // FIXME(Centril): This can be used on stable but shouldn't.
     ungated!(
         reexport_test_harness_main, CrateLevel, template!(NameValueStr: "name"), ErrorFollowing,
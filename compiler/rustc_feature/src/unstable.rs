@@ -598,6 +598,10 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, string_deref_patterns, "1.67.0", Some(87121)),
     /// Allows the use of `#[target_feature]` on safe functions.
     (unstable, target_feature_11, "1.45.0", Some(69098)),
+    /// Allows setting a hard execution time limit on tests with `#[test_timeout]`.
+    (unstable, test_timeout, "CURRENT_RUSTC_VERSION", None),
+    /// Allows running a test function once per case of `#[test_with(args)]`.
+    (unstable, test_with, "CURRENT_RUSTC_VERSION", None),
     /// Allows using `#[thread_local]` on `static` items.
     (unstable, thread_local, "1.0.0", Some(29594)),
     /// Allows defining `trait X = A + B;` alias items.
@@ -24,7 +24,10 @@ pub fn check_attr(psess: &ParseSess, attr: &Attribute) {
     // Check input tokens for built-in and key-value attributes.
     match attr_info {
         // `rustc_dummy` doesn't have any restrictions specific to built-in attributes.
-        Some(BuiltinAttribute { name, template, .. }) if *name != sym::rustc_dummy => {
+        // `test_with` takes arbitrary expressions, which `#[test]` parses and checks.
+        Some(BuiltinAttribute { name, template, .. })
+            if *name != sym::rustc_dummy && *name != sym::test_with =>
+        {
             match parse_meta(psess, attr) {
                 Ok(meta) => check_builtin_meta_item(psess, &meta, attr.style, *name, *template),
                 Err(err) => {
@@ -199,6 +199,7 @@ fn check_attributes(
                 }
                 [sym::ffi_pure] => self.check_ffi_pure(attr.span, attrs, target),
                 [sym::ffi_const] => self.check_ffi_const(attr.span, target),
+                [sym::test_with] => self.check_test_with(attr.span),
                 [sym::rustc_const_unstable]
                 | [sym::rustc_const_stable]
                 | [sym::unstable]
@@ -1355,6 +1356,13 @@ fn check_ffi_const(&self, attr_span: Span, target: Target) -> bool {
         }
     }
 
+    /// `#[test]` consumes the `#[test_with]` cases of the function it expands, so any that
+    /// are left were written on something that isn't a test.
+    fn check_test_with(&self, attr_span: Span) -> bool {
+        self.dcx().emit_err(errors::TestWithWithoutTest { attr_span });
+        false
+    }
+
     /// Warns against some misuses of `#[must_use]`
     fn check_must_use(&self, hir_id: HirId, attr: &Attribute, target: Target) -> bool {
         if !matches!(
@@ -130,6 +130,13 @@ pub struct AttrShouldBeAppliedToTrait {
 #[diag(passes_target_feature_on_statement)]
 pub struct TargetFeatureOnStatement;
 
+#[derive(Diagnostic)]
+#[diag(passes_test_with_without_test)]
+pub struct TestWithWithoutTest {
+    #[primary_span]
+    pub attr_span: Span,
+}
+
 #[derive(Diagnostic)]
 #[diag(passes_should_be_applied_to_static)]
 pub struct AttrShouldBeAppliedToStatic {
@@ -505,6 +505,7 @@
         call_ref_future,
         caller_location,
         capture_disjoint_fields,
+        carrying_mul_add,
         catch_unwind,
         cause,
         cdylib,
@@ -1847,7 +1848,9 @@
         test_case,
         test_removed_feature,
         test_runner,
+        test_timeout,
         test_unstable_lint,
+        test_with,
         thread,
         thread_local,
         thread_local_macro,
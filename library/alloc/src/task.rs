@@ -7,9 +7,14 @@
 //! This may be detected at compile time using
 //! `#[cfg(target_has_atomic = "ptr")]`.
 
+use crate::boxed::Box;
 use crate::rc::Rc;
-use core::mem::ManuallyDrop;
-use core::task::{LocalWaker, RawWaker, RawWakerVTable};
+use crate::vec::Vec;
+use core::fmt;
+use core::future::Future;
+use core::mem::{self, ManuallyDrop};
+use core::pin::Pin;
+use core::task::{Context, LocalWaker, Poll, RawWaker, RawWakerVTable};
 
 #[cfg(target_has_atomic = "ptr")]
 use crate::sync::Arc;
@@ -347,3 +352,183 @@ unsafe fn drop_waker<W: LocalWake + 'static>(waker: *const ()) {
         &RawWakerVTable::new(clone_waker::<W>, wake::<W>, wake_by_ref::<W>, drop_waker::<W>),
     )
 }
+
+/// Creates a future which waits for all the futures of an iterator to complete,
+/// and resolves to a [`Vec`] of their outputs, in the order of the iterator.
+///
+/// All the futures are polled on the task that polls the returned future, so
+/// they make progress concurrently but not in parallel. Each call to `poll`
+/// polls the futures that have not completed yet, in order.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(future_join_all)]
+/// use std::task::join_all;
+///
+/// # let _ = async {
+/// let futures = (1..=3).map(|i| async move { i * 10 });
+/// assert_eq!(join_all(futures).await, [10, 20, 30]);
+/// # };
+/// ```
+#[unstable(feature = "future_join_all", issue = "none")]
+pub fn join_all<I>(iter: I) -> JoinAll<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Future,
+{
+    JoinAll { elems: iter.into_iter().map(MaybeDone::Future).collect::<Box<[_]>>().into() }
+}
+
+/// Creates a future which waits for all the futures of an iterator to complete
+/// successfully, and resolves to a [`Vec`] of their outputs, in the order of the
+/// iterator.
+///
+/// If any of the futures resolves to an error, the returned future resolves to
+/// that error right away, and the futures which have not completed yet are
+/// dropped.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(future_join_all)]
+/// use std::task::try_join_all;
+///
+/// # let _ = async {
+/// let ok = (1..=3).map(|i| async move { Ok::<_, String>(i) });
+/// assert_eq!(try_join_all(ok).await, Ok(vec![1, 2, 3]));
+///
+/// let err = (1..=3).map(|i| async move { if i == 2 { Err(i) } else { Ok(i) } });
+/// assert_eq!(try_join_all(err).await, Err(2));
+/// # };
+/// ```
+#[unstable(feature = "future_join_all", issue = "none")]
+pub fn try_join_all<I, T, E>(iter: I) -> TryJoinAll<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Future<Output = Result<T, E>>,
+{
+    TryJoinAll { elems: iter.into_iter().map(MaybeDone::Future).collect::<Box<[_]>>().into() }
+}
+
+/// A future which waits for all the futures of an iterator to complete.
+///
+/// This `struct` is created by the [`join_all`] function. See its documentation
+/// for more.
+#[unstable(feature = "future_join_all", issue = "none")]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct JoinAll<F: Future> {
+    elems: Pin<Box<[MaybeDone<F>]>>,
+}
+
+/// A future which waits for all the futures of an iterator to complete
+/// successfully, or for the first error.
+///
+/// This `struct` is created by the [`try_join_all`] function. See its
+/// documentation for more.
+#[unstable(feature = "future_join_all", issue = "none")]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct TryJoinAll<F: Future> {
+    elems: Pin<Box<[MaybeDone<F>]>>,
+}
+
+#[derive(Debug)]
+enum MaybeDone<F: Future> {
+    Future(F),
+    Done(F::Output),
+}
+
+impl<F: Future> MaybeDone<F> {
+    fn into_output(self) -> F::Output {
+        match self {
+            MaybeDone::Done(output) => output,
+            MaybeDone::Future(_) => unreachable!(),
+        }
+    }
+}
+
+/// Polls all the futures of `elems` that have not completed yet, storing their
+/// outputs in place. Returns whether all of them have completed, or the first
+/// output for which `is_err` holds.
+fn poll_all<F: Future>(
+    elems: Pin<&mut [MaybeDone<F>]>,
+    cx: &mut Context<'_>,
+    mut is_err: impl FnMut(&F::Output) -> bool,
+) -> Poll<Option<F::Output>> {
+    let mut all_done = true;
+    // SAFETY: the futures are never moved out of the slice. Once completed,
+    // they are dropped in place by overwriting them with their output.
+    for elem in unsafe { elems.get_unchecked_mut() } {
+        if let MaybeDone::Future(future) = elem {
+            match unsafe { Pin::new_unchecked(future) }.poll(cx) {
+                Poll::Ready(output) if is_err(&output) => return Poll::Ready(Some(output)),
+                Poll::Ready(output) => *elem = MaybeDone::Done(output),
+                Poll::Pending => all_done = false,
+            }
+        }
+    }
+    if all_done { Poll::Ready(None) } else { Poll::Pending }
+}
+
+/// Takes the outputs out of `elems`, once all the futures have completed.
+fn take_outputs<F: Future>(elems: &mut Pin<Box<[MaybeDone<F>]>>) -> Vec<F::Output> {
+    let elems = mem::replace(elems, Box::pin([]));
+    // SAFETY: all the futures have completed, and the outputs are not pinned.
+    let elems = unsafe { Pin::into_inner_unchecked(elems) };
+    elems.into_vec().into_iter().map(MaybeDone::into_output).collect()
+}
+
+#[unstable(feature = "future_join_all", issue = "none")]
+impl<F: Future> Future for JoinAll<F> {
+    type Output = Vec<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match poll_all(self.elems.as_mut(), cx, |_| false) {
+            Poll::Ready(_) => Poll::Ready(take_outputs(&mut self.elems)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[unstable(feature = "future_join_all", issue = "none")]
+impl<F, T, E> Future for TryJoinAll<F>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    type Output = Result<Vec<T>, E>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match poll_all(self.elems.as_mut(), cx, Result::is_err) {
+            Poll::Ready(Some(Err(err))) => {
+                // Drop the futures that have not completed yet.
+                self.elems = Box::pin([]);
+                Poll::Ready(Err(err))
+            }
+            Poll::Ready(Some(Ok(_))) => unreachable!(),
+            Poll::Ready(None) => Poll::Ready(take_outputs(&mut self.elems).into_iter().collect()),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[unstable(feature = "future_join_all", issue = "none")]
+impl<F> fmt::Debug for JoinAll<F>
+where
+    F: Future + fmt::Debug,
+    F::Output: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinAll").field("elems", &self.elems).finish()
+    }
+}
+
+#[unstable(feature = "future_join_all", issue = "none")]
+impl<F> fmt::Debug for TryJoinAll<F>
+where
+    F: Future + fmt::Debug,
+    F::Output: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TryJoinAll").field("elems", &self.elems).finish()
+    }
+}
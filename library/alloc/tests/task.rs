@@ -1,7 +1,9 @@
 use alloc::rc::Rc;
 use alloc::sync::Arc;
-use alloc::task::{LocalWake, Wake};
-use core::task::{LocalWaker, Waker};
+use alloc::task::{join_all, try_join_all, LocalWake, Wake};
+use core::future::{self, Future};
+use core::pin::pin;
+use core::task::{Context, LocalWaker, Poll, Waker};
 
 #[test]
 #[cfg_attr(miri, should_panic)] // `will_wake` doesn't guarantee that this test will work, and indeed on Miri it fails
@@ -34,3 +36,50 @@ fn wake(self: Rc<Self>) {}
     assert!(waker.will_wake(&clone));
     assert!(clone.will_wake(&waker));
 }
+
+/// Polls `future` to completion with a no-op waker, returning its output and
+/// the number of times it was pending.
+fn poll_to_end<F: Future>(future: F) -> (F::Output, usize) {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut pending = 0;
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return (output, pending),
+            Poll::Pending => pending += 1,
+        }
+    }
+}
+
+/// A future that is pending `n` times before resolving to `value`.
+fn pending_n<T>(n: usize, value: T) -> impl Future<Output = T> {
+    let mut value = Some(value);
+    let mut n = n;
+    future::poll_fn(move |_| {
+        if n == 0 {
+            Poll::Ready(value.take().unwrap())
+        } else {
+            n -= 1;
+            Poll::Pending
+        }
+    })
+}
+
+#[test]
+fn test_join_all() {
+    let futures = [pending_n(2, 'a'), pending_n(0, 'b'), pending_n(1, 'c')];
+    assert_eq!(poll_to_end(join_all(futures)), (vec!['a', 'b', 'c'], 2));
+
+    let empty: [future::Ready<u8>; 0] = [];
+    assert_eq!(poll_to_end(join_all(empty)), (vec![], 0));
+}
+
+#[test]
+fn test_try_join_all() {
+    let futures = [pending_n(1, Ok::<_, ()>(1)), pending_n(3, Ok(2))];
+    assert_eq!(poll_to_end(try_join_all(futures)), (Ok(vec![1, 2]), 3));
+
+    // An error completes the future early, even if other futures are pending.
+    let futures = [pending_n(5, Ok(1)), pending_n(1, Err("bad")), pending_n(3, Err("worse"))];
+    assert_eq!(poll_to_end(try_join_all(futures)), (Err("bad"), 1));
+}
@@ -1,3 +1,5 @@
+use super::{Collect, Filter, Map, Next, Take, Then};
+use crate::future::Future;
 use crate::ops::DerefMut;
 use crate::pin::Pin;
 use crate::task::{Context, Poll};
@@ -80,6 +82,197 @@ pub trait AsyncIterator {
     fn size_hint(&self) -> (usize, Option<usize>) {
         (0, None)
     }
+
+    /// Returns a future which resolves to the next item of the async iterator.
+    ///
+    /// This is the `async` counterpart of [`Iterator::next`]: awaiting the
+    /// returned future resolves to `Some(item)`, or to `None` once the async
+    /// iterator is exhausted.
+    ///
+    /// Async iterators that are not [`Unpin`] need to be pinned first, e.g. with
+    /// the [`pin!`] macro.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(async_iterator, async_iter_from_iter, async_iter_combinators, noop_waker)]
+    /// use std::async_iter::{self, AsyncIterator};
+    /// # use std::future::Future;
+    /// # use std::pin::pin;
+    /// # use std::task::{Context, Poll, Waker};
+    ///
+    /// # let fut = pin!(async {
+    /// let mut it = async_iter::from_iter([1, 2]);
+    /// assert_eq!(it.next().await, Some(1));
+    /// assert_eq!(it.next().await, Some(2));
+    /// assert_eq!(it.next().await, None);
+    /// # });
+    /// # assert_eq!(fut.poll(&mut Context::from_waker(Waker::noop())), Poll::Ready(()));
+    /// ```
+    ///
+    /// [`pin!`]: crate::pin::pin
+    #[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+    fn next(&mut self) -> Next<'_, Self>
+    where
+        Self: Unpin,
+    {
+        Next::new(self)
+    }
+
+    /// Takes a closure and creates an async iterator which calls that closure
+    /// on each element.
+    ///
+    /// This is the `async` counterpart of [`Iterator::map`]. To map elements
+    /// with an asynchronous closure, use [`then`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(async_iterator, async_iter_from_iter, async_iter_combinators, noop_waker)]
+    /// use std::async_iter::{self, AsyncIterator};
+    /// # use std::future::Future;
+    /// # use std::pin::pin;
+    /// # use std::task::{Context, Poll, Waker};
+    ///
+    /// # let fut = pin!(async {
+    /// let doubled: Vec<_> = async_iter::from_iter([1, 2, 3]).map(|x| x * 2).collect().await;
+    /// assert_eq!(doubled, [2, 4, 6]);
+    /// # });
+    /// # assert_eq!(fut.poll(&mut Context::from_waker(Waker::noop())), Poll::Ready(()));
+    /// ```
+    ///
+    /// [`then`]: AsyncIterator::then
+    #[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+    fn map<B, F>(self, f: F) -> Map<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item) -> B,
+    {
+        Map::new(self, f)
+    }
+
+    /// Creates an async iterator which uses a closure to determine if an element
+    /// should be yielded.
+    ///
+    /// This is the `async` counterpart of [`Iterator::filter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(async_iterator, async_iter_from_iter, async_iter_combinators, noop_waker)]
+    /// use std::async_iter::{self, AsyncIterator};
+    /// # use std::future::Future;
+    /// # use std::pin::pin;
+    /// # use std::task::{Context, Poll, Waker};
+    ///
+    /// # let fut = pin!(async {
+    /// let evens: Vec<_> = async_iter::from_iter(0..6).filter(|x| x % 2 == 0).collect().await;
+    /// assert_eq!(evens, [0, 2, 4]);
+    /// # });
+    /// # assert_eq!(fut.poll(&mut Context::from_waker(Waker::noop())), Poll::Ready(()));
+    /// ```
+    #[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+    fn filter<P>(self, predicate: P) -> Filter<Self, P>
+    where
+        Self: Sized,
+        P: FnMut(&Self::Item) -> bool,
+    {
+        Filter::new(self, predicate)
+    }
+
+    /// Creates an async iterator that yields the first `n` elements, or fewer
+    /// if the underlying async iterator ends sooner.
+    ///
+    /// This is the `async` counterpart of [`Iterator::take`]. The underlying
+    /// async iterator is not polled again once `n` elements were yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(async_iterator, async_iter_from_iter, async_iter_combinators, noop_waker)]
+    /// use std::async_iter::{self, AsyncIterator};
+    /// # use std::future::Future;
+    /// # use std::pin::pin;
+    /// # use std::task::{Context, Poll, Waker};
+    ///
+    /// # let fut = pin!(async {
+    /// let first: Vec<_> = async_iter::from_iter(1..).take(3).collect().await;
+    /// assert_eq!(first, [1, 2, 3]);
+    /// # });
+    /// # assert_eq!(fut.poll(&mut Context::from_waker(Waker::noop())), Poll::Ready(()));
+    /// ```
+    #[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+    fn take(self, n: usize) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        Take::new(self, n)
+    }
+
+    /// Takes a closure returning a future and creates an async iterator which
+    /// yields the outputs of the futures created for each element.
+    ///
+    /// Each future is awaited to completion before the next element is pulled
+    /// from the underlying async iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(async_iterator, async_iter_from_iter, async_iter_combinators, noop_waker)]
+    /// use std::async_iter::{self, AsyncIterator};
+    /// # use std::future::Future;
+    /// # use std::pin::pin;
+    /// # use std::task::{Context, Poll, Waker};
+    ///
+    /// # let fut = pin!(async {
+    /// async fn square(x: u32) -> u32 {
+    ///     x * x
+    /// }
+    ///
+    /// let squares: Vec<_> = async_iter::from_iter(1..=3).then(square).collect().await;
+    /// assert_eq!(squares, [1, 4, 9]);
+    /// # });
+    /// # assert_eq!(fut.poll(&mut Context::from_waker(Waker::noop())), Poll::Ready(()));
+    /// ```
+    #[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+    fn then<F, Fut>(self, f: F) -> Then<Self, F, Fut>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item) -> Fut,
+        Fut: Future,
+    {
+        Then::new(self, f)
+    }
+
+    /// Returns a future which collects all the items of the async iterator into
+    /// a collection.
+    ///
+    /// Unlike [`Iterator::collect`], the collection is built through its
+    /// [`Default`] and [`Extend`] implementations, one element at a time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(async_iterator, async_iter_from_iter, async_iter_combinators, noop_waker)]
+    /// use std::async_iter::{self, AsyncIterator};
+    /// # use std::future::Future;
+    /// # use std::pin::pin;
+    /// # use std::task::{Context, Poll, Waker};
+    ///
+    /// # let fut = pin!(async {
+    /// let s: String = async_iter::from_iter(['a', 'b', 'c']).collect().await;
+    /// assert_eq!(s, "abc");
+    /// # });
+    /// # assert_eq!(fut.poll(&mut Context::from_waker(Waker::noop())), Poll::Ready(()));
+    /// ```
+    #[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+    fn collect<C>(self) -> Collect<Self, C>
+    where
+        Self: Sized,
+        C: Default + Extend<Self::Item>,
+    {
+        Collect::new(self)
+    }
 }
 
 #[unstable(feature = "async_iterator", issue = "79024")]
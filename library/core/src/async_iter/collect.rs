@@ -0,0 +1,47 @@
+use crate::async_iter::AsyncIterator;
+use crate::future::Future;
+use crate::mem;
+use crate::pin::Pin;
+use crate::task::{ready, Context, Poll};
+
+/// A future which collects all the items of an async iterator into a collection.
+///
+/// This `struct` is created by the [`collect`] method on [`AsyncIterator`]. See
+/// its documentation for more.
+///
+/// [`collect`]: AsyncIterator::collect
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct Collect<I, C> {
+    iter: I,
+    collection: C,
+}
+
+impl<I, C: Default> Collect<I, C> {
+    pub(super) fn new(iter: I) -> Self {
+        Collect { iter, collection: C::default() }
+    }
+}
+
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+impl<I: AsyncIterator, C> Future for Collect<I, C>
+where
+    C: Default + Extend<I::Item>,
+{
+    type Output = C;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<C> {
+        // SAFETY: `iter` is structurally pinned and never moved out of `self`,
+        // while `collection` is never pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        // SAFETY: `iter` is pinned because `self` is, see above.
+        let mut iter = unsafe { Pin::new_unchecked(&mut this.iter) };
+        loop {
+            match ready!(iter.as_mut().poll_next(cx)) {
+                Some(item) => this.collection.extend_one(item),
+                None => return Poll::Ready(mem::take(&mut this.collection)),
+            }
+        }
+    }
+}
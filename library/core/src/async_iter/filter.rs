@@ -0,0 +1,59 @@
+use crate::async_iter::AsyncIterator;
+use crate::fmt;
+use crate::pin::Pin;
+use crate::task::{ready, Context, Poll};
+
+/// An async iterator that filters the elements of `iter` with `predicate`.
+///
+/// This `struct` is created by the [`filter`] method on [`AsyncIterator`]. See its
+/// documentation for more.
+///
+/// [`filter`]: AsyncIterator::filter
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+#[must_use = "async iterators do nothing unless polled"]
+#[derive(Clone)]
+pub struct Filter<I, P> {
+    iter: I,
+    predicate: P,
+}
+
+impl<I, P> Filter<I, P> {
+    pub(super) fn new(iter: I, predicate: P) -> Self {
+        Filter { iter, predicate }
+    }
+}
+
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+impl<I: fmt::Debug, P> fmt::Debug for Filter<I, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Filter").field("iter", &self.iter).finish()
+    }
+}
+
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+impl<I: AsyncIterator, P> AsyncIterator for Filter<I, P>
+where
+    P: FnMut(&I::Item) -> bool,
+{
+    type Item = I::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<I::Item>> {
+        // SAFETY: `iter` is structurally pinned and never moved out of `self`,
+        // while `predicate` is never pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        // SAFETY: `iter` is pinned because `self` is, see above.
+        let mut iter = unsafe { Pin::new_unchecked(&mut this.iter) };
+        loop {
+            match ready!(iter.as_mut().poll_next(cx)) {
+                Some(item) if !(this.predicate)(&item) => continue,
+                item => return Poll::Ready(item),
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.iter.size_hint();
+        (0, upper) // can't know a lower bound, due to the predicate
+    }
+}
@@ -0,0 +1,53 @@
+use crate::async_iter::AsyncIterator;
+use crate::fmt;
+use crate::pin::Pin;
+use crate::task::{Context, Poll};
+
+/// An async iterator that maps the values of `iter` with `f`.
+///
+/// This `struct` is created by the [`map`] method on [`AsyncIterator`]. See its
+/// documentation for more.
+///
+/// [`map`]: AsyncIterator::map
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+#[must_use = "async iterators do nothing unless polled"]
+#[derive(Clone)]
+pub struct Map<I, F> {
+    iter: I,
+    f: F,
+}
+
+impl<I, F> Map<I, F> {
+    pub(super) fn new(iter: I, f: F) -> Self {
+        Map { iter, f }
+    }
+}
+
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+impl<I: fmt::Debug, F> fmt::Debug for Map<I, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Map").field("iter", &self.iter).finish()
+    }
+}
+
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+impl<B, I: AsyncIterator, F> AsyncIterator for Map<I, F>
+where
+    F: FnMut(I::Item) -> B,
+{
+    type Item = B;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<B>> {
+        // SAFETY: `iter` is structurally pinned and never moved out of `self`,
+        // while `f` is never pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        // SAFETY: `iter` is pinned because `self` is, see above.
+        let iter = unsafe { Pin::new_unchecked(&mut this.iter) };
+        iter.poll_next(cx).map(|item| item.map(&mut this.f))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
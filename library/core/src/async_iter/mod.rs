@@ -122,7 +122,25 @@
 //! ```
 
 mod async_iter;
+mod collect;
+mod filter;
 mod from_iter;
+mod map;
+mod next;
+mod take;
+mod then;
 
 pub use async_iter::{AsyncIterator, IntoAsyncIterator};
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+pub use collect::Collect;
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+pub use filter::Filter;
 pub use from_iter::{from_iter, FromIter};
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+pub use map::Map;
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+pub use next::Next;
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+pub use take::Take;
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+pub use then::Then;
@@ -0,0 +1,32 @@
+use crate::async_iter::AsyncIterator;
+use crate::future::Future;
+use crate::pin::Pin;
+use crate::task::{Context, Poll};
+
+/// A future which resolves to the next item of an async iterator.
+///
+/// This `struct` is created by the [`next`] method on [`AsyncIterator`]. See its
+/// documentation for more.
+///
+/// [`next`]: AsyncIterator::next
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct Next<'a, I: ?Sized> {
+    iter: &'a mut I,
+}
+
+impl<'a, I: ?Sized> Next<'a, I> {
+    pub(super) fn new(iter: &'a mut I) -> Self {
+        Next { iter }
+    }
+}
+
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+impl<I: AsyncIterator + Unpin + ?Sized> Future for Next<'_, I> {
+    type Output = Option<I::Item>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.iter).poll_next(cx)
+    }
+}
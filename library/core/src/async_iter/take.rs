@@ -0,0 +1,64 @@
+use crate::async_iter::AsyncIterator;
+use crate::pin::Pin;
+use crate::task::{Context, Poll};
+
+/// An async iterator that only yields the first `n` elements of `iter`.
+///
+/// This `struct` is created by the [`take`] method on [`AsyncIterator`]. See its
+/// documentation for more.
+///
+/// [`take`]: AsyncIterator::take
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+#[must_use = "async iterators do nothing unless polled"]
+#[derive(Clone, Debug)]
+pub struct Take<I> {
+    iter: I,
+    n: usize,
+}
+
+impl<I> Take<I> {
+    pub(super) fn new(iter: I, n: usize) -> Self {
+        Take { iter, n }
+    }
+}
+
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+impl<I: AsyncIterator> AsyncIterator for Take<I> {
+    type Item = I::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<I::Item>> {
+        // SAFETY: `iter` is structurally pinned and never moved out of `self`.
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.n == 0 {
+            return Poll::Ready(None);
+        }
+        // SAFETY: `iter` is pinned because `self` is, see above.
+        let iter = unsafe { Pin::new_unchecked(&mut this.iter) };
+        let item = iter.poll_next(cx);
+        match item {
+            Poll::Ready(Some(_)) => this.n -= 1,
+            // Don't poll `iter` again once it is exhausted.
+            Poll::Ready(None) => this.n = 0,
+            Poll::Pending => {}
+        }
+        item
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.n == 0 {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+
+        let lower = lower.min(self.n);
+
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+
+        (lower, upper)
+    }
+}
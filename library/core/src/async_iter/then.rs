@@ -0,0 +1,71 @@
+use crate::async_iter::AsyncIterator;
+use crate::fmt;
+use crate::future::Future;
+use crate::pin::Pin;
+use crate::task::{ready, Context, Poll};
+
+/// An async iterator that maps the values of `iter` to futures with `f`, and
+/// yields their outputs.
+///
+/// This `struct` is created by the [`then`] method on [`AsyncIterator`]. See its
+/// documentation for more.
+///
+/// [`then`]: AsyncIterator::then
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+#[must_use = "async iterators do nothing unless polled"]
+pub struct Then<I, F, Fut> {
+    iter: I,
+    future: Option<Fut>,
+    f: F,
+}
+
+impl<I, F, Fut> Then<I, F, Fut> {
+    pub(super) fn new(iter: I, f: F) -> Self {
+        Then { iter, future: None, f }
+    }
+}
+
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+impl<I: fmt::Debug, F, Fut: fmt::Debug> fmt::Debug for Then<I, F, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Then").field("iter", &self.iter).field("future", &self.future).finish()
+    }
+}
+
+#[unstable(feature = "async_iter_combinators", reason = "recently added", issue = "none")]
+impl<I: AsyncIterator, F, Fut> AsyncIterator for Then<I, F, Fut>
+where
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future,
+{
+    type Item = Fut::Output;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Fut::Output>> {
+        // SAFETY: `iter` and `future` are structurally pinned and never moved out
+        // of `self`, while `f` is never pinned. `future` is only ever replaced
+        // through `Pin::set`, which drops the old future in place.
+        let this = unsafe { self.get_unchecked_mut() };
+        // SAFETY: `iter` is pinned because `self` is, see above.
+        let mut iter = unsafe { Pin::new_unchecked(&mut this.iter) };
+        // SAFETY: `future` is pinned because `self` is, see above.
+        let mut future = unsafe { Pin::new_unchecked(&mut this.future) };
+        loop {
+            if let Some(fut) = future.as_mut().as_pin_mut() {
+                let output = ready!(fut.poll(cx));
+                future.set(None);
+                return Poll::Ready(Some(output));
+            }
+            match ready!(iter.as_mut().poll_next(cx)) {
+                Some(item) => future.set(Some((this.f)(item))),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending = self.future.is_some() as usize;
+        let (lower, upper) = self.iter.size_hint();
+        (lower.saturating_add(pending), upper.and_then(|upper| upper.checked_add(pending)))
+    }
+}
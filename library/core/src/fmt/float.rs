@@ -229,6 +229,229 @@ fn fmt(&self, fmt: &mut Formatter<'_>) -> Result {
 floating! { f32 }
 floating! { f64 }
 
+/// Writes the formatted parts into `out`, returning the written prefix.
+fn write_formatted<'a>(formatted: &numfmt::Formatted<'_>, out: &'a mut [u8]) -> Option<&'a str> {
+    let len = formatted.write(out)?;
+    // SAFETY: `flt2dec` only produces ASCII characters.
+    Some(unsafe { crate::str::from_utf8_unchecked(&out[..len]) })
+}
+
+/// Writes the formatted parts into `out`, which is large enough for them, returning the
+/// written prefix.
+fn write_formatted_uninit<'a>(
+    formatted: &numfmt::Formatted<'_>,
+    out: &'a mut [MaybeUninit<u8>],
+) -> &'a str {
+    let mut len = 0;
+    let mut push = |bytes: &[u8]| {
+        MaybeUninit::copy_from_slice(&mut out[len..len + bytes.len()], bytes);
+        len += bytes.len();
+    };
+    push(formatted.sign.as_bytes());
+    for part in formatted.parts {
+        match *part {
+            numfmt::Part::Zero(nzeroes) => {
+                for _ in 0..nzeroes {
+                    push(b"0");
+                }
+            }
+            numfmt::Part::Num(_) => {
+                // A `Num` part has at most 5 digits.
+                let mut digits = [0; 5];
+                let n = part.write(&mut digits).unwrap_or(0);
+                push(&digits[..n]);
+            }
+            numfmt::Part::Copy(bytes) => push(bytes),
+        }
+    }
+    // SAFETY: `out[..len]` is initialized, and `flt2dec` only produces ASCII characters.
+    unsafe { crate::str::from_utf8_unchecked(MaybeUninit::slice_assume_init_ref(&out[..len])) }
+}
+
+#[inline(never)]
+fn float_to_decimal_exact_into<T>(num: T, precision: usize, out: &mut [u8]) -> Option<&str>
+where
+    T: flt2dec::DecodableFloat,
+{
+    let mut buf: [MaybeUninit<u8>; 1024] = MaybeUninit::uninit_array(); // enough for f32 and f64
+    let mut parts: [MaybeUninit<numfmt::Part<'_>>; 4] = MaybeUninit::uninit_array();
+    let formatted = flt2dec::to_exact_fixed_str(
+        flt2dec::strategy::grisu::format_exact,
+        num,
+        flt2dec::Sign::Minus,
+        precision,
+        &mut buf,
+        &mut parts,
+    );
+    write_formatted(&formatted, out)
+}
+
+#[inline(never)]
+fn float_to_decimal_shortest_into<T>(num: T, out: &mut [MaybeUninit<u8>]) -> &str
+where
+    T: flt2dec::DecodableFloat,
+{
+    // enough for f32 and f64
+    let mut buf: [MaybeUninit<u8>; flt2dec::MAX_SIG_DIGITS] = MaybeUninit::uninit_array();
+    let mut parts: [MaybeUninit<numfmt::Part<'_>>; 4] = MaybeUninit::uninit_array();
+    let formatted = flt2dec::to_shortest_str(
+        flt2dec::strategy::grisu::format_shortest,
+        num,
+        flt2dec::Sign::Minus,
+        0,
+        &mut buf,
+        &mut parts,
+    );
+    write_formatted_uninit(&formatted, out)
+}
+
+#[inline(never)]
+fn float_to_exponential_exact_into<T>(num: T, precision: usize, out: &mut [u8]) -> Option<&str>
+where
+    T: flt2dec::DecodableFloat,
+{
+    let mut buf: [MaybeUninit<u8>; 1024] = MaybeUninit::uninit_array(); // enough for f32 and f64
+    let mut parts: [MaybeUninit<numfmt::Part<'_>>; 6] = MaybeUninit::uninit_array();
+    // 1 integral digit + `precision` fractional digits = `precision + 1` total digits
+    let formatted = flt2dec::to_exact_exp_str(
+        flt2dec::strategy::grisu::format_exact,
+        num,
+        flt2dec::Sign::Minus,
+        precision + 1,
+        false,
+        &mut buf,
+        &mut parts,
+    );
+    write_formatted(&formatted, out)
+}
+
+#[inline(never)]
+fn float_to_exponential_shortest_into<T>(num: T, out: &mut [MaybeUninit<u8>]) -> &str
+where
+    T: flt2dec::DecodableFloat,
+{
+    // enough for f32 and f64
+    let mut buf: [MaybeUninit<u8>; flt2dec::MAX_SIG_DIGITS] = MaybeUninit::uninit_array();
+    let mut parts: [MaybeUninit<numfmt::Part<'_>>; 6] = MaybeUninit::uninit_array();
+    let formatted = flt2dec::to_shortest_exp_str(
+        flt2dec::strategy::grisu::format_shortest,
+        num,
+        flt2dec::Sign::Minus,
+        (0, 0),
+        false,
+        &mut buf,
+        &mut parts,
+    );
+    write_formatted_uninit(&formatted, out)
+}
+
+macro_rules! format_into {
+    ($ty:ident, $shortest_len:literal, $shortest_exp_len:literal) => {
+        impl $ty {
+            /// The maximum number of bytes written by
+            #[doc = concat!("[`", stringify!($ty), "::format_shortest_into`].")]
+            #[unstable(feature = "num_format_into", issue = "none")]
+            pub const MAX_SHORTEST_LEN: usize = $shortest_len;
+
+            /// The maximum number of bytes written by
+            #[doc = concat!("[`", stringify!($ty), "::format_shortest_exp_into`].")]
+            #[unstable(feature = "num_format_into", issue = "none")]
+            pub const MAX_SHORTEST_EXP_LEN: usize = $shortest_exp_len;
+
+            /// Writes the shortest decimal representation that round-trips to `self` into `buf`
+            /// and returns the written prefix.
+            ///
+            /// The output is identical to that of the [`Display`] implementation without a
+            /// precision. `buf` has room for the longest output, so this can't fail.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// #![feature(num_format_into)]
+            /// use std::mem::MaybeUninit;
+            ///
+            #[doc = concat!("let mut buf = [MaybeUninit::uninit(); ", stringify!($ty), "::MAX_SHORTEST_LEN];")]
+            #[doc = concat!("assert_eq!(1.5", stringify!($ty), ".format_shortest_into(&mut buf), \"1.5\");")]
+            #[doc = concat!("assert_eq!((-1e7", stringify!($ty), ").format_shortest_into(&mut buf), \"-10000000\");")]
+            /// ```
+            #[unstable(feature = "num_format_into", issue = "none")]
+            pub fn format_shortest_into(
+                self,
+                buf: &mut [MaybeUninit<u8>; $ty::MAX_SHORTEST_LEN],
+            ) -> &str {
+                float_to_decimal_shortest_into(self, buf)
+            }
+
+            /// Writes the shortest exponential representation that round-trips to `self` into
+            /// `buf` and returns the written prefix.
+            ///
+            /// The output is identical to that of the [`LowerExp`] implementation without a
+            /// precision. `buf` has room for the longest output, so this can't fail.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// #![feature(num_format_into)]
+            /// use std::mem::MaybeUninit;
+            ///
+            #[doc = concat!("let mut buf = [MaybeUninit::uninit(); ", stringify!($ty), "::MAX_SHORTEST_EXP_LEN];")]
+            #[doc = concat!("assert_eq!(1500.0", stringify!($ty), ".format_shortest_exp_into(&mut buf), \"1.5e3\");")]
+            /// ```
+            #[unstable(feature = "num_format_into", issue = "none")]
+            pub fn format_shortest_exp_into(
+                self,
+                buf: &mut [MaybeUninit<u8>; $ty::MAX_SHORTEST_EXP_LEN],
+            ) -> &str {
+                float_to_exponential_shortest_into(self, buf)
+            }
+
+            /// Writes `self` with exactly `precision` fractional digits into `buf` and returns
+            /// the written prefix, or `None` if `buf` is too small.
+            ///
+            /// The output is identical to that of the [`Display`] implementation with the given
+            /// precision, i.e. `format!("{:.*}", precision, self)`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// #![feature(num_format_into)]
+            ///
+            /// let mut buf = [0; 8];
+            #[doc = concat!("assert_eq!(0.125", stringify!($ty), ".format_fixed_into(2, &mut buf), Some(\"0.12\"));")]
+            #[doc = concat!("assert_eq!(0.125", stringify!($ty), ".format_fixed_into(7, &mut buf), None);")]
+            /// ```
+            #[unstable(feature = "num_format_into", issue = "none")]
+            pub fn format_fixed_into(self, precision: usize, buf: &mut [u8]) -> Option<&str> {
+                float_to_decimal_exact_into(self, precision, buf)
+            }
+
+            /// Writes `self` in exponential form with exactly `precision` fractional digits into
+            /// `buf` and returns the written prefix, or `None` if `buf` is too small.
+            ///
+            /// The output is identical to that of the [`LowerExp`] implementation with the given
+            /// precision, i.e. `format!("{:.*e}", precision, self)`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// #![feature(num_format_into)]
+            ///
+            /// let mut buf = [0; 8];
+            #[doc = concat!("assert_eq!(1234.5", stringify!($ty), ".format_exp_into(2, &mut buf), Some(\"1.23e3\"));")]
+            /// ```
+            #[unstable(feature = "num_format_into", issue = "none")]
+            pub fn format_exp_into(self, precision: usize, buf: &mut [u8]) -> Option<&str> {
+                float_to_exponential_exact_into(self, precision, buf)
+            }
+        }
+    };
+}
+
+// The longest outputs are tiny negative values printed in full (`-0.000…22250738585072014`)
+// and negative exponentials with the most significant digits (`-2.2250738585072014e-308`).
+format_into! { f32, 48, 15 }
+format_into! { f64, 327, 24 }
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl Debug for f16 {
     #[inline]
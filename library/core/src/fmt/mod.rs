@@ -40,7 +40,298 @@ pub enum Alignment {
 pub use self::builders::{DebugList, DebugMap, DebugSet, DebugStruct, DebugTuple};
 
 #[unstable(feature = "debug_closure_helpers", issue = "117729")]
-pub use self::builders::FormatterFn;
+pub use self::builders::{from_fn, FormatterFn};
+
+/// The signedness of a [`Formatter`] (or of a [`FormattingOptions`]).
+#[unstable(feature = "formatting_options", issue = "none")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Sign {
+    /// Represents the `+` flag.
+    Plus,
+    /// Represents the `-` flag.
+    Minus,
+}
+
+/// Specifies whether the [`Debug`] trait should use lower-/upper-case
+/// hexadecimal or normal integers.
+#[unstable(feature = "formatting_options", issue = "none")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DebugAsHex {
+    /// Use lower-case hexadecimal integers for the `Debug` trait (like [the `x?` type](../../std/fmt/index.html#formatting-traits)).
+    Lower,
+    /// Use upper-case hexadecimal integers for the `Debug` trait (like [the `X?` type](../../std/fmt/index.html#formatting-traits)).
+    Upper,
+}
+
+/// Options for formatting.
+///
+/// `FormattingOptions` is a [`Formatter`] without an attached [`Write`] trait.
+/// It is mainly used to construct `Formatter` instances, either from scratch with
+/// [`FormattingOptions::create_formatter`] or by deriving one from an existing
+/// `Formatter` with [`Formatter::with_options`], e.g. to format an inner value of a
+/// composite with different flags.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(formatting_options)]
+/// use std::fmt::{self, Alignment};
+///
+/// struct Point(i32, i32);
+///
+/// impl fmt::Display for Point {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         // Pad each coordinate to the requested width, instead of the whole point.
+///         let mut options = f.options();
+///         options.align(Some(Alignment::Right));
+///         write!(f, "(")?;
+///         fmt::Display::fmt(&self.0, &mut f.with_options(options))?;
+///         write!(f, ", ")?;
+///         fmt::Display::fmt(&self.1, &mut f.with_options(options))?;
+///         write!(f, ")")
+///     }
+/// }
+///
+/// assert_eq!(format!("{:3}", Point(1, -2)), "(  1,  -2)");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[unstable(feature = "formatting_options", issue = "none")]
+pub struct FormattingOptions {
+    flags: u32,
+    fill: char,
+    align: Option<Alignment>,
+    width: Option<usize>,
+    precision: Option<usize>,
+}
+
+impl FormattingOptions {
+    /// Construct a new `FormattingOptions` that is equivalent to the `{}`
+    /// formatting specifier:
+    ///
+    /// - no flags,
+    /// - filled with spaces,
+    /// - no alignment,
+    /// - no width,
+    /// - no precision, and
+    /// - no [`DebugAsHex`] output mode.
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub const fn new() -> Self {
+        Self { flags: 0, fill: ' ', align: None, width: None, precision: None }
+    }
+
+    #[inline]
+    fn set_flag(&mut self, flag: rt::Flag, set: bool) {
+        if set {
+            self.flags |= 1 << flag as u32;
+        } else {
+            self.flags &= !(1 << flag as u32);
+        }
+    }
+
+    #[inline]
+    fn flag(&self, flag: rt::Flag) -> bool {
+        self.flags & (1 << flag as u32) != 0
+    }
+
+    /// Sets or removes the sign (the `+` or the `-` flag).
+    ///
+    /// - `+`: This is intended for numeric types and indicates that the sign
+    /// should always be printed. By default only the negative sign of signed
+    /// values is printed, and the sign of positive or unsigned values is
+    /// omitted. This flag indicates that the correct sign (+ or -) should
+    /// always be printed.
+    /// - `-`: Currently not used
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub fn sign(&mut self, sign: Option<Sign>) -> &mut Self {
+        self.set_flag(rt::Flag::SignPlus, sign == Some(Sign::Plus));
+        self.set_flag(rt::Flag::SignMinus, sign == Some(Sign::Minus));
+        self
+    }
+
+    /// Sets or unsets the `0` flag.
+    ///
+    /// This is used to indicate for integer formats that the padding to width should both be done
+    /// with a 0 character as well as be sign-aware
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub fn sign_aware_zero_pad(&mut self, sign_aware_zero_pad: bool) -> &mut Self {
+        self.set_flag(rt::Flag::SignAwareZeroPad, sign_aware_zero_pad);
+        self
+    }
+
+    /// Sets or unsets the `#` flag.
+    ///
+    /// This flag indicates that the "alternate" form of printing should be
+    /// used. The alternate forms are:
+    /// - [`Debug`] : pretty-print the [`Debug`] formatting (adds linebreaks and indentation)
+    /// - [`LowerHex`] as well as [`UpperHex`] - precedes the argument with a `0x`
+    /// - [`Octal`] - precedes the argument with a `0o`
+    /// - [`Binary`] - precedes the argument with a `0b`
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub fn alternate(&mut self, alternate: bool) -> &mut Self {
+        self.set_flag(rt::Flag::Alternate, alternate);
+        self
+    }
+
+    /// Sets the fill character.
+    ///
+    /// The optional fill character and alignment is provided normally in
+    /// conjunction with the width parameter. This indicates that if the value
+    /// being formatted is smaller than width some extra characters will be
+    /// printed around it.
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub fn fill(&mut self, fill: char) -> &mut Self {
+        self.fill = fill;
+        self
+    }
+
+    /// Sets or removes the alignment.
+    ///
+    /// The alignment specifies how the value being formatted should be
+    /// positioned if it is smaller than the width of the formatter.
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub fn align(&mut self, align: Option<Alignment>) -> &mut Self {
+        self.align = align;
+        self
+    }
+
+    /// Sets or removes the width.
+    ///
+    /// This is a parameter for the "minimum width" that the format should take
+    /// up. If the value's string does not fill up this many characters, then
+    /// the padding specified by [`FormattingOptions::fill`]/[`FormattingOptions::align`]
+    /// will be used to take up the required space.
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub fn width(&mut self, width: Option<usize>) -> &mut Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets or removes the precision.
+    ///
+    /// - For non-numeric types, this can be considered a "maximum width". If
+    /// the resulting string is longer than this width, then it is truncated
+    /// down to this many characters and that truncated value is emitted with
+    /// proper fill, alignment and width if those parameters are set.
+    /// - For integral types, this is ignored.
+    /// - For floating-point types, this indicates how many digits after the
+    /// decimal point should be printed.
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub fn precision(&mut self, precision: Option<usize>) -> &mut Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Specifies whether the [`Debug`] trait should use lower-/upper-case
+    /// hexadecimal or normal integers
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub fn debug_as_hex(&mut self, debug_as_hex: Option<DebugAsHex>) -> &mut Self {
+        self.set_flag(rt::Flag::DebugLowerHex, debug_as_hex == Some(DebugAsHex::Lower));
+        self.set_flag(rt::Flag::DebugUpperHex, debug_as_hex == Some(DebugAsHex::Upper));
+        self
+    }
+
+    /// Returns the current sign (the `+` or the `-` flag).
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub const fn get_sign(&self) -> Option<Sign> {
+        const SIGN_PLUS: u32 = 1 << rt::Flag::SignPlus as u32;
+        const SIGN_MINUS: u32 = 1 << rt::Flag::SignMinus as u32;
+        match self.flags & (SIGN_PLUS | SIGN_MINUS) {
+            SIGN_PLUS => Some(Sign::Plus),
+            SIGN_MINUS => Some(Sign::Minus),
+            _ => None,
+        }
+    }
+
+    /// Returns the current `0` flag.
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub fn get_sign_aware_zero_pad(&self) -> bool {
+        self.flag(rt::Flag::SignAwareZeroPad)
+    }
+
+    /// Returns the current `#` flag.
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub fn get_alternate(&self) -> bool {
+        self.flag(rt::Flag::Alternate)
+    }
+
+    /// Returns the current fill character.
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub const fn get_fill(&self) -> char {
+        self.fill
+    }
+
+    /// Returns the current alignment.
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub const fn get_align(&self) -> Option<Alignment> {
+        self.align
+    }
+
+    /// Returns the current width.
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub const fn get_width(&self) -> Option<usize> {
+        self.width
+    }
+
+    /// Returns the current precision.
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub const fn get_precision(&self) -> Option<usize> {
+        self.precision
+    }
+
+    /// Returns the current [`DebugAsHex`] output mode.
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub fn get_debug_as_hex(&self) -> Option<DebugAsHex> {
+        if self.flag(rt::Flag::DebugLowerHex) {
+            Some(DebugAsHex::Lower)
+        } else if self.flag(rt::Flag::DebugUpperHex) {
+            Some(DebugAsHex::Upper)
+        } else {
+            None
+        }
+    }
+
+    /// Creates a [`Formatter`] that writes its output to the given [`Write`] trait.
+    ///
+    /// You may alternatively use [`Formatter::with_options`] to derive a formatter
+    /// writing to the same output as an existing one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(formatting_options)]
+    /// use std::fmt::{self, FormattingOptions};
+    ///
+    /// let mut s = String::new();
+    /// let mut options = FormattingOptions::new();
+    /// options.width(Some(5)).sign_aware_zero_pad(true);
+    /// fmt::Display::fmt(&-12, &mut options.create_formatter(&mut s)).unwrap();
+    /// assert_eq!(s, "-0012");
+    /// ```
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub fn create_formatter<'a>(self, write: &'a mut (dyn Write + 'a)) -> Formatter<'a> {
+        Formatter {
+            flags: self.flags,
+            fill: self.fill,
+            align: match self.align {
+                Some(Alignment::Left) => rt::Alignment::Left,
+                Some(Alignment::Right) => rt::Alignment::Right,
+                Some(Alignment::Center) => rt::Alignment::Center,
+                None => rt::Alignment::Unknown,
+            },
+            width: self.width,
+            precision: self.precision,
+            buf: write,
+        }
+    }
+}
+
+#[unstable(feature = "formatting_options", issue = "none")]
+impl Default for FormattingOptions {
+    /// Same as [`FormattingOptions::new()`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 /// The type returned by formatter methods.
 ///
@@ -293,6 +584,50 @@ pub fn new(buf: &'a mut (dyn Write + 'a)) -> Formatter<'a> {
             buf,
         }
     }
+
+    /// Returns the formatting options this formatter was created with.
+    ///
+    /// These can be modified and passed to [`Formatter::with_options`] to format
+    /// sub-values with different flags.
+    #[must_use]
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub fn options(&self) -> FormattingOptions {
+        FormattingOptions {
+            flags: self.flags,
+            fill: self.fill,
+            align: self.align(),
+            width: self.width,
+            precision: self.precision,
+        }
+    }
+
+    /// Creates a new formatter that writes to the same output as `self`, but with
+    /// the given options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(formatting_options)]
+    /// use std::fmt;
+    ///
+    /// struct Hex(u32);
+    ///
+    /// impl fmt::Display for Hex {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         // Always use the alternate form, keeping the other options.
+    ///         let mut options = f.options();
+    ///         options.alternate(true);
+    ///         fmt::LowerHex::fmt(&self.0, &mut f.with_options(options))
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(format!("{:>8}", Hex(255)), "    0xff");
+    /// assert_eq!(format!("{}", Hex(255)), "0xff");
+    /// ```
+    #[unstable(feature = "formatting_options", issue = "none")]
+    pub fn with_options<'b>(&'b mut self, options: FormattingOptions) -> Formatter<'b> {
+        options.create_formatter(self.buf)
+    }
 }
 
 /// This structure represents a safely precompiled version of a format string
@@ -51,6 +51,14 @@ fn to_u128(&self) -> u128 { *self as u128 }
 impl_int! { i8 i16 i32 i64 i128 isize }
 impl_uint! { u8 u16 u32 u64 u128 usize }
 
+/// An integer that can write its decimal digits into a buffer without a `Formatter`.
+#[doc(hidden)]
+trait DecimalInto {
+    /// Writes the decimal digits of the magnitude into the end of `buf`, returning whether the
+    /// value is nonnegative along with the digits.
+    fn decimal_into(self, buf: &mut [MaybeUninit<u8>; 39]) -> (bool, &str);
+}
+
 /// A type that represents a specific radix
 ///
 /// # Safety
@@ -211,13 +219,11 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
       8081828384858687888990919293949596979899";
 
 macro_rules! impl_Display {
-    ($($t:ident),* as $u:ident via $conv_fn:ident named $name:ident) => {
+    ($($t:ident),* as $u:ident via $conv_fn:ident named $name:ident into $into:ident) => {
         #[cfg(not(feature = "optimize_for_size"))]
-        fn $name(mut n: $u, is_nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            // 2^128 is about 3*10^38, so 39 gives an extra byte of space
-            let mut buf = [MaybeUninit::<u8>::uninit(); 39];
+        fn $into(mut n: $u, buf: &mut [MaybeUninit<u8>; 39]) -> &str {
             let mut curr = buf.len();
-            let buf_ptr = MaybeUninit::slice_as_mut_ptr(&mut buf);
+            let buf_ptr = MaybeUninit::slice_as_mut_ptr(buf);
             let lut_ptr = DEC_DIGITS_LUT.as_ptr();
 
             // SAFETY: Since `d1` and `d2` are always less than or equal to `198`, we
@@ -271,19 +277,16 @@ fn $name(mut n: $u, is_nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::Re
 
             // SAFETY: `curr` > 0 (since we made `buf` large enough), and all the chars are valid
             // UTF-8 since `DEC_DIGITS_LUT` is
-            let buf_slice = unsafe {
+            unsafe {
                 str::from_utf8_unchecked(
                     slice::from_raw_parts(buf_ptr.add(curr), buf.len() - curr))
-            };
-            f.pad_integral(is_nonnegative, "", buf_slice)
+            }
         }
 
         #[cfg(feature = "optimize_for_size")]
-        fn $name(mut n: $u, is_nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            // 2^128 is about 3*10^38, so 39 gives an extra byte of space
-            let mut buf = [MaybeUninit::<u8>::uninit(); 39];
+        fn $into(mut n: $u, buf: &mut [MaybeUninit<u8>; 39]) -> &str {
             let mut curr = buf.len();
-            let buf_ptr = MaybeUninit::slice_as_mut_ptr(&mut buf);
+            let buf_ptr = MaybeUninit::slice_as_mut_ptr(buf);
 
             // SAFETY: To show that it's OK to copy into `buf_ptr`, notice that at the beginning
             // `curr == buf.len() == 39 > log(n)` since `n < 2^128 < 10^39`, and at
@@ -303,11 +306,16 @@ fn $name(mut n: $u, is_nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::Re
             }
 
             // SAFETY: `curr` > 0 (since we made `buf` large enough), and all the chars are valid UTF-8
-            let buf_slice = unsafe {
+            unsafe {
                 str::from_utf8_unchecked(
                     slice::from_raw_parts(buf_ptr.add(curr), buf.len() - curr))
-            };
-            f.pad_integral(is_nonnegative, "", buf_slice)
+            }
+        }
+
+        fn $name(n: $u, is_nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            // 2^128 is about 3*10^38, so 39 gives an extra byte of space
+            let mut buf = [MaybeUninit::<u8>::uninit(); 39];
+            f.pad_integral(is_nonnegative, "", $into(n, &mut buf))
         }
 
         $(#[stable(feature = "rust1", since = "1.0.0")]
@@ -323,6 +331,19 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 };
                 $name(n, is_nonnegative, f)
             }
+        }
+
+        impl DecimalInto for $t {
+            #[allow(unused_comparisons)]
+            fn decimal_into(self, buf: &mut [MaybeUninit<u8>; 39]) -> (bool, &str) {
+                let is_nonnegative = self >= 0;
+                let n = if is_nonnegative {
+                    self.$conv_fn()
+                } else {
+                    (!self.$conv_fn()).wrapping_add(1)
+                };
+                (is_nonnegative, $into(n, buf))
+            }
         })*
     };
 }
@@ -503,7 +524,7 @@ mod imp {
     use super::*;
     impl_Display!(
         i8, u8, i16, u16, i32, u32, i64, u64, usize, isize
-            as u64 via to_u64 named fmt_u64
+            as u64 via to_u64 named fmt_u64 into fmt_u64_into
     );
     impl_Exp!(
         i8, u8, i16, u16, i32, u32, i64, u64, usize, isize
@@ -514,8 +535,11 @@ mod imp {
 #[cfg(not(any(target_pointer_width = "64", target_arch = "wasm32")))]
 mod imp {
     use super::*;
-    impl_Display!(i8, u8, i16, u16, i32, u32, isize, usize as u32 via to_u32 named fmt_u32);
-    impl_Display!(i64, u64 as u64 via to_u64 named fmt_u64);
+    impl_Display!(
+        i8, u8, i16, u16, i32, u32, isize, usize
+            as u32 via to_u32 named fmt_u32 into fmt_u32_into
+    );
+    impl_Display!(i64, u64 as u64 via to_u64 named fmt_u64 into fmt_u64_into);
     impl_Exp!(i8, u8, i16, u16, i32, u32, isize, usize as u32 via to_u32 named exp_u32);
     impl_Exp!(i64, u64 as u64 via to_u64 named exp_u64);
 }
@@ -629,6 +653,20 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+impl DecimalInto for u128 {
+    fn decimal_into(self, buf: &mut [MaybeUninit<u8>; 39]) -> (bool, &str) {
+        (true, fmt_u128_into(self, buf))
+    }
+}
+
+impl DecimalInto for i128 {
+    fn decimal_into(self, buf: &mut [MaybeUninit<u8>; 39]) -> (bool, &str) {
+        let is_nonnegative = self >= 0;
+        let n = if is_nonnegative { self.to_u128() } else { (!self.to_u128()).wrapping_add(1) };
+        (is_nonnegative, fmt_u128_into(n, buf))
+    }
+}
+
 /// Specialized optimization for u128. Instead of taking two items at a time, it splits
 /// into at most 2 u64s, and then chunks by 10e16, 10e8, 10e4, 10e2, and then 10e1.
 /// It also has to handle 1 last item, as 10^40 > 2^128 > 10^39, whereas
@@ -636,10 +674,14 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 fn fmt_u128(n: u128, is_nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     // 2^128 is about 3*10^38, so 39 gives an extra byte of space
     let mut buf = [MaybeUninit::<u8>::uninit(); 39];
+    f.pad_integral(is_nonnegative, "", fmt_u128_into(n, &mut buf))
+}
+
+fn fmt_u128_into(n: u128, buf: &mut [MaybeUninit<u8>; 39]) -> &str {
     let mut curr = buf.len();
 
     let (n, rem) = udiv_1e19(n);
-    parse_u64_into(rem, &mut buf, &mut curr);
+    parse_u64_into(rem, buf, &mut curr);
 
     if n != 0 {
         // 0 pad up to point
@@ -647,22 +689,18 @@ fn fmt_u128(n: u128, is_nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::R
         // SAFETY: Guaranteed that we wrote at most 19 bytes, and there must be space
         // remaining since it has length 39
         unsafe {
-            ptr::write_bytes(
-                MaybeUninit::slice_as_mut_ptr(&mut buf).add(target),
-                b'0',
-                curr - target,
-            );
+            ptr::write_bytes(MaybeUninit::slice_as_mut_ptr(buf).add(target), b'0', curr - target);
         }
         curr = target;
 
         let (n, rem) = udiv_1e19(n);
-        parse_u64_into(rem, &mut buf, &mut curr);
+        parse_u64_into(rem, buf, &mut curr);
         // Should this following branch be annotated with unlikely?
         if n != 0 {
             let target = buf.len() - 38;
             // The raw `buf_ptr` pointer is only valid until `buf` is used the next time,
             // buf `buf` is not used in this scope so we are good.
-            let buf_ptr = MaybeUninit::slice_as_mut_ptr(&mut buf);
+            let buf_ptr = MaybeUninit::slice_as_mut_ptr(buf);
             // SAFETY: At this point we wrote at most 38 bytes, pad up to that point,
             // There can only be at most 1 digit remaining.
             unsafe {
@@ -675,13 +713,12 @@ fn fmt_u128(n: u128, is_nonnegative: bool, f: &mut fmt::Formatter<'_>) -> fmt::R
 
     // SAFETY: `curr` > 0 (since we made `buf` large enough), and all the chars are valid
     // UTF-8 since `DEC_DIGITS_LUT` is
-    let buf_slice = unsafe {
+    unsafe {
         str::from_utf8_unchecked(slice::from_raw_parts(
-            MaybeUninit::slice_as_mut_ptr(&mut buf).add(curr),
+            MaybeUninit::slice_as_mut_ptr(buf).add(curr),
             buf.len() - curr,
         ))
-    };
-    f.pad_integral(is_nonnegative, "", buf_slice)
+    }
 }
 
 /// Partition of `n` into n > 1e19 and rem <= 1e19
@@ -724,3 +761,139 @@ fn u128_mulhi(x: u128, y: u128) -> u128 {
 
     x_hi as u128 * y_hi as u128 + high1 + high2
 }
+
+/// Writes `digits`, preceded by a `-` if `is_nonnegative` is false, into `out`.
+fn write_int_into<'a>(is_nonnegative: bool, digits: &str, out: &'a mut [u8]) -> Option<&'a str> {
+    let sign = if is_nonnegative { "" } else { "-" };
+    let len = sign.len() + digits.len();
+    let out = out.get_mut(..len)?;
+    out[..sign.len()].copy_from_slice(sign.as_bytes());
+    out[sign.len()..].copy_from_slice(digits.as_bytes());
+    // SAFETY: `out` is a copy of the ASCII `sign` followed by the ASCII `digits`.
+    Some(unsafe { str::from_utf8_unchecked(out) })
+}
+
+/// Writes `digits`, preceded by a `-` if `is_nonnegative` is false, into `out`, which is
+/// large enough for them.
+fn write_int_into_uninit<'a>(
+    is_nonnegative: bool,
+    digits: &str,
+    out: &'a mut [MaybeUninit<u8>],
+) -> &'a str {
+    let sign = if is_nonnegative { "" } else { "-" };
+    let len = sign.len() + digits.len();
+    let (sign_out, digits_out) = out[..len].split_at_mut(sign.len());
+    MaybeUninit::copy_from_slice(sign_out, sign.as_bytes());
+    MaybeUninit::copy_from_slice(digits_out, digits.as_bytes());
+    // SAFETY: `out[..len]` now holds the ASCII `sign` followed by the ASCII `digits`.
+    unsafe { str::from_utf8_unchecked(MaybeUninit::slice_assume_init_ref(&out[..len])) }
+}
+
+/// Writes `x` in the given radix into `out`, using lower-case letters for digits above 9.
+fn fmt_radix_into<T: DisplayInt>(mut x: T, radix: u32, out: &mut [u8]) -> Option<&str> {
+    const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    assert!(
+        (2..=36).contains(&radix),
+        "format_radix_into: radix must lie in the range `[2, 36]` - found {}",
+        radix
+    );
+
+    // The radix can be as low as 2, so we need a buffer of at least 128
+    // characters for a base 2 number.
+    let zero = T::zero();
+    let is_nonnegative = x >= zero;
+    let mut buf = [0u8; 128];
+    let mut curr = buf.len();
+    let base = T::from_u8(radix as u8);
+    for byte in buf.iter_mut().rev() {
+        let n = if is_nonnegative { x % base } else { zero - (x % base) };
+        x = x / base;
+        *byte = DIGITS[n.to_u8() as usize];
+        curr -= 1;
+        if x == zero {
+            break;
+        }
+    }
+    // SAFETY: The only bytes in `buf[curr..]` are copied from the ASCII `DIGITS`.
+    let digits = unsafe { str::from_utf8_unchecked(&buf[curr..]) };
+    write_int_into(is_nonnegative, digits, out)
+}
+
+/// Returns the length of the decimal representation of `n`.
+const fn decimal_len(mut n: u128) -> usize {
+    let mut len = 1;
+    while n >= 10 {
+        n /= 10;
+        len += 1;
+    }
+    len
+}
+
+macro_rules! format_into {
+    ($($t:ident)*) => {$(
+        impl $t {
+            /// The maximum number of bytes written by
+            #[doc = concat!("[`", stringify!($t), "::format_into`].")]
+            #[unstable(feature = "num_format_into", issue = "none")]
+            #[allow(unused_comparisons)]
+            pub const MAX_DECIMAL_LEN: usize =
+                decimal_len($t::MAX as u128) + if $t::MIN < 0 { 1 } else { 0 };
+
+            /// Writes the decimal representation of `self` into `buf` and returns the written
+            /// prefix.
+            ///
+            /// The output is identical to that of the [`Display`](fmt::Display) implementation.
+            /// `buf` has room for the longest output, so this can't fail.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// #![feature(num_format_into)]
+            /// use std::mem::MaybeUninit;
+            ///
+            #[doc = concat!("let mut buf = [MaybeUninit::uninit(); ", stringify!($t), "::MAX_DECIMAL_LEN];")]
+            #[doc = concat!("assert_eq!(", stringify!($t), "::MAX.format_into(&mut buf), ", stringify!($t), "::MAX.to_string());")]
+            #[doc = concat!("assert_eq!(", stringify!($t), "::MIN.format_into(&mut buf), ", stringify!($t), "::MIN.to_string());")]
+            /// ```
+            #[unstable(feature = "num_format_into", issue = "none")]
+            pub fn format_into(self, buf: &mut [MaybeUninit<u8>; $t::MAX_DECIMAL_LEN]) -> &str {
+                let mut digits = [MaybeUninit::<u8>::uninit(); 39];
+                let (is_nonnegative, digits) = self.decimal_into(&mut digits);
+                write_int_into_uninit(is_nonnegative, digits, buf)
+            }
+
+            /// Writes `self` in the given radix into `buf` and returns the written prefix, or
+            /// `None` if `buf` is too small.
+            ///
+            /// Digits above 9 are written as lower-case letters and negative values are written
+            /// with a leading `-`, so the output is accepted by
+            #[doc = concat!("[`", stringify!($t), "::from_str_radix`].")]
+            /// A buffer of
+            #[doc = concat!("`", stringify!($t), "::BITS + 1`")]
+            /// bytes is large enough for any radix.
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `radix` is not in the range from 2 to 36.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// #![feature(num_format_into)]
+            ///
+            /// let mut buf = [0; 8];
+            #[doc = concat!("assert_eq!(100", stringify!($t), ".format_radix_into(16, &mut buf), Some(\"64\"));")]
+            #[doc = concat!("assert_eq!(100", stringify!($t), ".format_radix_into(36, &mut buf), Some(\"2s\"));")]
+            #[doc = concat!("assert_eq!(100", stringify!($t), ".format_radix_into(2, &mut buf[..4]), None);")]
+            /// ```
+            #[unstable(feature = "num_format_into", issue = "none")]
+            pub fn format_radix_into(self, radix: u32, buf: &mut [u8]) -> Option<&str> {
+                fmt_radix_into(self, radix, buf)
+            }
+        }
+    )*};
+}
+format_into! {
+  i8 i16 i32 i64 i128 isize
+  u8 u16 u32 u64 u128 usize
+}
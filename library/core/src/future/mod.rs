@@ -19,6 +19,7 @@
 mod pending;
 mod poll_fn;
 mod ready;
+mod select;
 
 #[stable(feature = "futures_api", since = "1.36.0")]
 pub use self::future::Future;
@@ -37,6 +38,9 @@
 #[stable(feature = "future_poll_fn", since = "1.64.0")]
 pub use poll_fn::{poll_fn, PollFn};
 
+#[unstable(feature = "future_select", issue = "none")]
+pub use select::{race, select, Either, Race, Select};
+
 #[unstable(feature = "async_drop", issue = "none")]
 pub use async_drop::{async_drop, async_drop_in_place, AsyncDrop, AsyncDropInPlace};
 
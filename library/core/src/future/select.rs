@@ -0,0 +1,158 @@
+use crate::future::Future;
+use crate::pin::Pin;
+use crate::task::{Context, Poll};
+
+/// The output of a [`select`], holding the output of the future that completed
+/// first alongside the other, unfinished future.
+#[unstable(feature = "future_select", issue = "none")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Either<A, B> {
+    /// The first future completed first.
+    Left(A),
+    /// The second future completed first.
+    Right(B),
+}
+
+/// Waits for either of two futures to complete.
+///
+/// The returned future resolves to [`Either::Left`] with the output of `a`
+/// and the still pending `b` if `a` completes first, or to [`Either::Right`]
+/// with the output of `b` and the still pending `a` otherwise. The unfinished
+/// future can be awaited later on, or dropped to cancel it.
+///
+/// The futures are polled in order, so if both are ready at the same time,
+/// `a` wins. Both futures need to be [`Unpin`] so that they can be handed back;
+/// use [`pin!`] to select over futures that are not.
+///
+/// To wait for either of two futures with the same output, discarding the
+/// other one, use [`race`].
+///
+/// # Examples
+///
+/// ```
+/// #![feature(future_select)]
+/// use std::future::{self, select, Either};
+///
+/// # let _ = async {
+/// let slow = future::pending::<u8>();
+/// let fast = future::ready("done");
+///
+/// match select(slow, fast).await {
+///     Either::Left((_, _fast)) => unreachable!(),
+///     Either::Right((output, _slow)) => assert_eq!(output, "done"),
+/// }
+/// # };
+/// ```
+///
+/// [`pin!`]: crate::pin::pin
+#[unstable(feature = "future_select", issue = "none")]
+pub fn select<A, B>(a: A, b: B) -> Select<A, B>
+where
+    A: Future + Unpin,
+    B: Future + Unpin,
+{
+    Select { inner: Some((a, b)) }
+}
+
+/// A future which waits for either of two futures to complete.
+///
+/// This `struct` is created by the [`select`] function. See its documentation
+/// for more.
+#[unstable(feature = "future_select", issue = "none")]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct Select<A, B> {
+    inner: Option<(A, B)>,
+}
+
+#[unstable(feature = "future_select", issue = "none")]
+impl<A, B> Future for Select<A, B>
+where
+    A: Future + Unpin,
+    B: Future + Unpin,
+{
+    type Output = Either<(A::Output, B), (B::Output, A)>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let (a, b) = self.inner.as_mut().expect("`Select` polled after completion");
+
+        if let Poll::Ready(output) = Pin::new(a).poll(cx) {
+            let (_, b) = self.inner.take().unwrap();
+            return Poll::Ready(Either::Left((output, b)));
+        }
+
+        if let Poll::Ready(output) = Pin::new(b).poll(cx) {
+            let (a, _) = self.inner.take().unwrap();
+            return Poll::Ready(Either::Right((output, a)));
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Waits for either of two futures with the same output to complete, and
+/// returns the output of the one that completes first.
+///
+/// Both futures are dropped as soon as the returned future completes, which
+/// cancels the one that didn't finish. The futures are polled in order, so if
+/// both are ready at the same time, the output of `a` is returned.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(future_select)]
+/// use std::future::{self, race};
+///
+/// # let _ = async {
+/// let output = race(future::pending(), async { 42 }).await;
+/// assert_eq!(output, 42);
+/// # };
+/// ```
+#[unstable(feature = "future_select", issue = "none")]
+pub fn race<A, B>(a: A, b: B) -> Race<A, B>
+where
+    A: Future,
+    B: Future<Output = A::Output>,
+{
+    Race { inner: Some((a, b)) }
+}
+
+/// A future which waits for the first of two futures with the same output.
+///
+/// This `struct` is created by the [`race`] function. See its documentation
+/// for more.
+#[unstable(feature = "future_select", issue = "none")]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct Race<A, B> {
+    inner: Option<(A, B)>,
+}
+
+#[unstable(feature = "future_select", issue = "none")]
+impl<A, B> Future for Race<A, B>
+where
+    A: Future,
+    B: Future<Output = A::Output>,
+{
+    type Output = A::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: both futures are structurally pinned. They are never moved out
+        // of `inner`, only dropped in place along with it.
+        let this = unsafe { self.get_unchecked_mut() };
+        let (a, b) = this.inner.as_mut().expect("`Race` polled after completion");
+
+        // SAFETY: `a` is pinned, see above.
+        let output = match unsafe { Pin::new_unchecked(a) }.poll(cx) {
+            Poll::Ready(output) => output,
+            // SAFETY: `b` is pinned, see above.
+            Poll::Pending => match unsafe { Pin::new_unchecked(b) }.poll(cx) {
+                Poll::Ready(output) => output,
+                Poll::Pending => return Poll::Pending,
+            },
+        };
+        // Cancel the losing future right away rather than when `Race` is dropped.
+        this.inner = None;
+        Poll::Ready(output)
+    }
+}
@@ -68,6 +68,7 @@
 use crate::ptr;
 use crate::ub_checks;
 
+pub mod fallback;
 pub mod mir;
 pub mod simd;
 
@@ -2487,6 +2488,39 @@ pub const fn ptr_guaranteed_cmp<T>(ptr: *const T, other: *const T) -> u8 {
     (ptr == other) as u8
 }
 
+/// Performs full-width multiplication and addition with a carry:
+/// `multiplier * multiplicand + addend + carry`.
+///
+/// This cannot overflow. For `uN`, the largest result is
+/// `MAX * MAX + MAX + MAX = (2ⁿ - 1)² + 2(2ⁿ - 1) = 2²ⁿ - 1`, and the range of
+/// results for `iN` is similarly contained in that of an `i2n`.
+///
+/// The result is returned as its low half, which is always unsigned, and its high half,
+/// which has the signedness of `T`.
+///
+/// Note that, unlike most intrinsics, this is safe to call;
+/// it does not require an `unsafe` block.
+/// Therefore, implementations must not require the user to uphold
+/// any safety invariants.
+///
+/// The unstable versions of this intrinsic are available on the integer
+/// primitives via the `carrying_mul_add` method. For example,
+/// [`u128::carrying_mul_add`]
+#[cfg(not(bootstrap))]
+#[unstable(feature = "core_intrinsics", issue = "none")]
+#[rustc_intrinsic]
+#[rustc_nounwind]
+#[inline]
+#[miri::intrinsic_fallback_is_spec]
+pub fn carrying_mul_add<T: fallback::CarryingMulAdd<Unsigned = U>, U>(
+    multiplier: T,
+    multiplicand: T,
+    addend: T,
+    carry: T,
+) -> (U, T) {
+    multiplier.carrying_mul_add(multiplicand, addend, carry)
+}
+
 extern "rust-intrinsic" {
     /// Determines whether the raw bytes of the two values are equal.
     ///
@@ -0,0 +1,49 @@
+//! Traits describing the portable behaviour of generic intrinsics.
+//!
+//! Intrinsics that are generic over several types, but have a fallback body, need a way for that
+//! body to pick the right implementation for each type. The traits in this module provide it;
+//! they are only implemented for the types the corresponding intrinsic supports.
+
+use crate::num::wide;
+
+/// The fallback implementation of [`carrying_mul_add`](super::carrying_mul_add).
+pub trait CarryingMulAdd: Copy + 'static {
+    /// The type of the low half of the result.
+    type Unsigned: Copy + 'static;
+
+    /// Returns the low and high halves of `self * multiplicand + addend + carry`.
+    fn carrying_mul_add(
+        self,
+        multiplicand: Self,
+        addend: Self,
+        carry: Self,
+    ) -> (Self::Unsigned, Self);
+}
+
+macro_rules! impl_carrying_mul_add {
+    ($($SelfT:ty => $UnsignedT:ty: $carrying_mul_add:path,)*) => {$(
+        impl CarryingMulAdd for $SelfT {
+            type Unsigned = $UnsignedT;
+
+            #[inline]
+            fn carrying_mul_add(self, b: Self, c: Self, d: Self) -> ($UnsignedT, Self) {
+                $carrying_mul_add(self, b, c, d)
+            }
+        }
+    )*};
+}
+
+impl_carrying_mul_add! {
+    u8 => u8: wide::carrying_mul_add_u8,
+    u16 => u16: wide::carrying_mul_add_u16,
+    u32 => u32: wide::carrying_mul_add_u32,
+    u64 => u64: wide::carrying_mul_add_u64,
+    u128 => u128: wide::portable_carrying_mul_add_u128,
+    usize => usize: wide::carrying_mul_add_usize,
+    i8 => u8: wide::carrying_mul_add_i8,
+    i16 => u16: wide::carrying_mul_add_i16,
+    i32 => u32: wide::carrying_mul_add_i32,
+    i64 => u64: wide::carrying_mul_add_i64,
+    i128 => u128: wide::portable_carrying_mul_add_i128,
+    isize => usize: wide::carrying_mul_add_isize,
+}
@@ -0,0 +1,219 @@
+use crate::fmt;
+use crate::iter::{self, FusedIterator};
+use crate::marker::PhantomData;
+
+/// An iterator over `chunk_size` elements of an iterator at a time, collected
+/// into a collection of type `B`.
+///
+/// This `struct` is created by the [`chunks`][Iterator::chunks] method on
+/// [`Iterator`]. See its documentation for more.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_chunks", reason = "recently added", issue = "none")]
+pub struct Chunks<I, B> {
+    iter: I,
+    chunk_size: usize,
+    // `fn() -> B` keeps `Chunks` `Send` and `Sync` regardless of `B`, which is
+    // only ever created and handed out.
+    _marker: PhantomData<fn() -> B>,
+}
+
+impl<I, B> Chunks<I, B> {
+    #[track_caller]
+    pub(in crate::iter) fn new(iter: I, chunk_size: usize) -> Self {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        Self { iter, chunk_size, _marker: PhantomData }
+    }
+}
+
+#[unstable(feature = "iter_chunks", reason = "recently added", issue = "none")]
+impl<I: Clone, B> Clone for Chunks<I, B> {
+    fn clone(&self) -> Self {
+        Self { iter: self.iter.clone(), chunk_size: self.chunk_size, _marker: PhantomData }
+    }
+}
+
+#[unstable(feature = "iter_chunks", reason = "recently added", issue = "none")]
+impl<I: fmt::Debug, B> fmt::Debug for Chunks<I, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Chunks")
+            .field("iter", &self.iter)
+            .field("chunk_size", &self.chunk_size)
+            .finish()
+    }
+}
+
+#[unstable(feature = "iter_chunks", reason = "recently added", issue = "none")]
+impl<I, B> Iterator for Chunks<I, B>
+where
+    I: Iterator,
+    B: FromIterator<I::Item>,
+{
+    type Item = B;
+
+    fn next(&mut self) -> Option<B> {
+        let mut chunk = self.iter.by_ref().take(self.chunk_size).peekable();
+        chunk.peek()?;
+        Some(chunk.collect())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.iter.size_hint();
+        (lo.div_ceil(self.chunk_size), hi.map(|hi| hi.div_ceil(self.chunk_size)))
+    }
+}
+
+#[unstable(feature = "iter_chunks", reason = "recently added", issue = "none")]
+impl<I, B> DoubleEndedIterator for Chunks<I, B>
+where
+    I: DoubleEndedIterator + ExactSizeIterator,
+    B: FromIterator<I::Item> + AsMut<[I::Item]>,
+{
+    fn next_back(&mut self) -> Option<B> {
+        let len = self.iter.len();
+        if len == 0 {
+            return None;
+        }
+        // The last chunk is the only one that may be short.
+        let n = match len % self.chunk_size {
+            0 => self.chunk_size,
+            rem => rem,
+        };
+        let mut chunk: B = self.iter.by_ref().rev().take(n).collect();
+        chunk.as_mut().reverse();
+        Some(chunk)
+    }
+}
+
+// `Chunks` cannot implement `TrustedLen`: when the underlying iterator is longer
+// than `usize::MAX`, the number of chunks may still fit into a `usize`, but it
+// is not known exactly.
+#[unstable(feature = "iter_chunks", reason = "recently added", issue = "none")]
+impl<I, B> ExactSizeIterator for Chunks<I, B>
+where
+    I: ExactSizeIterator,
+    B: FromIterator<I::Item>,
+{
+}
+
+#[unstable(feature = "iter_chunks", reason = "recently added", issue = "none")]
+impl<I, B> FusedIterator for Chunks<I, B>
+where
+    I: FusedIterator,
+    B: FromIterator<I::Item>,
+{
+}
+
+/// An iterator over runs of consecutive elements of an iterator that resolve
+/// to the same key, collected into a collection of type `B`.
+///
+/// This `struct` is created by the [`chunk_by`][Iterator::chunk_by] method on
+/// [`Iterator`]. See its documentation for more.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_chunks", reason = "recently added", issue = "none")]
+pub struct ChunkBy<I: Iterator, B, K, F> {
+    iter: I,
+    key: F,
+    // The element that ended the previous run, and its key.
+    next: Option<(K, I::Item)>,
+    _marker: PhantomData<fn() -> B>,
+}
+
+impl<I: Iterator, B, K, F> ChunkBy<I, B, K, F> {
+    pub(in crate::iter) fn new(iter: I, key: F) -> Self {
+        Self { iter, key, next: None, _marker: PhantomData }
+    }
+}
+
+#[unstable(feature = "iter_chunks", reason = "recently added", issue = "none")]
+impl<I, B, K, F> Clone for ChunkBy<I, B, K, F>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+    K: Clone,
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            key: self.key.clone(),
+            next: self.next.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[unstable(feature = "iter_chunks", reason = "recently added", issue = "none")]
+impl<I, B, K, F> fmt::Debug for ChunkBy<I, B, K, F>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    K: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkBy").field("iter", &self.iter).field("next", &self.next).finish()
+    }
+}
+
+#[unstable(feature = "iter_chunks", reason = "recently added", issue = "none")]
+impl<I, B, K, F> Iterator for ChunkBy<I, B, K, F>
+where
+    I: Iterator,
+    B: FromIterator<I::Item>,
+    K: PartialEq,
+    F: FnMut(&I::Item) -> K,
+{
+    type Item = (K, B);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, first) = match self.next.take() {
+            Some(next) => next,
+            None => {
+                let item = self.iter.next()?;
+                ((self.key)(&item), item)
+            }
+        };
+        let mut first = Some(first);
+        let mut done = false;
+        let mut run = iter::from_fn(|| {
+            if let Some(first) = first.take() {
+                return Some(first);
+            }
+            if done {
+                return None;
+            }
+            if let Some(item) = self.iter.next() {
+                let item_key = (self.key)(&item);
+                if item_key == key {
+                    return Some(item);
+                }
+                self.next = Some((item_key, item));
+            }
+            done = true;
+            None
+        });
+        let chunk = run.by_ref().collect();
+        // `B` may stop collecting early. The rest of the run is dropped rather
+        // than mistaken for the start of the next one.
+        run.for_each(drop);
+        Some((key, chunk))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let has_next = self.next.is_some();
+        let (lo, hi) = self.iter.size_hint();
+        let lo = if has_next || lo > 0 { 1 } else { 0 };
+        (lo, hi.and_then(|hi| hi.checked_add(has_next as usize)))
+    }
+}
+
+#[unstable(feature = "iter_chunks", reason = "recently added", issue = "none")]
+impl<I, B, K, F> FusedIterator for ChunkBy<I, B, K, F>
+where
+    I: FusedIterator,
+    B: FromIterator<I::Item>,
+    K: PartialEq,
+    F: FnMut(&I::Item) -> K,
+{
+}
@@ -0,0 +1,206 @@
+use crate::fmt;
+use crate::iter::FusedIterator;
+
+/// An iterator that removes consecutive repeated elements.
+///
+/// This `struct` is created by [`Iterator::dedup`]. See its documentation
+/// for more information.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_dedup", reason = "recently added", issue = "none")]
+#[derive(Debug, Clone)]
+pub struct Dedup<I: Iterator> {
+    iter: I,
+    next_item: Option<I::Item>,
+}
+
+impl<I: Iterator> Dedup<I> {
+    pub(in crate::iter) fn new(iter: I) -> Self {
+        Self { iter, next_item: None }
+    }
+}
+
+#[unstable(feature = "iter_dedup", reason = "recently added", issue = "none")]
+impl<I> Iterator for Dedup<I>
+where
+    I: Iterator,
+    I::Item: PartialEq,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        dedup_next(&mut self.iter, &mut self.next_item, |a, b| a == b)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        dedup_size_hint(&self.iter, self.next_item.is_some())
+    }
+}
+
+#[unstable(feature = "iter_dedup", reason = "recently added", issue = "none")]
+impl<I> FusedIterator for Dedup<I>
+where
+    I: FusedIterator,
+    I::Item: PartialEq,
+{
+}
+
+/// An iterator that removes consecutive elements that satisfy an equality
+/// relation.
+///
+/// This `struct` is created by [`Iterator::dedup_by`]. See its documentation
+/// for more information.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_dedup", reason = "recently added", issue = "none")]
+#[derive(Clone)]
+pub struct DedupBy<I: Iterator, F> {
+    iter: I,
+    next_item: Option<I::Item>,
+    same_bucket: F,
+}
+
+impl<I: Iterator, F> DedupBy<I, F> {
+    pub(in crate::iter) fn new(iter: I, same_bucket: F) -> Self {
+        Self { iter, next_item: None, same_bucket }
+    }
+}
+
+#[unstable(feature = "iter_dedup", reason = "recently added", issue = "none")]
+impl<I, F> fmt::Debug for DedupBy<I, F>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DedupBy")
+            .field("iter", &self.iter)
+            .field("next_item", &self.next_item)
+            .finish()
+    }
+}
+
+#[unstable(feature = "iter_dedup", reason = "recently added", issue = "none")]
+impl<I, F> Iterator for DedupBy<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let same_bucket = &mut self.same_bucket;
+        dedup_next(&mut self.iter, &mut self.next_item, |kept, item| same_bucket(item, kept))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        dedup_size_hint(&self.iter, self.next_item.is_some())
+    }
+}
+
+#[unstable(feature = "iter_dedup", reason = "recently added", issue = "none")]
+impl<I, F> FusedIterator for DedupBy<I, F>
+where
+    I: FusedIterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+}
+
+/// An iterator that removes consecutive elements that resolve to the same key.
+///
+/// This `struct` is created by [`Iterator::dedup_by_key`]. See its
+/// documentation for more information.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_dedup", reason = "recently added", issue = "none")]
+#[derive(Clone)]
+pub struct DedupByKey<I: Iterator, F> {
+    iter: I,
+    next_item: Option<I::Item>,
+    key: F,
+}
+
+impl<I: Iterator, F> DedupByKey<I, F> {
+    pub(in crate::iter) fn new(iter: I, key: F) -> Self {
+        Self { iter, next_item: None, key }
+    }
+}
+
+#[unstable(feature = "iter_dedup", reason = "recently added", issue = "none")]
+impl<I, F> fmt::Debug for DedupByKey<I, F>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DedupByKey")
+            .field("iter", &self.iter)
+            .field("next_item", &self.next_item)
+            .finish()
+    }
+}
+
+#[unstable(feature = "iter_dedup", reason = "recently added", issue = "none")]
+impl<I, F, K> Iterator for DedupByKey<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = &mut self.key;
+        dedup_next(&mut self.iter, &mut self.next_item, |kept, item| key(item) == key(kept))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        dedup_size_hint(&self.iter, self.next_item.is_some())
+    }
+}
+
+#[unstable(feature = "iter_dedup", reason = "recently added", issue = "none")]
+impl<I, F, K> FusedIterator for DedupByKey<I, F>
+where
+    I: FusedIterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+}
+
+/// Returns the first element of the next run of elements for which `same_bucket`
+/// holds, consuming the rest of the run.
+///
+/// The element ending the run is stashed in `next_item`, to be returned by the
+/// following call.
+fn dedup_next<I, F>(
+    iter: &mut I,
+    next_item: &mut Option<I::Item>,
+    mut same_bucket: F,
+) -> Option<I::Item>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    let kept = match next_item.take() {
+        Some(item) => item,
+        None => iter.next()?,
+    };
+    for item in iter {
+        if !same_bucket(&kept, &item) {
+            *next_item = Some(item);
+            break;
+        }
+    }
+    Some(kept)
+}
+
+fn dedup_size_hint<I: Iterator>(iter: &I, has_next_item: bool) -> (usize, Option<usize>) {
+    let (lo, hi) = iter.size_hint();
+    let lo = if has_next_item || lo > 0 { 1 } else { 0 };
+    let hi = hi.and_then(|hi| hi.checked_add(has_next_item as usize));
+    (lo, hi)
+}
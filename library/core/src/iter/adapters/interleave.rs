@@ -0,0 +1,115 @@
+use crate::iter::{Fuse, FusedIterator, TrustedLen};
+
+/// An iterator that alternates between the elements of two iterators.
+///
+/// This `struct` is created by [`Iterator::interleave`]. See its documentation
+/// for more information.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "iter_interleave", reason = "recently added", issue = "none")]
+#[derive(Debug, Clone)]
+pub struct Interleave<A, B> {
+    a: Fuse<A>,
+    b: Fuse<B>,
+    // Whether the next element is taken from `a` rather than `b`.
+    a_next: bool,
+}
+
+impl<A: Iterator, B: Iterator> Interleave<A, B> {
+    pub(in crate::iter) fn new(a: A, b: B) -> Self {
+        Self { a: a.fuse(), b: b.fuse(), a_next: true }
+    }
+}
+
+#[unstable(feature = "iter_interleave", reason = "recently added", issue = "none")]
+impl<A, B> Iterator for Interleave<A, B>
+where
+    A: Iterator,
+    B: Iterator<Item = A::Item>,
+{
+    type Item = A::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let a_next = self.a_next;
+        self.a_next = !a_next;
+        if a_next {
+            self.a.next().or_else(|| self.b.next())
+        } else {
+            self.b.next().or_else(|| self.a.next())
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lo, a_hi) = self.a.size_hint();
+        let (b_lo, b_hi) = self.b.size_hint();
+        let lo = a_lo.saturating_add(b_lo);
+        let hi = match (a_hi, b_hi) {
+            (Some(a_hi), Some(b_hi)) => a_hi.checked_add(b_hi),
+            _ => None,
+        };
+        (lo, hi)
+    }
+
+    fn fold<Acc, F>(self, init: Acc, mut f: F) -> Acc
+    where
+        F: FnMut(Acc, Self::Item) -> Acc,
+    {
+        let Self { mut a, mut b, a_next } = self;
+        let mut acc = init;
+        if !a_next {
+            match b.next() {
+                Some(item) => acc = f(acc, item),
+                None => return a.fold(acc, f),
+            }
+        }
+        loop {
+            match a.next() {
+                Some(item) => acc = f(acc, item),
+                None => return b.fold(acc, f),
+            }
+            match b.next() {
+                Some(item) => acc = f(acc, item),
+                None => return a.fold(acc, f),
+            }
+        }
+    }
+}
+
+#[unstable(feature = "iter_interleave", reason = "recently added", issue = "none")]
+impl<A, B> DoubleEndedIterator for Interleave<A, B>
+where
+    A: DoubleEndedIterator + ExactSizeIterator,
+    B: DoubleEndedIterator<Item = A::Item> + ExactSizeIterator,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // The longer iterator yields the last element. If both have the same length,
+        // the one that doesn't yield the next element from the front does.
+        let (a_len, b_len) = (self.a.len(), self.b.len());
+        if a_len > b_len || (a_len == b_len && !self.a_next) {
+            self.a.next_back()
+        } else {
+            self.b.next_back()
+        }
+    }
+}
+
+// `Interleave` cannot implement `ExactSizeIterator`, like `Chain`: the sum of
+// the lengths of both iterators may not fit into a `usize`.
+
+#[unstable(feature = "iter_interleave", reason = "recently added", issue = "none")]
+impl<A, B> FusedIterator for Interleave<A, B>
+where
+    A: Iterator,
+    B: Iterator<Item = A::Item>,
+{
+}
+
+#[unstable(feature = "trusted_len", issue = "37572")]
+unsafe impl<A, B> TrustedLen for Interleave<A, B>
+where
+    A: TrustedLen,
+    B: TrustedLen<Item = A::Item>,
+{
+}
@@ -5,15 +5,18 @@
 mod array_chunks;
 mod by_ref_sized;
 mod chain;
+mod chunks;
 mod cloned;
 mod copied;
 mod cycle;
+mod dedup;
 mod enumerate;
 mod filter;
 mod filter_map;
 mod flatten;
 mod fuse;
 mod inspect;
+mod interleave;
 mod intersperse;
 mod map;
 mod map_while;
@@ -44,9 +47,15 @@
 #[unstable(feature = "iter_chain", reason = "recently added", issue = "125964")]
 pub use self::chain::chain;
 
+#[unstable(feature = "iter_chunks", reason = "recently added", issue = "none")]
+pub use self::chunks::{ChunkBy, Chunks};
+
 #[stable(feature = "iter_cloned", since = "1.1.0")]
 pub use self::cloned::Cloned;
 
+#[unstable(feature = "iter_dedup", reason = "recently added", issue = "none")]
+pub use self::dedup::{Dedup, DedupBy, DedupByKey};
+
 #[stable(feature = "iterator_step_by", since = "1.28.0")]
 pub use self::step_by::StepBy;
 
@@ -56,6 +65,9 @@
 #[stable(feature = "iter_copied", since = "1.36.0")]
 pub use self::copied::Copied;
 
+#[unstable(feature = "iter_interleave", reason = "recently added", issue = "none")]
+pub use self::interleave::Interleave;
+
 #[unstable(feature = "iter_intersperse", reason = "recently added", issue = "79524")]
 pub use self::intersperse::{Intersperse, IntersperseWith};
 
@@ -162,10 +174,10 @@ pub(crate) struct GenericShunt<'a, I, R> {
 /// Process the given iterator as if it yielded a the item's `Try::Output`
 /// type instead. Any `Try::Residual`s encountered will stop the inner iterator
 /// and be propagated back to the overall result.
-pub(crate) fn try_process<I, T, R, F, U>(iter: I, mut f: F) -> ChangeOutputType<I::Item, U>
+pub(crate) fn try_process<I, T, R, F, U>(iter: I, f: F) -> ChangeOutputType<I::Item, U>
 where
     I: Iterator<Item: Try<Output = T, Residual = R>>,
-    for<'a> F: FnMut(GenericShunt<'a, I, R>) -> U,
+    for<'a> F: FnOnce(GenericShunt<'a, I, R>) -> U,
     R: Residual<U>,
 {
     let mut residual = None;
@@ -442,6 +442,8 @@ fn $fold<AAA, FFF>(mut self, init: AAA, fold: FFF) -> AAA
 pub use self::adapters::Copied;
 #[stable(feature = "iterator_flatten", since = "1.29.0")]
 pub use self::adapters::Flatten;
+#[unstable(feature = "iter_interleave", reason = "recently added", issue = "none")]
+pub use self::adapters::Interleave;
 #[stable(feature = "iter_map_while", since = "1.57.0")]
 pub use self::adapters::MapWhile;
 #[unstable(feature = "iter_map_windows", reason = "recently added", issue = "87155")]
@@ -459,6 +461,10 @@ fn $fold<AAA, FFF>(mut self, init: AAA, fold: FFF) -> AAA
     Chain, Cycle, Enumerate, Filter, FilterMap, FlatMap, Fuse, Inspect, Map, Peekable, Rev, Scan,
     Skip, SkipWhile, Take, TakeWhile, Zip,
 };
+#[unstable(feature = "iter_chunks", reason = "recently added", issue = "none")]
+pub use self::adapters::{ChunkBy, Chunks};
+#[unstable(feature = "iter_dedup", reason = "recently added", issue = "none")]
+pub use self::adapters::{Dedup, DedupBy, DedupByKey};
 #[unstable(feature = "iter_intersperse", reason = "recently added", issue = "79524")]
 pub use self::adapters::{Intersperse, IntersperseWith};
 
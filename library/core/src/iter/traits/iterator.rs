@@ -7,6 +7,7 @@
 use super::super::ByRefSized;
 use super::super::TrustedRandomAccessNoCoerce;
 use super::super::{ArrayChunks, Chain, Cloned, Copied, Cycle, Enumerate, Filter, FilterMap, Fuse};
+use super::super::{ChunkBy, Chunks, Dedup, DedupBy, DedupByKey, Interleave};
 use super::super::{FlatMap, Flatten};
 use super::super::{
     Inspect, Map, MapWhile, MapWindows, Peekable, Rev, Scan, Skip, SkipWhile, StepBy, Take,
@@ -84,6 +85,15 @@ pub trait Iterator {
     /// If there are not enough elements to fill the array then `Err` is returned
     /// containing an iterator over the remaining elements.
     ///
+    /// The iterator is advanced by at most `N` elements: it is never polled
+    /// again after returning [`None`], and the elements it yielded are never
+    /// lost, whether they end up in the array or in the returned iterator.
+    /// For `N == 0`, this always returns an empty array without advancing
+    /// the iterator.
+    ///
+    /// If the elements of the iterator should be collected into an array of
+    /// the same length instead, use [`collect_array`].
+    ///
     /// # Examples
     ///
     /// Basic usage:
@@ -109,6 +119,8 @@ pub trait Iterator {
     /// assert_eq!(second, "all");
     /// assert_eq!(third, "those");
     /// ```
+    ///
+    /// [`collect_array`]: Iterator::collect_array
     #[inline]
     #[unstable(feature = "iter_next_chunk", reason = "recently added", issue = "98326")]
     #[rustc_do_not_const_check]
@@ -707,6 +719,50 @@ fn intersperse_with<G>(self, separator: G) -> IntersperseWith<Self, G>
         IntersperseWith::new(self, separator)
     }
 
+    /// Creates a new iterator which alternates between the items of `self` and
+    /// the items of `other`, starting with `self`.
+    ///
+    /// Once either iterator is exhausted, the remaining items of the other one
+    /// are yielded in order. Both iterators are [fused], so neither of them is
+    /// polled again after it returned [`None`].
+    ///
+    /// The returned iterator implements [`DoubleEndedIterator`] if both
+    /// iterators implement [`DoubleEndedIterator`] and [`ExactSizeIterator`],
+    /// which are needed to tell which of them yields the last item.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_interleave)]
+    ///
+    /// let mut it = [1, 3, 5].into_iter().interleave([2, 4, 6, 8, 10]);
+    ///
+    /// assert_eq!(it.next(), Some(1));
+    /// assert_eq!(it.next(), Some(2));
+    /// assert_eq!(it.next(), Some(3));
+    /// assert_eq!(it.next(), Some(4));
+    /// assert_eq!(it.next(), Some(5));
+    /// assert_eq!(it.next(), Some(6));
+    /// // `self` is exhausted, so the rest of `other` follows.
+    /// assert_eq!(it.next(), Some(8));
+    /// assert_eq!(it.next(), Some(10));
+    /// assert_eq!(it.next(), None);
+    /// ```
+    ///
+    /// [fused]: Iterator::fuse
+    #[inline]
+    #[unstable(feature = "iter_interleave", reason = "recently added", issue = "none")]
+    #[rustc_do_not_const_check]
+    fn interleave<U>(self, other: U) -> Interleave<Self, U::IntoIter>
+    where
+        Self: Sized,
+        U: IntoIterator<Item = Self::Item>,
+    {
+        Interleave::new(self, other.into_iter())
+    }
+
     /// Takes a closure and creates an iterator which calls that closure on each
     /// element.
     ///
@@ -1705,6 +1761,103 @@ fn map_windows<F, R, const N: usize>(self, f: F) -> MapWindows<Self, F, N>
         MapWindows::new(self, f)
     }
 
+    /// Creates an iterator which removes consecutive repeated elements,
+    /// according to the [`PartialEq`] trait implementation.
+    ///
+    /// Of each run of equal elements, only the first one is yielded. This is
+    /// the lazy counterpart of [`Vec::dedup`]: if the iterator is sorted, this
+    /// removes all duplicates.
+    ///
+    /// The returned iterator doesn't implement [`DoubleEndedIterator`], as this
+    /// adapter and [`dedup_by`] and [`dedup_by_key`] don't: a run of elements
+    /// can only be told apart from its first element when iterating forwards.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_dedup)]
+    ///
+    /// let v: Vec<_> = [1, 2, 2, 3, 2, 2, 2, 1].into_iter().dedup().collect();
+    /// assert_eq!(v, [1, 2, 3, 2, 1]);
+    /// ```
+    ///
+    /// [`Vec::dedup`]: ../../std/vec/struct.Vec.html#method.dedup
+    /// [`dedup_by`]: Iterator::dedup_by
+    /// [`dedup_by_key`]: Iterator::dedup_by_key
+    #[inline]
+    #[unstable(feature = "iter_dedup", reason = "recently added", issue = "none")]
+    #[rustc_do_not_const_check]
+    fn dedup(self) -> Dedup<Self>
+    where
+        Self: Sized,
+        Self::Item: PartialEq,
+    {
+        Dedup::new(self)
+    }
+
+    /// Creates an iterator which removes consecutive elements that satisfy a
+    /// given equality relation.
+    ///
+    /// The `same_bucket` function is passed references to two elements: the
+    /// element currently being considered and the last element that was
+    /// yielded. If `same_bucket(a, b)` returns `true`, `a` is skipped.
+    /// This is the lazy counterpart of [`Vec::dedup_by`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_dedup)]
+    ///
+    /// let words = ["foo", "FOO", "bar", "Bar", "baz", "foo"];
+    /// let v: Vec<_> = words.into_iter().dedup_by(|a, b| a.eq_ignore_ascii_case(b)).collect();
+    /// assert_eq!(v, ["foo", "bar", "baz", "foo"]);
+    /// ```
+    ///
+    /// [`Vec::dedup_by`]: ../../std/vec/struct.Vec.html#method.dedup_by
+    #[inline]
+    #[unstable(feature = "iter_dedup", reason = "recently added", issue = "none")]
+    #[rustc_do_not_const_check]
+    fn dedup_by<F>(self, same_bucket: F) -> DedupBy<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item, &Self::Item) -> bool,
+    {
+        DedupBy::new(self, same_bucket)
+    }
+
+    /// Creates an iterator which removes consecutive elements that resolve to
+    /// the same key.
+    ///
+    /// This is the lazy counterpart of [`Vec::dedup_by_key`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_dedup)]
+    ///
+    /// let v: Vec<_> = [10, 20, 21, 30, 20].into_iter().dedup_by_key(|i| *i / 10).collect();
+    /// assert_eq!(v, [10, 20, 30, 20]);
+    /// ```
+    ///
+    /// [`Vec::dedup_by_key`]: ../../std/vec/struct.Vec.html#method.dedup_by_key
+    #[inline]
+    #[unstable(feature = "iter_dedup", reason = "recently added", issue = "none")]
+    #[rustc_do_not_const_check]
+    fn dedup_by_key<F, K>(self, key: F) -> DedupByKey<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> K,
+        K: PartialEq,
+    {
+        DedupByKey::new(self, key)
+    }
+
     /// Creates an iterator which ends after the first [`None`].
     ///
     /// After an iterator returns [`None`], future calls may or may not yield
@@ -2005,6 +2158,52 @@ fn collect<B: FromIterator<Self::Item>>(self) -> B
         FromIterator::from_iter(self)
     }
 
+    /// Collects all the items from an iterator into an array of length `N`.
+    ///
+    /// Returns [`None`] if the iterator does not yield *exactly* `N` items. In
+    /// that case, the items that were yielded are dropped. To take the first
+    /// `N` items of a longer iterator, use [`next_chunk`] instead.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_collect_array)]
+    ///
+    /// let doubled = [1, 2, 3].iter().map(|x| x * 2).collect_array();
+    /// assert_eq!(doubled, Some([2, 4, 6]));
+    ///
+    /// // Too few items.
+    /// assert_eq!((0..2).collect_array::<3>(), None);
+    /// // Too many items.
+    /// assert_eq!((0..4).collect_array::<3>(), None);
+    /// ```
+    ///
+    /// Destructuring the fields of a line, rejecting malformed ones:
+    ///
+    /// ```
+    /// #![feature(iter_collect_array)]
+    ///
+    /// let parse = |line: &str| line.split(',').collect_array();
+    ///
+    /// assert_eq!(parse("x,y,z"), Some(["x", "y", "z"]));
+    /// assert_eq!(parse("x,y"), None);
+    /// assert_eq!(parse("x,y,z,w"), None);
+    /// ```
+    ///
+    /// [`next_chunk`]: Iterator::next_chunk
+    #[inline]
+    #[unstable(feature = "iter_collect_array", reason = "recently added", issue = "none")]
+    #[must_use = "if you really need to exhaust the iterator, consider `.for_each(drop)` instead"]
+    #[rustc_do_not_const_check]
+    fn collect_array<const N: usize>(mut self) -> Option<[Self::Item; N]>
+    where
+        Self: Sized,
+    {
+        self.next_chunk().ok().filter(|_| self.next().is_none())
+    }
+
     /// Fallibly transforms an iterator into a collection, short circuiting if
     /// a failure is encountered.
     ///
@@ -2087,6 +2286,52 @@ fn try_collect<B>(&mut self) -> ChangeOutputType<Self::Item, B>
         try_process(ByRefSized(self), |i| i.collect())
     }
 
+    /// Fallibly collects the items of an iterator into an array of length `N`,
+    /// short circuiting if a failure is encountered.
+    ///
+    /// This is the array counterpart of [`try_collect`], just as [`collect_array`]
+    /// is for [`collect`]: the output is [`None`] if the iterator does not yield
+    /// *exactly* `N` successful items before it is exhausted.
+    ///
+    /// To detect additional items, the iterator is advanced by up to `N + 1`
+    /// elements. As with [`try_collect`], the iterator may continue to be used
+    /// after a failure, starting after the element that triggered it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_collect_array)]
+    ///
+    /// let u: [Result<i32, &str>; 3] = [Ok(1), Ok(2), Ok(3)];
+    /// assert_eq!(u.into_iter().try_collect_array(), Ok(Some([1, 2, 3])));
+    ///
+    /// let u = [Ok(1), Err("bad"), Ok(3)];
+    /// assert_eq!(u.into_iter().try_collect_array::<3>(), Err("bad"));
+    ///
+    /// let u: [Result<i32, &str>; 2] = [Ok(1), Ok(2)];
+    /// assert_eq!(u.into_iter().try_collect_array::<3>(), Ok(None));
+    ///
+    /// let u = ["1", "2", "x"].map(|s| s.parse::<u8>().ok());
+    /// assert_eq!(u.into_iter().try_collect_array::<3>(), None);
+    /// ```
+    ///
+    /// [`collect`]: Iterator::collect
+    /// [`collect_array`]: Iterator::collect_array
+    /// [`try_collect`]: Iterator::try_collect
+    #[inline]
+    #[unstable(feature = "iter_collect_array", reason = "recently added", issue = "none")]
+    #[rustc_do_not_const_check]
+    fn try_collect_array<const N: usize>(
+        &mut self,
+    ) -> ChangeOutputType<Self::Item, Option<[<Self::Item as Try>::Output; N]>>
+    where
+        Self: Sized,
+        Self::Item: Try,
+        <Self::Item as Try>::Residual: Residual<Option<[<Self::Item as Try>::Output; N]>>,
+    {
+        try_process(ByRefSized(self), |i| i.collect_array())
+    }
+
     /// Collects all the items from an iterator into a collection.
     ///
     /// This method consumes the iterator and adds all its items to the
@@ -2159,6 +2404,59 @@ fn collect_into<E: Extend<Self::Item>>(self, collection: &mut E) -> &mut E
         collection
     }
 
+    /// Fallibly collects the items of an iterator into an existing collection,
+    /// short circuiting if a failure is encountered.
+    ///
+    /// This is the fallible counterpart of [`collect_into`], in the way that
+    /// [`try_collect`] is for [`collect`]. It is useful for reusing a buffer
+    /// that was allocated up front, e.g. with [`Vec::with_capacity`], when the
+    /// items may fail to be produced.
+    ///
+    /// The successful items that precede a failure are still added to the
+    /// collection. As with [`try_collect`], the iterator may continue to be
+    /// used after a failure, starting after the element that triggered it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(iter_collect_into)]
+    ///
+    /// let mut buf = Vec::with_capacity(8);
+    ///
+    /// let r = ["1", "2", "3"].iter().map(|s| s.parse::<i32>()).try_collect_into(&mut buf);
+    /// assert_eq!(r.map(|v| v.len()), Ok(3));
+    ///
+    /// let r = ["4", "x", "5"].iter().map(|s| s.parse::<i32>()).try_collect_into(&mut buf);
+    /// assert!(r.is_err());
+    ///
+    /// // The items before the failure were collected.
+    /// assert_eq!(buf, [1, 2, 3, 4]);
+    /// assert_eq!(buf.capacity(), 8);
+    /// ```
+    ///
+    /// [`collect`]: Iterator::collect
+    /// [`collect_into`]: Iterator::collect_into
+    /// [`try_collect`]: Iterator::try_collect
+    /// [`Vec::with_capacity`]: ../../std/vec/struct.Vec.html#method.with_capacity
+    #[inline]
+    #[unstable(feature = "iter_collect_into", reason = "new API", issue = "94780")]
+    #[rustc_do_not_const_check]
+    fn try_collect_into<'a, E>(
+        &mut self,
+        collection: &'a mut E,
+    ) -> ChangeOutputType<Self::Item, &'a mut E>
+    where
+        Self: Sized,
+        Self::Item: Try,
+        <Self::Item as Try>::Residual: Residual<&'a mut E>,
+        E: Extend<<Self::Item as Try>::Output>,
+    {
+        try_process(ByRefSized(self), |i| {
+            collection.extend(i);
+            collection
+        })
+    }
+
     /// Consumes an iterator, creating two collections from it.
     ///
     /// The predicate passed to `partition()` can return `true`, or `false`.
@@ -3548,6 +3846,107 @@ fn array_chunks<const N: usize>(self) -> ArrayChunks<Self, N>
         ArrayChunks::new(self)
     }
 
+    /// Returns an iterator over `chunk_size` elements of the iterator at a
+    /// time, each collected into a collection of type `B`, such as a
+    /// [`Vec`].
+    ///
+    /// The chunks do not overlap. If `chunk_size` does not divide the length of
+    /// the iterator, then the last chunk will be shorter. Unlike
+    /// [`array_chunks`], the chunk size doesn't need to be known at compile
+    /// time, and no elements are left over.
+    ///
+    /// The returned iterator implements [`DoubleEndedIterator`] if the
+    /// underlying iterator implements both [`DoubleEndedIterator`] and
+    /// [`ExactSizeIterator`], and `B` can be viewed as a mutable slice.
+    /// Iterating from the back yields the same chunks as iterating from the
+    /// front, in reverse order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_chunks)]
+    ///
+    /// let mut it = [1, 2, 3, 4, 5].into_iter().chunks(2);
+    /// assert_eq!(it.next(), Some(vec![1, 2]));
+    /// assert_eq!(it.next(), Some(vec![3, 4]));
+    /// assert_eq!(it.next(), Some(vec![5]));
+    /// assert_eq!(it.next(), None);
+    ///
+    /// let mut it = [1, 2, 3, 4, 5].into_iter().chunks(2);
+    /// assert_eq!(it.next_back(), Some(vec![5]));
+    /// assert_eq!(it.next_back(), Some(vec![3, 4]));
+    /// assert_eq!(it.next(), Some(vec![1, 2]));
+    /// assert_eq!(it.next(), None);
+    /// ```
+    ///
+    /// The type of the chunks can be given explicitly:
+    ///
+    /// ```
+    /// #![feature(iter_chunks)]
+    ///
+    /// for chunk in "lorem ipsum".chars().chunks::<String>(4) {
+    ///     assert!(chunk.len() <= 4);
+    /// }
+    /// ```
+    ///
+    /// [`Vec`]: ../../std/vec/struct.Vec.html
+    /// [`array_chunks`]: Iterator::array_chunks
+    #[track_caller]
+    #[unstable(feature = "iter_chunks", reason = "recently added", issue = "none")]
+    #[rustc_do_not_const_check]
+    fn chunks<B>(self, chunk_size: usize) -> Chunks<Self, B>
+    where
+        Self: Sized,
+        B: FromIterator<Self::Item>,
+    {
+        Chunks::new(self, chunk_size)
+    }
+
+    /// Returns an iterator over runs of consecutive elements that resolve to the
+    /// same key, each collected into a collection of type `B`, such as a
+    /// [`Vec`].
+    ///
+    /// Each item of the returned iterator is a pair of the key shared by the run
+    /// and its elements. The key function is called exactly once per element.
+    /// Elements with the same key that are not adjacent end up in different
+    /// runs; sort the iterator by the key first to group all of them.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(iter_chunks)]
+    ///
+    /// let words = ["apple", "avocado", "banana", "blueberry", "cherry", "apricot"];
+    /// let mut it = words.into_iter().chunk_by(|w| w.as_bytes()[0]);
+    ///
+    /// assert_eq!(it.next(), Some((b'a', vec!["apple", "avocado"])));
+    /// assert_eq!(it.next(), Some((b'b', vec!["banana", "blueberry"])));
+    /// assert_eq!(it.next(), Some((b'c', vec!["cherry"])));
+    /// assert_eq!(it.next(), Some((b'a', vec!["apricot"])));
+    /// assert_eq!(it.next(), None);
+    /// ```
+    ///
+    /// [`Vec`]: ../../std/vec/struct.Vec.html
+    #[unstable(feature = "iter_chunks", reason = "recently added", issue = "none")]
+    #[rustc_do_not_const_check]
+    fn chunk_by<B, K, F>(self, key: F) -> ChunkBy<Self, B, K, F>
+    where
+        Self: Sized,
+        B: FromIterator<Self::Item>,
+        K: PartialEq,
+        F: FnMut(&Self::Item) -> K,
+    {
+        ChunkBy::new(self, key)
+    }
+
     /// Sums the elements of an iterator.
     ///
     /// Takes each element, adds them together, and returns the result.
@@ -202,7 +202,7 @@ pub fn right_shift(&mut self, shift: usize) {
 }
 
 /// Parse a big integer representation of the float as a decimal.
-pub fn parse_decimal(mut s: &[u8]) -> Decimal {
+pub fn parse_decimal(mut s: &[u8], decimal_point: u8) -> Decimal {
     let mut d = Decimal::default();
     let start = s;
 
@@ -212,7 +212,9 @@ pub fn parse_decimal(mut s: &[u8]) -> Decimal {
 
     s = s.parse_digits(|digit| d.try_add_digit(digit));
 
-    if let Some((b'.', s_next)) = s.split_first() {
+    if let Some((&c, s_next)) = s.split_first()
+        && c == decimal_point
+    {
         s = s_next;
         let first = s;
         // Skip leading zeros.
@@ -239,7 +241,7 @@ pub fn parse_decimal(mut s: &[u8]) -> Decimal {
         for &c in start[..(start.len() - s.len())].iter().rev() {
             if c == b'0' {
                 n_trailing_zeros += 1;
-            } else if c != b'.' {
+            } else if c != decimal_point {
                 break;
             }
         }
@@ -82,7 +82,11 @@
 use self::common::BiasedFp;
 use self::float::RawFloat;
 use self::lemire::compute_float;
-use self::parse::{parse_inf_nan, parse_number};
+use self::number::Number;
+use self::parse::{
+    parse_inf_nan, parse_number, parse_partial_hex_number, parse_partial_inf_nan,
+    parse_partial_number,
+};
 use self::slow::parse_long_mantissa;
 
 mod common;
@@ -155,14 +159,161 @@ impl FromStr for $t {
             // possible instances can ever exist. Adding #[inline(never)] avoids this.
             #[inline(never)]
             fn from_str(src: &str) -> Result<Self, ParseFloatError> {
+                dec2flt(src.as_bytes())
+            }
+        }
+
+        impl $t {
+            /// Parses a float from an ASCII byte slice.
+            ///
+            /// This accepts the same syntax as the [`FromStr`] implementation, but
+            /// operates on bytes so the input does not need to be valid UTF-8.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// #![feature(float_parse_options)]
+            ///
+            #[doc = concat!("assert_eq!(", stringify!($t), "::from_ascii(b\"-2.5e3\"), Ok(-2500.0));")]
+            #[doc = concat!("assert!(", stringify!($t), "::from_ascii(b\"2.5 \").is_err());")]
+            /// ```
+            #[unstable(feature = "float_parse_options", issue = "none")]
+            #[inline(never)]
+            pub fn from_ascii(src: &[u8]) -> Result<Self, ParseFloatError> {
                 dec2flt(src)
             }
+
+            /// Parses a float from an ASCII byte slice, using the syntax described by
+            /// `options`.
+            ///
+            /// The whole input must be consumed; see
+            #[doc = concat!("[`", stringify!($t), "::parse_partial`]")]
+            /// to parse a float from the start of a longer input.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// #![feature(float_parse_options)]
+            /// use std::num::ParseFloatOptions;
+            ///
+            /// let options = ParseFloatOptions::new().decimal_point(b',').hexadecimal(true);
+            #[doc = concat!("assert_eq!(", stringify!($t), "::from_ascii_with(b\"1,5\", options), Ok(1.5));")]
+            #[doc = concat!("assert_eq!(", stringify!($t), "::from_ascii_with(b\"-0x1,8p3\", options), Ok(-12.0));")]
+            #[doc = concat!("assert!(", stringify!($t), "::from_ascii_with(b\"1.5\", options).is_err());")]
+            /// ```
+            #[unstable(feature = "float_parse_options", issue = "none")]
+            #[inline(never)]
+            pub fn from_ascii_with(
+                src: &[u8],
+                options: ParseFloatOptions,
+            ) -> Result<Self, ParseFloatError> {
+                match Self::parse_partial(src, options)? {
+                    (float, len) if len == src.len() => Ok(float),
+                    _ => Err(pfe_invalid()),
+                }
+            }
+
+            /// Parses a float from the start of an ASCII byte slice, using the syntax
+            /// described by `options`, and returns it along with the number of bytes
+            /// consumed.
+            ///
+            /// The longest prefix of `src` that forms a valid float is parsed, so an
+            /// exponent marker that is not followed by digits is not consumed. An error is
+            /// returned if `src` does not start with a float.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// #![feature(float_parse_options)]
+            /// use std::num::ParseFloatOptions;
+            ///
+            /// let options = ParseFloatOptions::new();
+            #[doc = concat!("assert_eq!(", stringify!($t), "::parse_partial(b\"1.5,2.5\", options), Ok((1.5, 3)));")]
+            #[doc = concat!("assert_eq!(", stringify!($t), "::parse_partial(b\"2e\", options), Ok((2.0, 1)));")]
+            #[doc = concat!("assert_eq!(", stringify!($t), "::parse_partial(b\"-infinity!\", options), Ok((", stringify!($t), "::NEG_INFINITY, 9)));")]
+            #[doc = concat!("assert!(", stringify!($t), "::parse_partial(b\"x1\", options).is_err());")]
+            /// ```
+            #[unstable(feature = "float_parse_options", issue = "none")]
+            #[inline(never)]
+            pub fn parse_partial(
+                src: &[u8],
+                options: ParseFloatOptions,
+            ) -> Result<(Self, usize), ParseFloatError> {
+                dec2flt_partial(src, options)
+            }
         }
     };
 }
 from_str_float_impl!(f32);
 from_str_float_impl!(f64);
 
+/// Options controlling the syntax accepted when parsing a float.
+///
+/// By default, the syntax is the same as that of the [`FromStr`] implementation for
+/// [`f32`] and [`f64`].
+///
+/// # Examples
+///
+/// ```
+/// #![feature(float_parse_options)]
+/// use std::num::ParseFloatOptions;
+///
+/// let options = ParseFloatOptions::new().hexadecimal(true);
+/// assert_eq!(f64::from_ascii_with(b"0x1.8p3", options), Ok(12.0));
+/// assert_eq!(f64::from_ascii_with(b"12", options), Ok(12.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[unstable(feature = "float_parse_options", issue = "none")]
+pub struct ParseFloatOptions {
+    decimal_point: u8,
+    hexadecimal: bool,
+}
+
+impl ParseFloatOptions {
+    /// Creates options accepting the same syntax as the [`FromStr`] implementation.
+    #[unstable(feature = "float_parse_options", issue = "none")]
+    #[must_use]
+    pub const fn new() -> Self {
+        ParseFloatOptions { decimal_point: b'.', hexadecimal: false }
+    }
+
+    /// Sets the byte separating the integral and fractional digits, `.` by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `decimal_point` is an ASCII letter or digit, `+` or `-`, since those
+    /// would make the syntax ambiguous.
+    #[unstable(feature = "float_parse_options", issue = "none")]
+    #[must_use]
+    pub const fn decimal_point(mut self, decimal_point: u8) -> Self {
+        assert!(
+            !decimal_point.is_ascii_alphanumeric() && decimal_point != b'+' && decimal_point != b'-',
+            "invalid decimal point"
+        );
+        self.decimal_point = decimal_point;
+        self
+    }
+
+    /// Sets whether hexadecimal floats such as `0x1.8p3` are accepted, `false` by default.
+    ///
+    /// A hexadecimal float consists of `0x` or `0X`, hexadecimal digits with an optional
+    /// decimal point, and an optional binary exponent introduced by `p` or `P`. The
+    /// exponent is written in decimal and scales the value by a power of two.
+    #[unstable(feature = "float_parse_options", issue = "none")]
+    #[must_use]
+    pub const fn hexadecimal(mut self, hexadecimal: bool) -> Self {
+        self.hexadecimal = hexadecimal;
+        self
+    }
+}
+
+#[unstable(feature = "float_parse_options", issue = "none")]
+impl Default for ParseFloatOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// An error which can be returned when parsing a float.
 ///
 /// This error is used as the error type for the [`FromStr`] implementation
@@ -229,8 +380,7 @@ fn biased_fp_to_float<T: RawFloat>(x: BiasedFp) -> T {
 
 /// Converts a decimal string into a floating point number.
 #[inline(always)] // Will be inlined into a function with `#[inline(never)]`, see above
-pub fn dec2flt<F: RawFloat>(s: &str) -> Result<F, ParseFloatError> {
-    let mut s = s.as_bytes();
+pub fn dec2flt<F: RawFloat>(mut s: &[u8]) -> Result<F, ParseFloatError> {
     let c = if let Some(&c) = s.first() {
         c
     } else {
@@ -250,8 +400,59 @@ pub fn dec2flt<F: RawFloat>(s: &str) -> Result<F, ParseFloatError> {
         None => return Err(pfe_invalid()),
     };
     num.negative = negative;
+    Ok(number_to_float(num, s, b'.'))
+}
+
+/// Converts the start of a string into a floating point number, using the
+/// syntax described by `options`, and returns it along with the number of
+/// bytes consumed.
+#[inline(always)] // Will be inlined into a function with `#[inline(never)]`, see above
+pub fn dec2flt_partial<F: RawFloat>(
+    src: &[u8],
+    options: ParseFloatOptions,
+) -> Result<(F, usize), ParseFloatError> {
+    let mut s = src;
+    let c = if let Some(&c) = s.first() {
+        c
+    } else {
+        return Err(pfe_empty());
+    };
+    let negative = c == b'-';
+    if c == b'-' || c == b'+' {
+        s = &s[1..];
+    }
+    if s.is_empty() {
+        return Err(pfe_invalid());
+    }
+    let sign_len = src.len() - s.len();
+
+    if options.hexadecimal
+        && let Some((fp, len)) = parse_partial_hex_number::<F>(s, options.decimal_point)
+    {
+        let mut float = biased_fp_to_float::<F>(fp);
+        if negative {
+            float = -float;
+        }
+        return Ok((float, sign_len + len));
+    }
+
+    let (float, len) = match parse_partial_number(s, options.decimal_point) {
+        Some((mut num, len)) => {
+            num.negative = negative;
+            (number_to_float(num, &s[..len], options.decimal_point), len)
+        }
+        None if let Some(r) = parse_partial_inf_nan(s, negative) => r,
+        None => return Err(pfe_invalid()),
+    };
+    Ok((float, sign_len + len))
+}
+
+/// Converts a parsed number into the closest floating point number, where
+/// `s` holds its digits for the slow path.
+#[inline(always)]
+fn number_to_float<F: RawFloat>(num: Number, s: &[u8], decimal_point: u8) -> F {
     if let Some(value) = num.try_fast_path::<F>() {
-        return Ok(value);
+        return value;
     }
 
     // If significant digits were truncated, then we can have rounding error
@@ -265,12 +466,12 @@ pub fn dec2flt<F: RawFloat>(s: &str) -> Result<F, ParseFloatError> {
     // Unable to correctly round the float using the Eisel-Lemire algorithm.
     // Fallback to a slower, but always correct algorithm.
     if fp.e < 0 {
-        fp = parse_long_mantissa::<F>(s);
+        fp = parse_long_mantissa::<F>(s, decimal_point);
     }
 
     let mut float = biased_fp_to_float::<F>(fp);
     if num.negative {
         float = -float;
     }
-    Ok(float)
+    float
 }
@@ -1,6 +1,6 @@
 //! Functions to parse floating-point numbers.
 
-use crate::num::dec2flt::common::{is_8digits, ByteSlice};
+use crate::num::dec2flt::common::{is_8digits, BiasedFp, ByteSlice};
 use crate::num::dec2flt::float::RawFloat;
 use crate::num::dec2flt::number::Number;
 
@@ -101,8 +101,9 @@ fn parse_scientific(s_ref: &mut &[u8]) -> Option<i64> {
 /// Parse a partial, non-special floating point number.
 ///
 /// This creates a representation of the float as the
-/// significant digits and the decimal exponent.
-fn parse_partial_number(mut s: &[u8]) -> Option<(Number, usize)> {
+/// significant digits and the decimal exponent, along with
+/// the number of bytes consumed.
+pub(crate) fn parse_partial_number(mut s: &[u8], decimal_point: u8) -> Option<(Number, usize)> {
     debug_assert!(!s.is_empty());
 
     // parse initial digits before dot
@@ -118,7 +119,9 @@ fn parse_partial_number(mut s: &[u8]) -> Option<(Number, usize)> {
     let mut exponent = 0_i64;
     let int_end = s;
 
-    if let Some((&b'.', s_next)) = s.split_first() {
+    if let Some((&c, s_next)) = s.split_first()
+        && c == decimal_point
+    {
         s = s_next;
         let before = s;
         let tmp = try_parse_digits(s, mantissa);
@@ -137,10 +140,14 @@ fn parse_partial_number(mut s: &[u8]) -> Option<(Number, usize)> {
     let mut exp_number = 0_i64;
     if let Some((&c, s_next)) = s.split_first() {
         if c == b'e' || c == b'E' {
-            s = s_next;
-            // If None, we have no trailing digits after exponent, or an invalid float.
-            exp_number = parse_scientific(&mut s)?;
-            exponent += exp_number;
+            let mut s_exp = s_next;
+            // If None, we have no trailing digits after exponent, so the
+            // exponent is not part of the number.
+            if let Some(exp) = parse_scientific(&mut s_exp) {
+                s = s_exp;
+                exp_number = exp;
+                exponent += exp_number;
+            }
         }
     }
 
@@ -155,12 +162,12 @@ fn parse_partial_number(mut s: &[u8]) -> Option<(Number, usize)> {
     let mut many_digits = false;
     let mut p = start;
     while let Some((&c, p_next)) = p.split_first() {
-        if c == b'.' || c == b'0' {
-            n_digits -= c.saturating_sub(b'0' - 1) as isize;
-            p = p_next;
-        } else {
+        if c == b'0' {
+            n_digits -= 1;
+        } else if c != decimal_point {
             break;
         }
+        p = p_next;
     }
     if n_digits > 0 {
         // at this point we have more than 19 significant digits, let's try again
@@ -188,7 +195,7 @@ fn parse_partial_number(mut s: &[u8]) -> Option<(Number, usize)> {
 /// as well as two slices with integer and fractional parts
 /// and the parsed exponent.
 pub fn parse_number(s: &[u8]) -> Option<Number> {
-    if let Some((float, rest)) = parse_partial_number(s) {
+    if let Some((float, rest)) = parse_partial_number(s, b'.') {
         if rest == s.len() {
             return Some(float);
         }
@@ -196,6 +203,141 @@ pub fn parse_number(s: &[u8]) -> Option<Number> {
     None
 }
 
+/// Parse a partial hexadecimal floating point number, such as `0x1.8p3`.
+///
+/// This creates the biased, binary representation of the float, rounded
+/// to nearest, ties to even, along with the number of bytes consumed.
+pub(crate) fn parse_partial_hex_number<F: RawFloat>(
+    s: &[u8],
+    decimal_point: u8,
+) -> Option<(BiasedFp, usize)> {
+    let start = s;
+    let mut s = match s {
+        [b'0', b'x' | b'X', s_next @ ..] => s_next,
+        _ => return None,
+    };
+
+    // Keep as many digits as fit into 64 bits, and only remember
+    // whether any of the dropped digits were nonzero.
+    let mut mantissa = 0_u64;
+    let mut exponent = 0_i64;
+    let mut truncated = false;
+    let mut n_digits = 0;
+    let mut in_fraction = false;
+    while let Some((&c, s_next)) = s.split_first() {
+        if c == decimal_point && !in_fraction {
+            in_fraction = true;
+        } else if let Some(digit) = (c as char).to_digit(16) {
+            if mantissa >> 60 == 0 {
+                mantissa = (mantissa << 4) | digit as u64;
+                if in_fraction {
+                    exponent -= 4;
+                }
+            } else {
+                truncated |= digit != 0;
+                if !in_fraction {
+                    exponent += 4;
+                }
+            }
+            n_digits += 1;
+        } else {
+            break;
+        }
+        s = s_next;
+    }
+    if n_digits == 0 {
+        return None;
+    }
+
+    // handle binary exponent
+    if let Some((&(b'p' | b'P'), s_next)) = s.split_first() {
+        let mut s_exp = s_next;
+        if let Some(exp) = parse_scientific(&mut s_exp) {
+            s = s_exp;
+            exponent = exponent.saturating_add(exp);
+        }
+    }
+
+    let len = s.offset_from(start) as _;
+    Some((round_binary::<F>(mantissa, exponent, truncated), len))
+}
+
+/// Round `mantissa * 2^exponent` to the nearest float, ties to even.
+///
+/// `truncated` indicates that nonzero bits below `mantissa` were dropped,
+/// which breaks any tie upwards.
+fn round_binary<F: RawFloat>(mantissa: u64, exponent: i64, truncated: bool) -> BiasedFp {
+    let fp_zero = BiasedFp::zero_pow2(0);
+    let fp_inf = BiasedFp::zero_pow2(F::INFINITE_POWER);
+    if mantissa == 0 {
+        return fp_zero;
+    }
+
+    // Normalize so the value is `1.xxx * 2^power2`, with the leading bit at position 63.
+    let lz = mantissa.leading_zeros();
+    let mantissa = mantissa << lz;
+    let power2 = exponent.saturating_add(63 - lz as i64);
+    let mut biased = power2.saturating_sub(F::MINIMUM_EXPONENT as i64);
+    if biased >= F::INFINITE_POWER as i64 {
+        return fp_inf;
+    }
+
+    // Subnormals have a fixed exponent, and lose precision instead.
+    let mut shift = 63 - F::MANTISSA_EXPLICIT_BITS as i64;
+    if biased <= 0 {
+        shift += 1 - biased;
+        biased = 0;
+    }
+    if shift > 64 {
+        // Below half of the smallest subnormal.
+        return fp_zero;
+    }
+
+    let (mut f, rest) = if shift == 64 {
+        (0, mantissa)
+    } else {
+        (mantissa >> shift, mantissa & ((1 << shift) - 1))
+    };
+    let halfway = 1_u64 << (shift - 1);
+    if rest > halfway || (rest == halfway && (truncated || f & 1 == 1)) {
+        f += 1;
+    }
+
+    if biased == 0 {
+        // Rounding a subnormal up may carry into the hidden bit, which
+        // correctly encodes the smallest normal exponent.
+        return BiasedFp { f, e: 0 };
+    }
+    if f >> (F::MANTISSA_EXPLICIT_BITS + 1) != 0 {
+        f >>= 1;
+        biased += 1;
+        if biased >= F::INFINITE_POWER as i64 {
+            return fp_inf;
+        }
+    }
+    BiasedFp { f: f & ((1 << F::MANTISSA_EXPLICIT_BITS) - 1), e: biased as i32 }
+}
+
+/// Try to parse a special, non-finite float at the start of `s`,
+/// along with the number of bytes consumed.
+pub(crate) fn parse_partial_inf_nan<F: RawFloat>(s: &[u8], negative: bool) -> Option<(F, usize)> {
+    let starts_with = |prefix: &[u8]| {
+        s.len() >= prefix.len() && s[..prefix.len()].eq_ignore_ascii_case(prefix)
+    };
+
+    let (float, len) = if starts_with(b"infinity") {
+        (F::INFINITY, 8)
+    } else if starts_with(b"inf") {
+        (F::INFINITY, 3)
+    } else if starts_with(b"nan") {
+        (F::NAN, 3)
+    } else {
+        return None;
+    };
+
+    if negative { Some((-float, len)) } else { Some((float, len)) }
+}
+
 /// Try to parse a special, non-finite float.
 pub(crate) fn parse_inf_nan<F: RawFloat>(s: &[u8], negative: bool) -> Option<F> {
     // Since a valid string has at most the length 8, we can load
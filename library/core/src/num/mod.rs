@@ -47,6 +47,7 @@ macro_rules! unlikely {
 mod nonzero;
 mod overflow_panic;
 mod saturating;
+pub(crate) mod wide;
 mod wrapping;
 
 #[stable(feature = "saturating_int_impl", since = "1.74.0")]
@@ -57,6 +58,9 @@ macro_rules! unlikely {
 #[stable(feature = "rust1", since = "1.0.0")]
 #[cfg(not(no_fp_fmt_parse))]
 pub use dec2flt::ParseFloatError;
+#[unstable(feature = "float_parse_options", issue = "none")]
+#[cfg(not(no_fp_fmt_parse))]
+pub use dec2flt::ParseFloatOptions;
 
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use error::ParseIntError;
@@ -157,7 +161,7 @@ pub const fn midpoint(self, rhs: $SelfT) -> $SelfT {
 }
 
 macro_rules! widening_impl {
-    ($SelfT:ty, $WideT:ty, $BITS:literal, unsigned) => {
+    ($SelfT:ty, unsigned, $carrying_mul_add:path, $div_rem_wide:path) => {
         /// Calculates the complete product `self * rhs` without the possibility to overflow.
         ///
         /// This returns the low-order (wrapping) bits and the high-order (overflow) bits
@@ -184,11 +188,7 @@ macro_rules! widening_impl {
                       without modifying the original"]
         #[inline]
         pub const fn widening_mul(self, rhs: Self) -> (Self, Self) {
-            // note: longer-term this should be done via an intrinsic,
-            //   but for now we can deal without an impl for u128/i128
-            // SAFETY: overflow will be contained within the wider types
-            let wide = unsafe { (self as $WideT).unchecked_mul(rhs as $WideT) };
-            (wide as $SelfT, (wide >> $BITS) as $SelfT)
+            $carrying_mul_add(self, rhs, 0, 0)
         }
 
         /// Calculates the "full multiplication" `self * rhs + carry`
@@ -275,13 +275,229 @@ pub const fn widening_mul(self, rhs: Self) -> (Self, Self) {
                       without modifying the original"]
         #[inline]
         pub const fn carrying_mul(self, rhs: Self, carry: Self) -> (Self, Self) {
-            // note: longer-term this should be done via an intrinsic,
-            //   but for now we can deal without an impl for u128/i128
-            // SAFETY: overflow will be contained within the wider types
-            let wide = unsafe {
-                (self as $WideT).unchecked_mul(rhs as $WideT).unchecked_add(carry as $WideT)
-            };
-            (wide as $SelfT, (wide >> $BITS) as $SelfT)
+            $carrying_mul_add(self, rhs, carry, 0)
+        }
+
+        /// Calculates the "full multiplication" `self * rhs + carry + add`
+        /// without the possibility to overflow.
+        ///
+        /// This returns the low-order (wrapping) bits and the high-order (overflow) bits
+        /// of the result as two separate values, in that order.
+        ///
+        /// This is the operation at the heart of schoolbook multiplication of big integers:
+        /// `add` is the digit of the accumulated result, and `carry` the overflow of the
+        /// previous digit's product. Even when all four values are `MAX`, the result fits.
+        ///
+        /// # Examples
+        ///
+        /// Please note that this example is shared between integer types.
+        /// Which explains why `u32` is used here.
+        ///
+        /// ```
+        /// #![feature(bigint_helper_methods)]
+        /// assert_eq!(5u32.carrying_mul_add(2, 0, 0), (10, 0));
+        /// assert_eq!(5u32.carrying_mul_add(2, 10, 10), (30, 0));
+        /// assert_eq!(1_000_000_000u32.carrying_mul_add(10, 0, 0), (1410065408, 2));
+        /// assert_eq!(1_000_000_000u32.carrying_mul_add(10, 10, 10), (1410065428, 2));
+        #[doc = concat!("assert_eq!(",
+            stringify!($SelfT), "::MAX.carrying_mul_add(", stringify!($SelfT), "::MAX, ",
+            stringify!($SelfT), "::MAX, ", stringify!($SelfT), "::MAX), ",
+            "(", stringify!($SelfT), "::MAX, ", stringify!($SelfT), "::MAX));"
+        )]
+        /// ```
+        ///
+        /// Multiplying two little-endian big integers:
+        ///
+        /// ```
+        /// #![feature(bigint_helper_methods)]
+        /// fn mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+        ///     let mut result = vec![0; a.len() + b.len()];
+        ///     for (i, &x) in a.iter().enumerate() {
+        ///         let mut carry = 0;
+        ///         for (j, &y) in b.iter().enumerate() {
+        ///             (result[i + j], carry) = x.carrying_mul_add(y, carry, result[i + j]);
+        ///         }
+        ///         result[i + b.len()] = carry;
+        ///     }
+        ///     result
+        /// }
+        ///
+        /// // 0xFFFF_FFFF_FFFF_FFFF² = 0xFFFF_FFFF_FFFF_FFFE_0000_0000_0000_0001
+        /// assert_eq!(mul(&[u32::MAX; 2], &[u32::MAX; 2]), [1, 0, u32::MAX - 1, u32::MAX]);
+        /// ```
+        #[unstable(feature = "bigint_helper_methods", issue = "85532")]
+        #[rustc_const_unstable(feature = "const_bigint_helper_methods", issue = "85532")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
+        #[inline]
+        pub const fn carrying_mul_add(self, rhs: Self, carry: Self, add: Self) -> (Self, Self) {
+            $carrying_mul_add(self, rhs, carry, add)
+        }
+
+        /// Calculates the quotient and remainder of the double-width integer `high:self`,
+        /// whose low-order bits are `self` and high-order bits are `high`, divided by `rhs`.
+        ///
+        /// The halves of the dividend come first, low-order first, in the same order as
+        /// [`widening_mul`](Self::widening_mul) and [`carrying_mul`](Self::carrying_mul)
+        /// return them: their `(low, high)` result divides as `low.div_rem_wide(high, rhs)`.
+        ///
+        /// This is the inverse of `carrying_mul`: the quotient and remainder are returned
+        /// in that order, and `quotient.carrying_mul(rhs, remainder) == (self, high)`.
+        ///
+        /// This is the core operation needed to divide a big integer by a single digit,
+        /// where `high` is the remainder of dividing the previous, more significant, digits.
+        ///
+        /// # Panics
+        ///
+        /// This function will panic if `rhs` is zero, or if `high` is not less than `rhs`,
+        /// in which case the quotient would not fit in `Self`.
+        ///
+        /// # Examples
+        ///
+        /// Please note that this example is shared between integer types.
+        /// Which explains why `u32` is used here.
+        ///
+        /// ```
+        /// #![feature(bigint_helper_methods)]
+        /// assert_eq!(10u32.div_rem_wide(0, 3), (3, 1));
+        /// // (2 << 32 | 1410065408) = 10_000_000_000
+        /// assert_eq!(1410065408u32.div_rem_wide(2, 1_000_000_000), (10, 0));
+        /// assert_eq!(u32::MAX.div_rem_wide(u32::MAX - 1, u32::MAX), (u32::MAX, u32::MAX - 1));
+        /// ```
+        ///
+        /// Dividing a little-endian big integer by a digit:
+        ///
+        /// ```
+        /// #![feature(bigint_helper_methods)]
+        /// fn div_rem(digits: &mut [u16], divisor: u16) -> u16 {
+        ///     let mut rem = 0;
+        ///     for d in digits.iter_mut().rev() {
+        ///         (*d, rem) = d.div_rem_wide(rem, divisor);
+        ///     }
+        ///     rem
+        /// }
+        ///
+        /// // 0x8765_4321 = 0x1234 * 0x0007_7023 + 0x0605
+        /// let mut v = [0x4321, 0x8765];
+        /// assert_eq!(div_rem(&mut v, 0x1234), 0x0605);
+        /// assert_eq!(v, [0x7023, 0x0007]);
+        /// ```
+        #[unstable(feature = "bigint_helper_methods", issue = "85532")]
+        #[rustc_const_unstable(feature = "const_bigint_helper_methods", issue = "85532")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
+        #[inline]
+        #[track_caller]
+        pub const fn div_rem_wide(self, high: Self, rhs: Self) -> (Self, Self) {
+            assert!(rhs != 0, "attempt to divide by zero");
+            assert!(high < rhs, "attempt to divide with overflow");
+            $div_rem_wide(self, high, rhs)
+        }
+    };
+    ($SelfT:ty, $UnsignedT:ty, signed, $carrying_mul_add:path) => {
+        /// Calculates the complete product `self * rhs` without the possibility to overflow.
+        ///
+        /// This returns the low-order (wrapping) bits and the high-order (overflow) bits
+        /// of the result as two separate values, in that order. As with all signed big
+        /// integers, only the most significant part carries the sign, so the low-order bits
+        #[doc = concat!("are returned as a [`", stringify!($UnsignedT), "`].")]
+        ///
+        /// If you also need to add a carry to the wide result, then you want
+        /// [`Self::carrying_mul`] instead.
+        ///
+        /// # Examples
+        ///
+        /// Please note that this example is shared between integer types.
+        /// Which explains why `i32` is used here.
+        ///
+        /// ```
+        /// #![feature(bigint_helper_methods)]
+        /// assert_eq!(5i32.widening_mul(-2), (4294967286, -1));
+        /// assert_eq!(1_000_000_000i32.widening_mul(-10), (2884901888, -3));
+        /// ```
+        #[unstable(feature = "bigint_helper_methods", issue = "85532")]
+        #[rustc_const_unstable(feature = "const_bigint_helper_methods", issue = "85532")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
+        #[inline]
+        pub const fn widening_mul(self, rhs: Self) -> ($UnsignedT, Self) {
+            $carrying_mul_add(self, rhs, 0, 0)
+        }
+
+        /// Calculates the "full multiplication" `self * rhs + carry`
+        /// without the possibility to overflow.
+        ///
+        /// This returns the low-order (wrapping) bits and the high-order (overflow) bits
+        /// of the result as two separate values, in that order. As with all signed big
+        /// integers, only the most significant part carries the sign, so the low-order bits
+        #[doc = concat!("are returned as a [`", stringify!($UnsignedT), "`].")]
+        ///
+        /// If you don't need the `carry`, then you can use [`Self::widening_mul`] instead.
+        ///
+        /// # Examples
+        ///
+        /// Please note that this example is shared between integer types.
+        /// Which explains why `i32` is used here.
+        ///
+        /// ```
+        /// #![feature(bigint_helper_methods)]
+        /// assert_eq!(5i32.carrying_mul(-2, 0), (4294967286, -1));
+        /// assert_eq!(5i32.carrying_mul(-2, 10), (0, 0));
+        /// assert_eq!(1_000_000_000i32.carrying_mul(-10, 0), (2884901888, -3));
+        /// assert_eq!(1_000_000_000i32.carrying_mul(-10, 10), (2884901898, -3));
+        #[doc = concat!("assert_eq!(",
+            stringify!($SelfT), "::MAX.carrying_mul(", stringify!($SelfT), "::MAX, ", stringify!($SelfT), "::MAX), ",
+            "(", stringify!($UnsignedT), "::MAX / 2 + 1, ", stringify!($SelfT), "::MAX / 2));"
+        )]
+        /// ```
+        #[unstable(feature = "bigint_helper_methods", issue = "85532")]
+        #[rustc_const_unstable(feature = "const_bigint_helper_methods", issue = "85532")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
+        #[inline]
+        pub const fn carrying_mul(self, rhs: Self, carry: Self) -> ($UnsignedT, Self) {
+            $carrying_mul_add(self, rhs, carry, 0)
+        }
+
+        /// Calculates the "full multiplication" `self * rhs + carry + add`
+        /// without the possibility to overflow.
+        ///
+        /// This returns the low-order (wrapping) bits and the high-order (overflow) bits
+        /// of the result as two separate values, in that order. As with all signed big
+        /// integers, only the most significant part carries the sign, so the low-order bits
+        #[doc = concat!("are returned as a [`", stringify!($UnsignedT), "`].")]
+        ///
+        /// Even at the extremes of the signed range, the result fits.
+        ///
+        /// # Examples
+        ///
+        /// Please note that this example is shared between integer types.
+        /// Which explains why `i32` is used here.
+        ///
+        /// ```
+        /// #![feature(bigint_helper_methods)]
+        /// assert_eq!(5i32.carrying_mul_add(-2, 0, 0), (4294967286, -1));
+        /// assert_eq!(5i32.carrying_mul_add(-2, 10, 10), (10, 0));
+        /// assert_eq!(1_000_000_000i32.carrying_mul_add(-10, 0, 0), (2884901888, -3));
+        /// assert_eq!(1_000_000_000i32.carrying_mul_add(-10, 10, 10), (2884901908, -3));
+        #[doc = concat!("assert_eq!(",
+            stringify!($SelfT), "::MIN.carrying_mul_add(", stringify!($SelfT), "::MIN, ",
+            stringify!($SelfT), "::MIN, ", stringify!($SelfT), "::MIN), ",
+            "(0, ", stringify!($SelfT), "::MAX / 2));"
+        )]
+        /// ```
+        #[unstable(feature = "bigint_helper_methods", issue = "85532")]
+        #[rustc_const_unstable(feature = "const_bigint_helper_methods", issue = "85532")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
+        #[inline]
+        pub const fn carrying_mul_add(
+            self,
+            rhs: Self,
+            carry: Self,
+            add: Self,
+        ) -> ($UnsignedT, Self) {
+            $carrying_mul_add(self, rhs, carry, add)
         }
     };
 }
@@ -307,6 +523,7 @@ impl i8 {
         from_xe_bytes_doc = "",
         bound_condition = "",
     }
+    widening_impl! { i8, u8, signed, wide::carrying_mul_add_i8 }
 }
 
 impl i16 {
@@ -330,6 +547,7 @@ impl i16 {
         from_xe_bytes_doc = "",
         bound_condition = "",
     }
+    widening_impl! { i16, u16, signed, wide::carrying_mul_add_i16 }
 }
 
 impl i32 {
@@ -353,6 +571,7 @@ impl i32 {
         from_xe_bytes_doc = "",
         bound_condition = "",
     }
+    widening_impl! { i32, u32, signed, wide::carrying_mul_add_i32 }
 }
 
 impl i64 {
@@ -376,6 +595,7 @@ impl i64 {
         from_xe_bytes_doc = "",
         bound_condition = "",
     }
+    widening_impl! { i64, u64, signed, wide::carrying_mul_add_i64 }
 }
 
 impl i128 {
@@ -401,6 +621,7 @@ impl i128 {
         from_xe_bytes_doc = "",
         bound_condition = "",
     }
+    widening_impl! { i128, u128, signed, wide::carrying_mul_add_i128 }
 }
 
 #[cfg(target_pointer_width = "16")]
@@ -425,6 +646,7 @@ impl isize {
         from_xe_bytes_doc = usize_isize_from_xe_bytes_doc!(),
         bound_condition = " on 16-bit targets",
     }
+    widening_impl! { isize, usize, signed, wide::carrying_mul_add_isize }
 }
 
 #[cfg(target_pointer_width = "32")]
@@ -449,6 +671,7 @@ impl isize {
         from_xe_bytes_doc = usize_isize_from_xe_bytes_doc!(),
         bound_condition = " on 32-bit targets",
     }
+    widening_impl! { isize, usize, signed, wide::carrying_mul_add_isize }
 }
 
 #[cfg(target_pointer_width = "64")]
@@ -473,6 +696,7 @@ impl isize {
         from_xe_bytes_doc = usize_isize_from_xe_bytes_doc!(),
         bound_condition = " on 64-bit targets",
     }
+    widening_impl! { isize, usize, signed, wide::carrying_mul_add_isize }
 }
 
 /// If the 6th bit is set ascii is lower case.
@@ -498,7 +722,7 @@ impl u8 {
         from_xe_bytes_doc = "",
         bound_condition = "",
     }
-    widening_impl! { u8, u16, 8, unsigned }
+    widening_impl! { u8, unsigned, wide::carrying_mul_add_u8, wide::div_rem_wide_u8 }
     midpoint_impl! { u8, u16, unsigned }
 
     /// Checks if the value is within the ASCII range.
@@ -1113,7 +1337,7 @@ impl u16 {
         from_xe_bytes_doc = "",
         bound_condition = "",
     }
-    widening_impl! { u16, u32, 16, unsigned }
+    widening_impl! { u16, unsigned, wide::carrying_mul_add_u16, wide::div_rem_wide_u16 }
     midpoint_impl! { u16, u32, unsigned }
 
     /// Checks if the value is a Unicode surrogate code point, which are disallowed values for [`char`].
@@ -1162,7 +1386,7 @@ impl u32 {
         from_xe_bytes_doc = "",
         bound_condition = "",
     }
-    widening_impl! { u32, u64, 32, unsigned }
+    widening_impl! { u32, unsigned, wide::carrying_mul_add_u32, wide::div_rem_wide_u32 }
     midpoint_impl! { u32, u64, unsigned }
 }
 
@@ -1186,7 +1410,7 @@ impl u64 {
         from_xe_bytes_doc = "",
         bound_condition = "",
     }
-    widening_impl! { u64, u128, 64, unsigned }
+    widening_impl! { u64, unsigned, wide::carrying_mul_add_u64, wide::div_rem_wide_u64 }
     midpoint_impl! { u64, u128, unsigned }
 }
 
@@ -1212,6 +1436,7 @@ impl u128 {
         from_xe_bytes_doc = "",
         bound_condition = "",
     }
+    widening_impl! { u128, unsigned, wide::carrying_mul_add_u128, wide::div_rem_wide_u128 }
     midpoint_impl! { u128, unsigned }
 }
 
@@ -1236,7 +1461,7 @@ impl usize {
         from_xe_bytes_doc = usize_isize_from_xe_bytes_doc!(),
         bound_condition = " on 16-bit targets",
     }
-    widening_impl! { usize, u32, 16, unsigned }
+    widening_impl! { usize, unsigned, wide::carrying_mul_add_usize, wide::div_rem_wide_usize }
     midpoint_impl! { usize, u32, unsigned }
 }
 
@@ -1261,7 +1486,7 @@ impl usize {
         from_xe_bytes_doc = usize_isize_from_xe_bytes_doc!(),
         bound_condition = " on 32-bit targets",
     }
-    widening_impl! { usize, u64, 32, unsigned }
+    widening_impl! { usize, unsigned, wide::carrying_mul_add_usize, wide::div_rem_wide_usize }
     midpoint_impl! { usize, u64, unsigned }
 }
 
@@ -1286,7 +1511,7 @@ impl usize {
         from_xe_bytes_doc = usize_isize_from_xe_bytes_doc!(),
         bound_condition = " on 64-bit targets",
     }
-    widening_impl! { usize, u128, 64, unsigned }
+    widening_impl! { usize, unsigned, wide::carrying_mul_add_usize, wide::div_rem_wide_usize }
     midpoint_impl! { usize, u128, unsigned }
 }
 
@@ -1419,7 +1644,7 @@ const fn from_str_radix_panic(radix: u32) {
 }
 
 macro_rules! from_str_radix {
-    ($($int_ty:ty)+) => {$(
+    ($sign:literal => $($int_ty:ty)+) => {$(
         impl $int_ty {
             /// Converts a string slice in a given base to an integer.
             ///
@@ -1446,6 +1671,144 @@ impl $int_ty {
             #[stable(feature = "rust1", since = "1.0.0")]
             #[rustc_const_unstable(feature = "const_int_from_str", issue = "59133")]
             pub const fn from_str_radix(src: &str, radix: u32) -> Result<$int_ty, ParseIntError> {
+                match <$int_ty>::parse_ascii::<false, false>(src.as_bytes(), radix) {
+                    Ok((value, _)) => Ok(value),
+                    Err(e) => Err(e),
+                }
+            }
+
+            /// Parses an integer from an ASCII-byte slice with decimal digits.
+            ///
+            #[doc = concat!("The characters are expected to be an optional ", $sign, " sign")]
+            /// followed by only digits. Leading and trailing non-digit characters
+            /// (including whitespace) represent an error. Underscores (which are
+            /// accepted in Rust literals) also represent an error.
+            ///
+            /// # Examples
+            ///
+            /// Basic usage:
+            ///
+            /// ```
+            /// #![feature(int_from_ascii)]
+            ///
+            #[doc = concat!("assert_eq!(", stringify!($int_ty), "::from_ascii(b\"+10\"), Ok(10));")]
+            #[doc = concat!("assert!(", stringify!($int_ty), "::from_ascii(b\"1 \").is_err());")]
+            /// ```
+            #[unstable(feature = "int_from_ascii", issue = "none")]
+            #[rustc_const_unstable(feature = "int_from_ascii", issue = "none")]
+            #[inline]
+            pub const fn from_ascii(src: &[u8]) -> Result<$int_ty, ParseIntError> {
+                <$int_ty>::from_ascii_radix(src, 10)
+            }
+
+            /// Parses an integer from an ASCII-byte slice with digits in the given base.
+            ///
+            #[doc = concat!("The characters are expected to be an optional ", $sign, " sign")]
+            /// followed by only digits. Leading and trailing non-digit characters
+            /// (including whitespace) represent an error. Underscores (which are
+            /// accepted in Rust literals) also represent an error.
+            ///
+            /// Digits are a subset of these characters, depending on `radix`:
+            ///
+            /// * `0-9`
+            /// * `a-z`
+            /// * `A-Z`
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `radix` is not in the range from 2 to 36.
+            ///
+            /// # Examples
+            ///
+            /// Basic usage:
+            ///
+            /// ```
+            /// #![feature(int_from_ascii)]
+            ///
+            #[doc = concat!("assert_eq!(", stringify!($int_ty), "::from_ascii_radix(b\"A\", 16), Ok(10));")]
+            /// ```
+            #[unstable(feature = "int_from_ascii", issue = "none")]
+            #[rustc_const_unstable(feature = "int_from_ascii", issue = "none")]
+            pub const fn from_ascii_radix(src: &[u8], radix: u32) -> Result<$int_ty, ParseIntError> {
+                match <$int_ty>::parse_ascii::<false, false>(src, radix) {
+                    Ok((value, _)) => Ok(value),
+                    Err(e) => Err(e),
+                }
+            }
+
+            /// Parses an integer from the start of an ASCII-byte slice with digits in the
+            /// given base, returning it along with the remaining input.
+            ///
+            #[doc = concat!("The characters are expected to start with an optional ", $sign)]
+            /// sign followed by at least one digit. Parsing stops at the first character that
+            /// is not a digit in the given base.
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `radix` is not in the range from 2 to 36.
+            ///
+            /// # Examples
+            ///
+            /// Basic usage:
+            ///
+            /// ```
+            /// #![feature(int_from_ascii)]
+            ///
+            #[doc = concat!("assert_eq!(", stringify!($int_ty), "::from_ascii_radix_partial(b\"12,34\", 10), Ok((12, &b\",34\"[..])));")]
+            #[doc = concat!("assert!(", stringify!($int_ty), "::from_ascii_radix_partial(b\",34\", 10).is_err());")]
+            /// ```
+            #[unstable(feature = "int_from_ascii", issue = "none")]
+            #[rustc_const_unstable(feature = "int_from_ascii", issue = "none")]
+            pub const fn from_ascii_radix_partial(
+                src: &[u8],
+                radix: u32,
+            ) -> Result<($int_ty, &[u8]), ParseIntError> {
+                match <$int_ty>::parse_ascii::<true, false>(src, radix) {
+                    Ok((value, len)) => Ok((value, src.split_at(len).1)),
+                    Err(e) => Err(e),
+                }
+            }
+
+            /// Parses an integer from an ASCII-byte slice, using the syntax of Rust integer
+            /// literals.
+            ///
+            #[doc = concat!("The characters are expected to be an optional ", $sign, " sign,")]
+            /// an optional `0x`, `0o` or `0b` prefix selecting base 16, 8 or 2 respectively
+            /// (base 10 otherwise), and a digit followed by digits and `_` separators.
+            ///
+            /// # Examples
+            ///
+            /// Basic usage:
+            ///
+            /// ```
+            /// #![feature(int_from_ascii)]
+            ///
+            #[doc = concat!("assert_eq!(", stringify!($int_ty), "::from_ascii_prefixed(b\"0x7f\"), Ok(0x7f));")]
+            #[doc = concat!("assert_eq!(", stringify!($int_ty), "::from_ascii_prefixed(b\"0b_\").is_err(), true);")]
+            #[doc = concat!("assert_eq!(", stringify!($int_ty), "::from_ascii_prefixed(b\"1_0\"), Ok(10));")]
+            /// ```
+            #[unstable(feature = "int_from_ascii", issue = "none")]
+            #[rustc_const_unstable(feature = "int_from_ascii", issue = "none")]
+            pub const fn from_ascii_prefixed(src: &[u8]) -> Result<$int_ty, ParseIntError> {
+                match <$int_ty>::parse_ascii::<false, true>(src, 10) {
+                    Ok((value, _)) => Ok(value),
+                    Err(e) => Err(e),
+                }
+            }
+
+            /// Shared implementation of the parsing functions above, returning the parsed
+            /// value and the number of bytes consumed.
+            ///
+            /// If `PARTIAL` is set, parsing stops at the first non-digit instead of
+            /// returning an error. If `PREFIXED` is set, `radix` is replaced according to
+            /// the prefix of the digits, and `_` separators are skipped. These are const
+            /// parameters so that each entry point gets its own copy of the parser, without
+            /// the checks it doesn't need.
+            #[inline(always)]
+            const fn parse_ascii<const PARTIAL: bool, const PREFIXED: bool>(
+                src: &[u8],
+                mut radix: u32,
+            ) -> Result<($int_ty, usize), ParseIntError> {
                 use self::IntErrorKind::*;
                 use self::ParseIntError as PIE;
 
@@ -1464,8 +1827,6 @@ pub const fn from_str_radix(src: &str, radix: u32) -> Result<$int_ty, ParseIntEr
                 // and cast them to chars. .to_digit() will safely return None for anything
                 // other than a valid ascii digit for the given radix, including the first-byte
                 // of multi-byte sequences
-                let src = src.as_bytes();
-
                 let (is_positive, mut digits) = match src {
                     [b'+' | b'-'] => {
                         return Err(PIE { kind: InvalidDigit });
@@ -1475,6 +1836,20 @@ pub const fn from_str_radix(src: &str, radix: u32) -> Result<$int_ty, ParseIntEr
                     _ => (true, src),
                 };
 
+                if PREFIXED {
+                    (radix, digits) = match digits {
+                        [b'0', b'x', rest @ ..] => (16, rest),
+                        [b'0', b'o', rest @ ..] => (8, rest),
+                        [b'0', b'b', rest @ ..] => (2, rest),
+                        _ => (10, digits),
+                    };
+                    // Separators may only follow a digit.
+                    if let [] | [b'_', ..] = digits {
+                        return Err(PIE { kind: InvalidDigit });
+                    }
+                }
+
+                let n_digits = digits.len();
                 let mut result = 0;
 
                 macro_rules! unwrap_or_PIE {
@@ -1486,6 +1861,21 @@ macro_rules! unwrap_or_PIE {
                     };
                 }
 
+                // Converts `c` to a digit, or skips it, stops parsing or fails as requested.
+                macro_rules! next_digit {
+                    ($c:expr, $rest:expr) => {
+                        match ($c as char).to_digit(radix) {
+                            Some(x) => x,
+                            None if PREFIXED && $c == b'_' => {
+                                digits = $rest;
+                                continue;
+                            }
+                            None if PARTIAL => break,
+                            None => return Err(PIE { kind: InvalidDigit }),
+                        }
+                    };
+                }
+
                 if can_not_overflow::<$int_ty>(radix, is_signed_ty, digits) {
                     // If the len of the str is short compared to the range of the type
                     // we are parsing into, then we can be certain that an overflow will not occur.
@@ -1498,8 +1888,8 @@ macro_rules! unwrap_or_PIE {
                     macro_rules! run_unchecked_loop {
                         ($unchecked_additive_op:tt) => {{
                             while let [c, rest @ ..] = digits {
+                                let x = next_digit!(*c, rest);
                                 result = result * (radix as $int_ty);
-                                let x = unwrap_or_PIE!((*c as char).to_digit(radix), InvalidDigit);
                                 result = result $unchecked_additive_op (x as $int_ty);
                                 digits = rest;
                             }
@@ -1525,7 +1915,7 @@ macro_rules! run_checked_loop {
                                 // doing multiplication first and let the CPU spends other cycles
                                 // doing other computation and get multiplication result later.
                                 let mul = result.checked_mul(radix as $int_ty);
-                                let x = unwrap_or_PIE!((*c as char).to_digit(radix), InvalidDigit) as $int_ty;
+                                let x = next_digit!(*c, rest) as $int_ty;
                                 result = unwrap_or_PIE!(mul, $overflow_err);
                                 result = unwrap_or_PIE!(<$int_ty>::$checked_additive_op(result, x), $overflow_err);
                                 digits = rest;
@@ -1538,18 +1928,24 @@ macro_rules! run_checked_loop {
                         run_checked_loop!(checked_sub, NegOverflow)
                     };
                 }
-                Ok(result)
+
+                if PARTIAL && digits.len() == n_digits {
+                    // The first digit is invalid.
+                    return Err(PIE { kind: InvalidDigit });
+                }
+                Ok((result, src.len() - digits.len()))
             }
         }
     )+}
 }
 
-from_str_radix! { i8 u8 i16 u16 i32 u32 i64 u64 i128 u128 }
+from_str_radix! { "`+` or `-`" => i8 i16 i32 i64 i128 }
+from_str_radix! { "`+`" => u8 u16 u32 u64 u128 }
 
 // Re-use the relevant implementation of from_str_radix for isize and usize to avoid outputting two
 // identical functions.
 macro_rules! from_str_radix_size_impl {
-    ($($t:ident $size:ty),*) => {$(
+    ($($sign:literal $t:ident $size:ty),*) => {$(
     impl $size {
         /// Converts a string slice in a given base to an integer.
         ///
@@ -1581,12 +1977,137 @@ pub const fn from_str_radix(src: &str, radix: u32) -> Result<$size, ParseIntErro
                 Err(e) => Err(e),
             }
         }
+
+        /// Parses an integer from an ASCII-byte slice with decimal digits.
+        ///
+        #[doc = concat!("The characters are expected to be an optional ", $sign, " sign")]
+        /// followed by only digits. Leading and trailing non-digit characters
+        /// (including whitespace) represent an error. Underscores (which are
+        /// accepted in Rust literals) also represent an error.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```
+        /// #![feature(int_from_ascii)]
+        ///
+        #[doc = concat!("assert_eq!(", stringify!($size), "::from_ascii(b\"+10\"), Ok(10));")]
+        #[doc = concat!("assert!(", stringify!($size), "::from_ascii(b\"1 \").is_err());")]
+        /// ```
+        #[unstable(feature = "int_from_ascii", issue = "none")]
+        #[rustc_const_unstable(feature = "int_from_ascii", issue = "none")]
+        #[inline]
+        pub const fn from_ascii(src: &[u8]) -> Result<$size, ParseIntError> {
+            match <$t>::from_ascii(src) {
+                Ok(x) => Ok(x as $size),
+                Err(e) => Err(e),
+            }
+        }
+
+        /// Parses an integer from an ASCII-byte slice with digits in the given base.
+        ///
+        #[doc = concat!("The characters are expected to be an optional ", $sign, " sign")]
+        /// followed by only digits. Leading and trailing non-digit characters
+        /// (including whitespace) represent an error. Underscores (which are
+        /// accepted in Rust literals) also represent an error.
+        ///
+        /// Digits are a subset of these characters, depending on `radix`:
+        ///
+        /// * `0-9`
+        /// * `a-z`
+        /// * `A-Z`
+        ///
+        /// # Panics
+        ///
+        /// This function panics if `radix` is not in the range from 2 to 36.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```
+        /// #![feature(int_from_ascii)]
+        ///
+        #[doc = concat!("assert_eq!(", stringify!($size), "::from_ascii_radix(b\"A\", 16), Ok(10));")]
+        /// ```
+        #[unstable(feature = "int_from_ascii", issue = "none")]
+        #[rustc_const_unstable(feature = "int_from_ascii", issue = "none")]
+        #[inline]
+        pub const fn from_ascii_radix(src: &[u8], radix: u32) -> Result<$size, ParseIntError> {
+            match <$t>::from_ascii_radix(src, radix) {
+                Ok(x) => Ok(x as $size),
+                Err(e) => Err(e),
+            }
+        }
+
+        /// Parses an integer from the start of an ASCII-byte slice with digits in the
+        /// given base, returning it along with the remaining input.
+        ///
+        #[doc = concat!("The characters are expected to start with an optional ", $sign)]
+        /// sign followed by at least one digit. Parsing stops at the first character that
+        /// is not a digit in the given base.
+        ///
+        /// # Panics
+        ///
+        /// This function panics if `radix` is not in the range from 2 to 36.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```
+        /// #![feature(int_from_ascii)]
+        ///
+        #[doc = concat!("assert_eq!(", stringify!($size), "::from_ascii_radix_partial(b\"12,34\", 10), Ok((12, &b\",34\"[..])));")]
+        #[doc = concat!("assert!(", stringify!($size), "::from_ascii_radix_partial(b\",34\", 10).is_err());")]
+        /// ```
+        #[unstable(feature = "int_from_ascii", issue = "none")]
+        #[rustc_const_unstable(feature = "int_from_ascii", issue = "none")]
+        #[inline]
+        pub const fn from_ascii_radix_partial(
+            src: &[u8],
+            radix: u32,
+        ) -> Result<($size, &[u8]), ParseIntError> {
+            match <$t>::from_ascii_radix_partial(src, radix) {
+                Ok((x, rest)) => Ok((x as $size, rest)),
+                Err(e) => Err(e),
+            }
+        }
+
+        /// Parses an integer from an ASCII-byte slice, using the syntax of Rust integer
+        /// literals.
+        ///
+        #[doc = concat!("The characters are expected to be an optional ", $sign, " sign,")]
+        /// an optional `0x`, `0o` or `0b` prefix selecting base 16, 8 or 2 respectively
+        /// (base 10 otherwise), and a digit followed by digits and `_` separators.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```
+        /// #![feature(int_from_ascii)]
+        ///
+        #[doc = concat!("assert_eq!(", stringify!($size), "::from_ascii_prefixed(b\"0x7f\"), Ok(0x7f));")]
+        #[doc = concat!("assert_eq!(", stringify!($size), "::from_ascii_prefixed(b\"0b_\").is_err(), true);")]
+        #[doc = concat!("assert_eq!(", stringify!($size), "::from_ascii_prefixed(b\"1_0\"), Ok(10));")]
+        /// ```
+        #[unstable(feature = "int_from_ascii", issue = "none")]
+        #[rustc_const_unstable(feature = "int_from_ascii", issue = "none")]
+        #[inline]
+        pub const fn from_ascii_prefixed(src: &[u8]) -> Result<$size, ParseIntError> {
+            match <$t>::from_ascii_prefixed(src) {
+                Ok(x) => Ok(x as $size),
+                Err(e) => Err(e),
+            }
+        }
     })*}
 }
 
 #[cfg(target_pointer_width = "16")]
-from_str_radix_size_impl! { i16 isize, u16 usize }
+from_str_radix_size_impl! { "`+` or `-`" i16 isize, "`+`" u16 usize }
 #[cfg(target_pointer_width = "32")]
-from_str_radix_size_impl! { i32 isize, u32 usize }
+from_str_radix_size_impl! { "`+` or `-`" i32 isize, "`+`" u32 usize }
 #[cfg(target_pointer_width = "64")]
-from_str_radix_size_impl! { i64 isize, u64 usize }
+from_str_radix_size_impl! { "`+` or `-`" i64 isize, "`+`" u64 usize }
@@ -817,6 +817,75 @@ fn from_str(src: &str) -> Result<Self, Self::Err> {
             }
         }
 
+        impl NonZero<$Int> {
+            /// Parses a non-zero integer from an ASCII-byte slice with decimal digits.
+            ///
+            #[doc = concat!("See [`", stringify!($Int), "::from_ascii`] for the accepted syntax.")]
+            /// Parsing zero returns an error of kind [`IntErrorKind::Zero`].
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// #![feature(int_from_ascii)]
+            /// # use std::num::NonZero;
+            ///
+            #[doc = concat!("assert_eq!(NonZero::<", stringify!($Int), ">::from_ascii(b\"7\"), Ok(NonZero::new(7).unwrap()));")]
+            #[doc = concat!("assert!(NonZero::<", stringify!($Int), ">::from_ascii(b\"0\").is_err());")]
+            /// ```
+            #[unstable(feature = "int_from_ascii", issue = "none")]
+            #[inline]
+            pub fn from_ascii(src: &[u8]) -> Result<Self, ParseIntError> {
+                Self::new(<$Int>::from_ascii(src)?)
+                    .ok_or(ParseIntError { kind: IntErrorKind::Zero })
+            }
+
+            /// Parses a non-zero integer from an ASCII-byte slice with digits in the given base.
+            ///
+            #[doc = concat!("See [`", stringify!($Int), "::from_ascii_radix`] for the accepted syntax.")]
+            /// Parsing zero returns an error of kind [`IntErrorKind::Zero`].
+            ///
+            /// # Panics
+            ///
+            /// This function panics if `radix` is not in the range from 2 to 36.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// #![feature(int_from_ascii)]
+            /// # use std::num::NonZero;
+            ///
+            #[doc = concat!("assert_eq!(NonZero::<", stringify!($Int), ">::from_ascii_radix(b\"A\", 16), Ok(NonZero::new(10).unwrap()));")]
+            /// ```
+            #[unstable(feature = "int_from_ascii", issue = "none")]
+            #[inline]
+            pub fn from_ascii_radix(src: &[u8], radix: u32) -> Result<Self, ParseIntError> {
+                Self::new(<$Int>::from_ascii_radix(src, radix)?)
+                    .ok_or(ParseIntError { kind: IntErrorKind::Zero })
+            }
+
+            /// Parses a non-zero integer from an ASCII-byte slice, using the syntax of Rust
+            /// integer literals.
+            ///
+            #[doc = concat!("See [`", stringify!($Int), "::from_ascii_prefixed`] for the accepted syntax.")]
+            /// Parsing zero returns an error of kind [`IntErrorKind::Zero`].
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// #![feature(int_from_ascii)]
+            /// # use std::num::NonZero;
+            ///
+            #[doc = concat!("assert_eq!(NonZero::<", stringify!($Int), ">::from_ascii_prefixed(b\"0b1_0\"), Ok(NonZero::new(2).unwrap()));")]
+            #[doc = concat!("assert!(NonZero::<", stringify!($Int), ">::from_ascii_prefixed(b\"0x0\").is_err());")]
+            /// ```
+            #[unstable(feature = "int_from_ascii", issue = "none")]
+            #[inline]
+            pub fn from_ascii_prefixed(src: &[u8]) -> Result<Self, ParseIntError> {
+                Self::new(<$Int>::from_ascii_prefixed(src)?)
+                    .ok_or(ParseIntError { kind: IntErrorKind::Zero })
+            }
+        }
+
         nonzero_integer_signedness_dependent_impls!($Ty $signedness $Int);
     };
 
@@ -0,0 +1,205 @@
+//! Double-width multiplication and division, backing the `carrying_mul_add` and `div_rem_wide`
+//! methods of the integer types.
+//!
+//! Types narrower than 128 bits compute in the integer type of twice their width, which LLVM
+//! lowers to a single widening multiplication or division. The 128-bit types have no wider
+//! type, so they are implemented here in terms of 64-bit halves; at runtime, `u128` and `i128`
+//! multiplication goes through `intrinsics::carrying_mul_add` instead, which backends can lower
+//! to a 256-bit multiplication.
+
+macro_rules! narrow_impls {
+    ($($SelfT:ty, $UnsignedT:ty, $WideT:ty: $carrying_mul_add:ident $(, $div_rem_wide:ident)?;)*) => {$(
+        /// Returns the low and high halves of `a * b + c + d`.
+        #[inline]
+        pub(crate) const fn $carrying_mul_add(
+            a: $SelfT,
+            b: $SelfT,
+            c: $SelfT,
+            d: $SelfT,
+        ) -> ($UnsignedT, $SelfT) {
+            // SAFETY: the result cannot overflow the wider type; see `intrinsics::carrying_mul_add`.
+            let wide = unsafe {
+                (a as $WideT)
+                    .unchecked_mul(b as $WideT)
+                    .unchecked_add(c as $WideT)
+                    .unchecked_add(d as $WideT)
+            };
+            (wide as $UnsignedT, (wide >> <$SelfT>::BITS) as $SelfT)
+        }
+
+        $(
+        /// Returns the quotient and remainder of `(high << BITS | low) / rhs`.
+        ///
+        /// `rhs` must be greater than `high`, so that the quotient fits in a single word.
+        #[inline]
+        pub(crate) const fn $div_rem_wide(low: $SelfT, high: $SelfT, rhs: $SelfT) -> ($SelfT, $SelfT) {
+            debug_assert!(high < rhs);
+            let lhs = ((high as $WideT) << <$SelfT>::BITS) | (low as $WideT);
+            let rhs = rhs as $WideT;
+            ((lhs / rhs) as $SelfT, (lhs % rhs) as $SelfT)
+        }
+        )?
+    )*};
+}
+
+narrow_impls! {
+    u8, u8, u16: carrying_mul_add_u8, div_rem_wide_u8;
+    u16, u16, u32: carrying_mul_add_u16, div_rem_wide_u16;
+    u32, u32, u64: carrying_mul_add_u32, div_rem_wide_u32;
+    u64, u64, u128: carrying_mul_add_u64, div_rem_wide_u64;
+    i8, u8, i16: carrying_mul_add_i8;
+    i16, u16, i32: carrying_mul_add_i16;
+    i32, u32, i64: carrying_mul_add_i32;
+    i64, u64, i128: carrying_mul_add_i64;
+}
+
+#[cfg(target_pointer_width = "16")]
+narrow_impls! {
+    usize, usize, u32: carrying_mul_add_usize, div_rem_wide_usize;
+    isize, usize, i32: carrying_mul_add_isize;
+}
+
+#[cfg(target_pointer_width = "32")]
+narrow_impls! {
+    usize, usize, u64: carrying_mul_add_usize, div_rem_wide_usize;
+    isize, usize, i64: carrying_mul_add_isize;
+}
+
+#[cfg(target_pointer_width = "64")]
+narrow_impls! {
+    usize, usize, u128: carrying_mul_add_usize, div_rem_wide_usize;
+    isize, usize, i128: carrying_mul_add_isize;
+}
+
+const LOW: u128 = u64::MAX as u128;
+
+/// Returns the low and high halves of `a * b`, by long multiplication of 64-bit halves.
+#[inline]
+const fn widening_mul_u128(a: u128, b: u128) -> (u128, u128) {
+    let (a0, a1) = (a & LOW, a >> 64);
+    let (b0, b1) = (b & LOW, b >> 64);
+
+    // None of the partial products or the sums below can overflow a `u128`.
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+
+    let mid = (p00 >> 64) + (p01 & LOW) + (p10 & LOW);
+    let low = (p00 & LOW) | (mid << 64);
+    let high = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    (low, high)
+}
+
+macro_rules! wide_impls {
+    ($($SelfT:ty, $UnsignedT:ty: $carrying_mul_add:ident, $portable:ident;)*) => {$(
+        /// Returns the low and high halves of `a * b + c + d`.
+        #[inline]
+        #[rustc_allow_const_fn_unstable(const_eval_select)]
+        pub(crate) const fn $carrying_mul_add(
+            a: $SelfT,
+            b: $SelfT,
+            c: $SelfT,
+            d: $SelfT,
+        ) -> ($UnsignedT, $SelfT) {
+            #[cfg(bootstrap)]
+            {
+                $portable(a, b, c, d)
+            }
+            #[cfg(not(bootstrap))]
+            {
+                #[inline]
+                fn rt(a: $SelfT, b: $SelfT, c: $SelfT, d: $SelfT) -> ($UnsignedT, $SelfT) {
+                    crate::intrinsics::carrying_mul_add(a, b, c, d)
+                }
+                crate::intrinsics::const_eval_select((a, b, c, d), $portable, rt)
+            }
+        }
+    )*};
+}
+
+wide_impls! {
+    u128, u128: carrying_mul_add_u128, portable_carrying_mul_add_u128;
+    i128, u128: carrying_mul_add_i128, portable_carrying_mul_add_i128;
+}
+
+/// Returns the low and high halves of `a * b + c + d`, without using the
+/// `carrying_mul_add` intrinsic.
+#[inline]
+pub(crate) const fn portable_carrying_mul_add_u128(
+    a: u128,
+    b: u128,
+    c: u128,
+    d: u128,
+) -> (u128, u128) {
+    let (low, high) = widening_mul_u128(a, b);
+    let (low, carry_c) = low.overflowing_add(c);
+    let (low, carry_d) = low.overflowing_add(d);
+    // The full result fits in 256 bits, so this cannot overflow.
+    (low, high + carry_c as u128 + carry_d as u128)
+}
+
+/// Returns the low and high halves of `a * b + c + d`, without using the
+/// `carrying_mul_add` intrinsic.
+#[inline]
+pub(crate) const fn portable_carrying_mul_add_i128(
+    a: i128,
+    b: i128,
+    c: i128,
+    d: i128,
+) -> (u128, i128) {
+    // Multiply as unsigned, then correct the high half for the two's complement weight of the
+    // sign bits: a negative `a` is `a as u128 - 2¹²⁸`, which subtracts `b << 128` from the
+    // product (and likewise for `b`).
+    let (low, high) = widening_mul_u128(a as u128, b as u128);
+    let mut high = high as i128;
+    high = high.wrapping_add((a >> 127).wrapping_mul(b));
+    high = high.wrapping_add((b >> 127).wrapping_mul(a));
+
+    // Sign-extend the addends into the high half.
+    let (low, carry_c) = low.overflowing_add(c as u128);
+    high = high.wrapping_add(carry_c as i128).wrapping_add(c >> 127);
+    let (low, carry_d) = low.overflowing_add(d as u128);
+    high = high.wrapping_add(carry_d as i128).wrapping_add(d >> 127);
+    (low, high)
+}
+
+/// Returns the quotient and remainder of `(high << 128 | low) / rhs`.
+///
+/// `rhs` must be greater than `high`, so that the quotient fits in a `u128`.
+///
+/// This is Knuth's Algorithm D, specialized to a two-digit quotient with 64-bit digits.
+#[inline]
+pub(crate) const fn div_rem_wide_u128(low: u128, high: u128, rhs: u128) -> (u128, u128) {
+    debug_assert!(high < rhs);
+    const B: u128 = 1 << 64;
+
+    // Normalize the divisor so that its most significant bit is set, which bounds the error of
+    // each estimated quotient digit to at most 2.
+    let shift = rhs.leading_zeros();
+    let v = rhs << shift;
+    let (v1, v0) = (v >> 64, v & LOW);
+
+    let un32 = if shift == 0 { high } else { (high << shift) | (low >> (128 - shift)) };
+    let un10 = low << shift;
+    let (un1, un0) = (un10 >> 64, un10 & LOW);
+
+    // Estimates a quotient digit of `(rem * B + u) / v`, given `rem < v`.
+    const fn digit(rem: u128, u: u128, v: u128, v1: u128, v0: u128) -> (u128, u128) {
+        let mut q = rem / v1;
+        let mut r = rem - q * v1;
+        while q >= B || q * v0 > (r << 64) + u {
+            q -= 1;
+            r += v1;
+            if r >= B {
+                break;
+            }
+        }
+        // The true remainder is less than `v`, so the wrapping arithmetic is exact.
+        (q, rem.wrapping_mul(B).wrapping_add(u).wrapping_sub(q.wrapping_mul(v)))
+    }
+
+    let (q1, rem) = digit(un32, un1, v, v1, v0);
+    let (q0, rem) = digit(rem, un0, v, v1, v0);
+    ((q1 << 64) | q0, rem >> shift)
+}
@@ -14,3 +14,91 @@ fn into_async_iter() {
     assert_eq!(async_iter.as_mut().poll_next(&mut cx), Poll::Ready(Some(2)));
     assert_eq!(async_iter.as_mut().poll_next(&mut cx), Poll::Ready(None));
 }
+
+/// An async iterator that is pending once before yielding each item.
+struct Yielding<I> {
+    iter: I,
+    ready: bool,
+}
+
+impl<I: Iterator + Unpin> AsyncIterator for Yielding<I> {
+    type Item = I::Item;
+
+    fn poll_next(
+        mut self: core::pin::Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> Poll<Option<I::Item>> {
+        let ready = self.ready;
+        self.ready = !ready;
+        if ready {
+            Poll::Ready(self.iter.next())
+        } else {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+fn poll_to_end<F: core::future::Future>(fut: F) -> (F::Output, usize) {
+    let mut fut = pin!(fut);
+    let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+    let mut pending = 0;
+    loop {
+        match fut.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return (output, pending),
+            Poll::Pending => pending += 1,
+        }
+    }
+}
+
+#[test]
+fn async_iter_next() {
+    let mut it = async_iter::from_iter(0..2);
+    assert_eq!(poll_to_end(it.next()), (Some(0), 0));
+    assert_eq!(poll_to_end(it.next()), (Some(1), 0));
+    assert_eq!(poll_to_end(it.next()), (None, 0));
+
+    let mut it = Yielding { iter: 0..1, ready: false };
+    assert_eq!(poll_to_end(it.next()), (Some(0), 1));
+    assert_eq!(poll_to_end(it.next()), (None, 1));
+}
+
+#[test]
+fn async_iter_map_filter_take() {
+    let it = async_iter::from_iter(0..).map(|x| x * 3).filter(|x| x % 2 == 0).take(4);
+    assert_eq!(it.size_hint(), (0, Some(4)));
+    assert_eq!(poll_to_end(it.collect::<Vec<_>>()), (vec![0, 6, 12, 18], 0));
+
+    let it = Yielding { iter: 0..10, ready: false }.filter(|x| x % 5 == 0).take(3);
+    // `take` stops after the inner async iterator is exhausted.
+    assert_eq!(poll_to_end(it.collect::<Vec<_>>()), (vec![0, 5], 11));
+
+    let it = async_iter::from_iter(0..10).take(0);
+    assert_eq!(it.size_hint(), (0, Some(0)));
+    assert_eq!(poll_to_end(it.collect::<Vec<_>>()), (vec![], 0));
+}
+
+#[test]
+fn async_iter_then() {
+    let it = async_iter::from_iter(1..=3).then(|x| async move { x * 10 });
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    assert_eq!(poll_to_end(it.collect::<Vec<_>>()), (vec![10, 20, 30], 0));
+
+    // Pending futures are polled again before the next element is pulled.
+    let it = async_iter::from_iter(1..=2).then(|x| pending_once(x));
+    assert_eq!(poll_to_end(it.collect::<Vec<_>>()), (vec![1, 2], 2));
+}
+
+async fn pending_once(x: i32) -> i32 {
+    let mut ready = false;
+    core::future::poll_fn(|cx| {
+        if ready {
+            Poll::Ready(x)
+        } else {
+            ready = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    })
+    .await
+}
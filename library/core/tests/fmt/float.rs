@@ -173,3 +173,29 @@ fn test_format_f32_rounds_ties_to_even() {
 fn is_exponential(s: &str) -> bool {
     s.contains("e") || s.contains("E")
 }
+
+#[test]
+fn test_format_float_into() {
+    use core::mem::MaybeUninit;
+
+    let values = [0.0, -0.0, 1.0, -1.5, 0.1, 1e-7, 1e21, f64::MAX, f64::MIN_POSITIVE, 5e-324];
+    let mut buf = [MaybeUninit::uninit(); f64::MAX_SHORTEST_LEN];
+    let mut exp_buf = [MaybeUninit::uninit(); f64::MAX_SHORTEST_EXP_LEN];
+    let mut fixed_buf = [0; 1024];
+    for x in values.into_iter().chain([f64::INFINITY, f64::NAN]) {
+        assert_eq!(x.format_shortest_into(&mut buf), format!("{x}"));
+        assert_eq!(x.format_shortest_exp_into(&mut exp_buf), format!("{x:e}"));
+        assert_eq!(x.format_fixed_into(3, &mut fixed_buf), Some(&*format!("{x:.3}")));
+        assert_eq!(x.format_exp_into(3, &mut fixed_buf), Some(&*format!("{x:.3e}")));
+
+        let y = x as f32;
+        let mut buf = [MaybeUninit::uninit(); f32::MAX_SHORTEST_LEN];
+        let mut exp_buf = [MaybeUninit::uninit(); f32::MAX_SHORTEST_EXP_LEN];
+        assert_eq!(y.format_shortest_into(&mut buf), format!("{y}"));
+        assert_eq!(y.format_shortest_exp_into(&mut exp_buf), format!("{y:e}"));
+    }
+
+    assert_eq!((-f64::MIN_POSITIVE).format_shortest_into(&mut buf).len(), f64::MAX_SHORTEST_LEN);
+    assert_eq!(1.25f64.format_fixed_into(1, &mut [0; 3]), Some("1.2"));
+    assert_eq!(1.25f64.format_fixed_into(2, &mut [0; 3]), None);
+}
@@ -43,3 +43,87 @@ fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
 
     assert_eq!(format!("{Bar:<03}"), "1  0051  ");
 }
+
+#[test]
+fn formatting_options_flags() {
+    use core::fmt::*;
+    for sign in [None, Some(Sign::Plus), Some(Sign::Minus)] {
+        for alternate in [true, false] {
+            for sign_aware_zero_pad in [true, false] {
+                for debug_as_hex in [None, Some(DebugAsHex::Lower), Some(DebugAsHex::Upper)] {
+                    let mut options = FormattingOptions::new();
+                    options
+                        .sign(sign)
+                        .sign_aware_zero_pad(sign_aware_zero_pad)
+                        .alternate(alternate)
+                        .debug_as_hex(debug_as_hex);
+
+                    assert_eq!(options.get_sign(), sign);
+                    assert_eq!(options.get_sign_aware_zero_pad(), sign_aware_zero_pad);
+                    assert_eq!(options.get_alternate(), alternate);
+                    assert_eq!(options.get_debug_as_hex(), debug_as_hex);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn formatting_options_create_formatter() {
+    use core::fmt::*;
+
+    let mut options = FormattingOptions::new();
+    assert_eq!(options, FormattingOptions::default());
+    options.width(Some(6)).precision(Some(2)).fill('*').align(Some(Alignment::Center));
+
+    let mut s = String::new();
+    let mut f = options.create_formatter(&mut s);
+    assert_eq!(f.width(), Some(6));
+    assert_eq!(f.precision(), Some(2));
+    assert_eq!(f.fill(), '*');
+    assert_eq!(f.align(), Some(Alignment::Center));
+    assert_eq!(f.options(), options);
+    Display::fmt(&1.5, &mut f).unwrap();
+    assert_eq!(s, "*1.50*");
+
+    let mut s = String::new();
+    let mut options = FormattingOptions::new();
+    options.sign(Some(Sign::Plus)).width(Some(5)).sign_aware_zero_pad(true);
+    Display::fmt(&7, &mut options.create_formatter(&mut s)).unwrap();
+    assert_eq!(s, "+0007");
+
+    let mut s = String::new();
+    let mut options = FormattingOptions::new();
+    options.debug_as_hex(Some(DebugAsHex::Upper));
+    Debug::fmt(&255, &mut options.create_formatter(&mut s)).unwrap();
+    assert_eq!(s, "FF");
+}
+
+#[test]
+fn formatter_with_options() {
+    use core::fmt::*;
+
+    struct Pair(u32, u32);
+
+    impl Display for Pair {
+        fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+            let mut options = f.options();
+            options.alternate(true).align(Some(Alignment::Left));
+            LowerHex::fmt(&self.0, &mut f.with_options(options))?;
+            f.write_str("/")?;
+            // The original formatter is unaffected.
+            LowerHex::fmt(&self.1, f)
+        }
+    }
+
+    assert_eq!(format!("{:>6}", Pair(10, 11)), "0xa   /     b");
+    assert_eq!(format!("{}", Pair(10, 11)), "0xa/b");
+}
+
+#[test]
+fn from_fn() {
+    let value = 7;
+    let wrapped = core::fmt::from_fn(|f| write!(f, "<{value}>"));
+    assert_eq!(format!("{wrapped}"), "<7>");
+    assert_eq!(format!("{wrapped:?}"), "<7>");
+}
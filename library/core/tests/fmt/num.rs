@@ -238,3 +238,30 @@ fn test_format_debug_hex() {
     assert_eq!(format!("{:02x?}", b"Foo\0"), "[46, 6f, 6f, 00]");
     assert_eq!(format!("{:02X?}", b"Foo\0"), "[46, 6F, 6F, 00]");
 }
+
+#[test]
+fn test_format_int_into() {
+    use core::mem::MaybeUninit;
+
+    macro_rules! check {
+        ($($t:ident)*) => {$(
+            let mut buf = [MaybeUninit::uninit(); $t::MAX_DECIMAL_LEN];
+            for n in [$t::MIN, $t::MAX, 0, 1, $t::MAX / 3] {
+                assert_eq!(n.format_into(&mut buf), n.to_string());
+                let mut buf = [0; $t::BITS as usize + 1];
+                for radix in [2, 8, 10, 16, 36] {
+                    let s = n.format_radix_into(radix, &mut buf).unwrap();
+                    assert_eq!($t::from_str_radix(s, radix), Ok(n));
+                }
+            }
+            assert_eq!($t::MAX_DECIMAL_LEN, $t::MIN.to_string().len().max($t::MAX.to_string().len()));
+        )*};
+    }
+    check! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize }
+
+    let mut buf = [0; 4];
+    assert_eq!((-255i32).format_radix_into(16, &mut buf), Some("-ff"));
+    assert_eq!(0xabcdu32.format_radix_into(16, &mut buf), Some("abcd"));
+    assert_eq!(0x10000u32.format_radix_into(16, &mut buf), None);
+    assert_eq!((-0x1000i32).format_radix_into(16, &mut buf), None);
+}
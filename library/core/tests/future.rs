@@ -1,5 +1,6 @@
-use std::future::{join, Future};
-use std::pin::Pin;
+use std::cell::Cell;
+use std::future::{self, join, race, select, Either, Future};
+use std::pin::{pin, Pin};
 use std::sync::Arc;
 use std::task::{Context, Poll, Wake};
 use std::thread;
@@ -64,6 +65,58 @@ fn test_join() {
     });
 }
 
+#[test]
+fn test_select() {
+    block_on(async move {
+        match select(poll_n(0, 3), poll_n(1, 2)).await {
+            Either::Right((1, left)) => assert_eq!(left.await, 0),
+            _ => panic!("the second future should complete first"),
+        }
+
+        // The first future wins ties.
+        match select(future::ready(0), future::ready(1)).await {
+            Either::Left((0, right)) => assert_eq!(right.await, 1),
+            _ => panic!("the first future should complete first"),
+        }
+
+        match select(future::pending::<()>(), poll_n(2, 4)).await {
+            Either::Right((2, _)) => {}
+            _ => panic!("the second future should complete first"),
+        }
+    });
+}
+
+#[test]
+fn test_race() {
+    block_on(async move {
+        assert_eq!(race(poll_n(0, 3), poll_n(1, 2)).await, 1);
+        assert_eq!(race(poll_n(0, 2), poll_n(1, 2)).await, 0);
+        assert_eq!(race(future::pending(), async { 2 }).await, 2);
+    });
+}
+
+#[test]
+fn test_race_drops_loser() {
+    struct SetOnDrop<'a>(&'a Cell<bool>);
+
+    impl Drop for SetOnDrop<'_> {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    let dropped = Cell::new(false);
+    let loser = async {
+        let _guard = SetOnDrop(&dropped);
+        future::pending::<usize>().await
+    };
+    let mut fut = pin!(race(loser, future::ready(1)));
+    let mut cx = Context::from_waker(std::task::Waker::noop());
+    assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(1));
+    // The loser is cancelled as soon as `fut` completes, not when it is dropped.
+    assert!(dropped.get());
+}
+
 /// Tests that `join!(…)` behaves "like a function": evaluating its arguments
 /// before applying any of its own logic.
 ///
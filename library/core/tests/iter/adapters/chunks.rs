@@ -0,0 +1,111 @@
+use super::*;
+use core::iter::*;
+
+#[test]
+fn test_chunks() {
+    let v: Vec<Vec<_>> = (1..=7).chunks(3).collect();
+    assert_eq!(v, [vec![1, 2, 3], vec![4, 5, 6], vec![7]]);
+
+    let v: Vec<Vec<_>> = (1..=6).chunks(3).collect();
+    assert_eq!(v, [vec![1, 2, 3], vec![4, 5, 6]]);
+
+    let v: Vec<Vec<_>> = (1..=2).chunks(5).collect();
+    assert_eq!(v, [vec![1, 2]]);
+
+    let v: Vec<String> = "abcde".chars().chunks(2).collect();
+    assert_eq!(v, ["ab", "cd", "e"]);
+
+    assert_eq!((0..0).chunks::<Vec<_>>(2).next(), None);
+}
+
+#[test]
+#[should_panic = "chunk size must be non-zero"]
+fn test_chunks_zero() {
+    let _ = (0..3).chunks::<Vec<_>>(0);
+}
+
+#[test]
+fn test_chunks_size_hint() {
+    let mut it = (0..7).chunks::<Vec<_>>(3);
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    assert_eq!(it.len(), 3);
+    it.next();
+    assert_eq!(it.len(), 2);
+    it.next();
+    it.next();
+    assert_eq!(it.len(), 0);
+
+    let it = (0..).filter(|x| x % 2 == 0).take(10).chunks::<Vec<_>>(4);
+    assert_eq!(it.size_hint(), (0, Some(3)));
+}
+
+#[test]
+fn test_chunks_rev() {
+    let v: Vec<Vec<_>> = (1..8).chunks(3).rev().collect();
+    assert_eq!(v, [vec![7], vec![4, 5, 6], vec![1, 2, 3]]);
+
+    let mut it = (1..9).chunks(3);
+    assert_eq!(it.next(), Some(vec![1, 2, 3]));
+    assert_eq!(it.next_back(), Some(vec![7, 8]));
+    assert_eq!(it.next_back(), Some(vec![4, 5, 6]));
+    assert_eq!(it.next_back(), None);
+    assert_eq!(it.next(), None);
+
+    let v: Vec<Box<[_]>> = (1..6).chunks(2).rev().collect();
+    assert_eq!(v, [Box::from([5]), Box::from([3, 4]), Box::from([1, 2])]);
+}
+
+#[test]
+fn test_chunk_by() {
+    let v: Vec<(_, Vec<_>)> = [1, 3, 2, 4, 6, 5].into_iter().chunk_by(|x| x % 2).collect();
+    assert_eq!(v, [(1, vec![1, 3]), (0, vec![2, 4, 6]), (1, vec![5])]);
+
+    let v: Vec<(i32, Vec<_>)> = (0..0).chunk_by(|&x| x).collect();
+    assert_eq!(v, []);
+
+    // The key function is called exactly once per element.
+    let mut calls = 0;
+    let v: Vec<_> = "aabccc"
+        .chars()
+        .chunk_by(|&c| {
+            calls += 1;
+            c
+        })
+        .map(|(c, run): (_, Vec<_>)| (c, run.len()))
+        .collect();
+    assert_eq!(v, [('a', 2), ('b', 1), ('c', 3)]);
+    assert_eq!(calls, 6);
+}
+
+#[test]
+fn test_chunk_by_size_hint() {
+    let mut it = [1, 1, 2].into_iter().chunk_by(|&x| x);
+    assert_eq!(it.size_hint(), (1, Some(3)));
+    assert_eq!(it.next(), Some((1, vec![1, 1])));
+    assert_eq!(it.size_hint(), (1, Some(1)));
+    assert_eq!(it.next(), Some((2, vec![2])));
+    assert_eq!(it.size_hint(), (0, Some(0)));
+}
+
+#[test]
+fn test_chunk_by_exhausted() {
+    // The last run ends when the iterator does, which isn't polled again for it.
+    let mut it = NonFused::new([1, 1, 2].into_iter()).chunk_by(|&x| x);
+    assert_eq!(it.next(), Some((1, vec![1, 1])));
+    assert_eq!(it.next(), Some((2, vec![2])));
+}
+
+#[test]
+fn test_chunk_by_partial_collect() {
+    // A collection that only takes the first element of each run.
+    struct First(i32);
+    impl FromIterator<i32> for First {
+        fn from_iter<I: IntoIterator<Item = i32>>(iter: I) -> Self {
+            First(iter.into_iter().next().unwrap())
+        }
+    }
+
+    let v: Vec<_> =
+        [1, 1, 1, 2, 2, 1].into_iter().chunk_by(|&x| x).map(|(k, First(x))| (k, x)).collect();
+    assert_eq!(v, [(1, 1), (2, 2), (1, 1)]);
+}
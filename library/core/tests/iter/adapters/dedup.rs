@@ -0,0 +1,50 @@
+use core::iter::*;
+
+#[test]
+fn test_dedup() {
+    let v: Vec<i32> = empty().dedup().collect();
+    assert_eq!(v, []);
+
+    let v: Vec<_> = [1, 1, 2, 3, 3, 3, 1, 4, 4].into_iter().dedup().collect();
+    assert_eq!(v, [1, 2, 3, 1, 4]);
+
+    let v: Vec<_> = [5; 10].into_iter().dedup().collect();
+    assert_eq!(v, [5]);
+}
+
+#[test]
+fn test_dedup_by() {
+    // Like `Vec::dedup_by`, the first argument is the element being considered
+    // and the second one is the last element that was kept.
+    let mut calls = Vec::new();
+    let v: Vec<_> = [1, 2, 4, 3, 7]
+        .into_iter()
+        .dedup_by(|&a, &b| {
+            calls.push((a, b));
+            a - b < 3
+        })
+        .collect();
+    assert_eq!(v, [1, 4, 7]);
+    assert_eq!(calls, [(2, 1), (4, 1), (3, 4), (7, 4)]);
+}
+
+#[test]
+fn test_dedup_by_key() {
+    let v: Vec<_> = [10, 11, 20, 25, 12, 13].into_iter().dedup_by_key(|x| x / 10).collect();
+    assert_eq!(v, [10, 20, 12]);
+}
+
+#[test]
+fn test_dedup_size_hint() {
+    let mut it = [1, 1, 2, 2].into_iter().dedup();
+    assert_eq!(it.size_hint(), (1, Some(4)));
+    assert_eq!(it.next(), Some(1));
+    // `2` has been stashed as the start of the next run.
+    assert_eq!(it.size_hint(), (1, Some(2)));
+    assert_eq!(it.next(), Some(2));
+    assert_eq!(it.size_hint(), (0, Some(0)));
+    assert_eq!(it.next(), None);
+
+    assert_eq!(empty::<i32>().dedup().size_hint(), (0, Some(0)));
+    assert_eq!(repeat(1).dedup_by_key(|_| ()).size_hint(), (1, None));
+}
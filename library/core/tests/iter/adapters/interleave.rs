@@ -0,0 +1,82 @@
+use super::*;
+use core::iter::*;
+
+#[test]
+fn test_interleave() {
+    let v: Vec<_> = [1, 3, 5].into_iter().interleave([2, 4, 6]).collect();
+    assert_eq!(v, [1, 2, 3, 4, 5, 6]);
+
+    let v: Vec<_> = [1, 3].into_iter().interleave([2, 4, 6, 8]).collect();
+    assert_eq!(v, [1, 2, 3, 4, 6, 8]);
+
+    let v: Vec<_> = [1, 3, 5, 7].into_iter().interleave([2]).collect();
+    assert_eq!(v, [1, 2, 3, 5, 7]);
+
+    let v: Vec<i32> = empty().interleave([1, 2]).collect();
+    assert_eq!(v, [1, 2]);
+}
+
+#[test]
+fn test_interleave_fold() {
+    let mut it = [1, 3, 5].into_iter().interleave([2, 4, 6, 8]);
+    assert_eq!(it.next(), Some(1));
+    // Folding resumes with `other`.
+    let v = it.fold(Vec::new(), |mut v, x| {
+        v.push(x);
+        v
+    });
+    assert_eq!(v, [2, 3, 4, 5, 6, 8]);
+}
+
+#[test]
+fn test_interleave_rev() {
+    let v: Vec<_> = [1, 3, 5].into_iter().interleave([2, 4, 6]).rev().collect();
+    assert_eq!(v, [6, 5, 4, 3, 2, 1]);
+
+    let v: Vec<_> = [1, 3].into_iter().interleave([2, 4, 6, 8]).rev().collect();
+    assert_eq!(v, [8, 6, 4, 3, 2, 1]);
+
+    let v: Vec<_> = [1, 3, 5, 7].into_iter().interleave([2]).rev().collect();
+    assert_eq!(v, [7, 5, 3, 2, 1]);
+
+    // Both ends meet in the middle, whichever iterator yields the next element.
+    let mut it = [1, 3, 5].into_iter().interleave([2, 4]);
+    assert_eq!(it.next(), Some(1));
+    assert_eq!(it.next_back(), Some(5));
+    assert_eq!(it.next_back(), Some(4));
+    assert_eq!(it.next(), Some(2));
+    assert_eq!(it.next_back(), Some(3));
+    assert_eq!(it.size_hint(), (0, Some(0)));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next_back(), None);
+}
+
+#[test]
+fn test_interleave_size_hint() {
+    let mut it = [1, 2, 3].into_iter().interleave([4, 5]);
+    assert_eq!(it.size_hint(), (5, Some(5)));
+    it.next();
+    assert_eq!(it.size_hint(), (4, Some(4)));
+
+    let it = repeat(1).interleave(repeat(2));
+    assert_eq!(it.size_hint(), (usize::MAX, None));
+
+    let it = (0..usize::MAX - 1).interleave(0..1);
+    assert_eq!(it.size_hint(), (usize::MAX, Some(usize::MAX)));
+
+    let it = (0..usize::MAX).interleave(0..1);
+    assert_eq!(it.size_hint(), (usize::MAX, None));
+
+    let it = (0..usize::MAX).interleave(0..usize::MAX);
+    assert_eq!(it.size_hint(), (usize::MAX, None));
+}
+
+#[test]
+fn test_interleave_fused() {
+    let mut it = NonFused::new([1].into_iter()).interleave(NonFused::new([2, 3].into_iter()));
+    assert_eq!(it.next(), Some(1));
+    assert_eq!(it.next(), Some(2));
+    assert_eq!(it.next(), Some(3));
+    assert_eq!(it.next(), None);
+    assert_eq!(it.next(), None);
+}
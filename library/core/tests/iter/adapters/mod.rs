@@ -1,9 +1,11 @@
 mod array_chunks;
 mod by_ref_sized;
 mod chain;
+mod chunks;
 mod cloned;
 mod copied;
 mod cycle;
+mod dedup;
 mod enumerate;
 mod filter;
 mod filter_map;
@@ -11,6 +13,7 @@
 mod flatten;
 mod fuse;
 mod inspect;
+mod interleave;
 mod intersperse;
 mod map;
 mod map_windows;
@@ -573,6 +573,79 @@ fn test_try_collect() {
     assert_eq!(v, Continue(vec![4, 5]));
 }
 
+#[test]
+fn test_next_chunk_drop() {
+    use core::cell::Cell;
+
+    struct Bomb<'a>(&'a Cell<usize>);
+    impl Drop for Bomb<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    // Items of a short iterator are moved into the returned iterator, and are
+    // dropped exactly once.
+    let drops = Cell::new(0);
+    let rest = [(); 3].map(|()| Bomb(&drops)).into_iter().next_chunk::<5>().err().unwrap();
+    assert_eq!(rest.len(), 3);
+    assert_eq!(drops.get(), 0);
+    drop(rest);
+    assert_eq!(drops.get(), 3);
+}
+
+#[test]
+fn test_collect_array() {
+    assert_eq!((0..3).collect_array(), Some([0, 1, 2]));
+    assert_eq!((0..2).collect_array::<3>(), None);
+    assert_eq!((0..4).collect_array::<3>(), None);
+    assert_eq!(core::iter::empty::<i32>().collect_array(), Some([]));
+    assert_eq!((0..1).collect_array::<0>(), None);
+
+    // The iterator is polled once past the array, to check that it's exhausted.
+    let mut polled = 0;
+    let it = core::iter::from_fn(|| {
+        polled += 1;
+        Some(polled)
+    });
+    assert_eq!(it.collect_array::<3>(), None);
+    assert_eq!(polled, 4);
+}
+
+#[test]
+fn test_try_collect_array() {
+    use core::ops::ControlFlow::{Break, Continue};
+
+    let u: [Result<i32, ()>; 3] = [Ok(1), Ok(2), Ok(3)];
+    assert_eq!(u.into_iter().try_collect_array(), Ok(Some([1, 2, 3])));
+    assert_eq!(u.into_iter().try_collect_array::<2>(), Ok(None));
+    assert_eq!(u.into_iter().try_collect_array::<4>(), Ok(None));
+
+    let u = [Some(1), None, Some(3), Some(4)];
+    let mut it = u.into_iter();
+    assert_eq!(it.try_collect_array::<2>(), None);
+    // The iterator continues after the failure.
+    assert_eq!(it.try_collect_array(), Some(Some([3, 4])));
+
+    // A failure in the element past the end of the array is still reported.
+    let u = [Continue(1), Continue(2), Break(3)];
+    assert_eq!(u.into_iter().try_collect_array::<2>(), Break(3));
+}
+
+#[test]
+fn test_try_collect_into() {
+    let mut v = Vec::with_capacity(4);
+    let u: [Result<i32, ()>; 2] = [Ok(1), Ok(2)];
+    assert_eq!(u.into_iter().try_collect_into(&mut v).map(|b| b.len()), Ok(2));
+
+    let u = [Some(3), None, Some(5)];
+    let mut it = u.into_iter();
+    assert!(it.try_collect_into(&mut v).is_none());
+    assert_eq!(v, [1, 2, 3]);
+    assert!(it.try_collect_into(&mut v).is_some());
+    assert_eq!(v, [1, 2, 3, 5]);
+}
+
 #[test]
 fn test_collect_into() {
     let a = vec![1, 2, 3, 4, 5];
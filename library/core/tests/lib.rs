@@ -4,6 +4,7 @@
 #![feature(array_windows)]
 #![feature(ascii_char)]
 #![feature(ascii_char_variants)]
+#![feature(async_iter_combinators)]
 #![feature(async_iter_from_iter)]
 #![feature(async_iterator)]
 #![feature(bigint_helper_methods)]
@@ -28,6 +29,7 @@
 #![feature(core_io_borrowed_buf)]
 #![feature(core_private_bignum)]
 #![feature(core_private_diy_float)]
+#![feature(debug_closure_helpers)]
 #![feature(dec2flt)]
 #![feature(duration_abs_diff)]
 #![feature(duration_consts_float)]
@@ -39,7 +41,10 @@
 #![feature(flt2dec)]
 #![feature(fmt_internals)]
 #![feature(float_minimum_maximum)]
+#![feature(float_parse_options)]
+#![feature(formatting_options)]
 #![feature(future_join)]
+#![feature(future_select)]
 #![feature(generic_assert_internals)]
 #![feature(array_try_from_fn)]
 #![feature(hasher_prefixfree_extras)]
@@ -60,8 +65,10 @@
 #![feature(min_specialization)]
 #![feature(noop_waker)]
 #![feature(numfmt)]
+#![feature(num_format_into)]
 #![feature(num_midpoint)]
 #![feature(offset_of_nested)]
+#![feature(int_from_ascii)]
 #![feature(isqrt)]
 #![feature(step_trait)]
 #![feature(str_internals)]
@@ -76,7 +83,11 @@
 #![feature(iter_advance_by)]
 #![feature(iter_array_chunks)]
 #![feature(iter_chain)]
+#![feature(iter_chunks)]
+#![feature(iter_collect_array)]
 #![feature(iter_collect_into)]
+#![feature(iter_dedup)]
+#![feature(iter_interleave)]
 #![feature(iter_partition_in_place)]
 #![feature(iter_intersperse)]
 #![feature(iter_is_partitioned)]
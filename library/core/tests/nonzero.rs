@@ -354,3 +354,16 @@ fn test_signed_nonzero_neg() {
     assert_eq!((-NonZero::<i128>::new(1).unwrap()).get(), -1);
     assert_eq!((-NonZero::<i128>::new(-1).unwrap()).get(), 1);
 }
+
+#[test]
+fn test_from_ascii() {
+    assert_eq!(NonZero::<u8>::from_ascii(b"12"), Ok(NonZero::new(12).unwrap()));
+    assert_eq!(NonZero::<i32>::from_ascii_radix(b"-z", 36), Ok(NonZero::new(-35).unwrap()));
+    assert_eq!(NonZero::<u64>::from_ascii_prefixed(b"0xff_ff"), Ok(NonZero::new(0xffff).unwrap()));
+    assert_eq!(NonZero::<u8>::from_ascii(b"0").unwrap_err().kind(), &IntErrorKind::Zero);
+    assert_eq!(
+        NonZero::<i8>::from_ascii_prefixed(b"-0b0").unwrap_err().kind(),
+        &IntErrorKind::Zero
+    );
+    assert_eq!(NonZero::<u8>::from_ascii(b"256").unwrap_err().kind(), &IntErrorKind::PosOverflow);
+}
@@ -1,5 +1,5 @@
 use core::num::bignum::tests::Big8x3 as Big;
-use core::num::bignum::Big32x40;
+use core::num::bignum::{Big32x40, FullOps};
 
 #[test]
 #[should_panic]
@@ -274,3 +274,93 @@ fn test_fmt() {
     assert_eq!(format!("{:?}", Big::from_u64(0x12345)), "0x1_23_45");
     assert_eq!(format!("{:?}", Big::from_u64(0x123456)), "0x12_34_56");
 }
+
+#[test]
+fn test_full_ops_match_carrying_mul_add() {
+    for a in 0..=u8::MAX {
+        for &b in &[0, 1, 2, 0x7f, 0x80, 0xfe, 0xff] {
+            for &c in &[0, 1, 0x80, 0xff] {
+                let (high, low) = a.full_mul_add(b, c, c ^ 0x5a);
+                assert_eq!(a.carrying_mul_add(b, c ^ 0x5a, c), (low, high));
+                if c < b {
+                    assert_eq!(a.full_div_rem(b, c), a.div_rem_wide(b, c));
+                }
+            }
+        }
+    }
+
+    let samples = [0, 1, 0x1234_5678, 0x8000_0000, 0xdead_beef, u32::MAX - 1, u32::MAX];
+    for &a in &samples {
+        for &b in &samples {
+            for &c in &samples {
+                let (high, low) = a.full_mul_add(b, c, !c);
+                assert_eq!(a.carrying_mul_add(b, !c, c), (low, high));
+                if c < b {
+                    assert_eq!(a.full_div_rem(b, c), a.div_rem_wide(b, c));
+                }
+            }
+        }
+    }
+}
+
+fn big_from_u128(low: u128, high: u128) -> Big32x40 {
+    let mut big = Big32x40::from_u64((high >> 64) as u64);
+    big.mul_pow2(64).add(&Big32x40::from_u64(high as u64));
+    big.mul_pow2(64).add(&Big32x40::from_u64((low >> 64) as u64));
+    big.mul_pow2(64).add(&Big32x40::from_u64(low as u64));
+    big
+}
+
+#[test]
+fn test_u128_carrying_mul_add() {
+    let samples = [
+        0,
+        1,
+        0xffff_ffff_ffff_ffff,
+        0x1_0000_0000_0000_0000,
+        0x0123_4567_89ab_cdef_fedc_ba98_7654_3210,
+        1 << 127,
+        u128::MAX - 1,
+        u128::MAX,
+    ];
+    for &a in &samples {
+        for &b in &samples {
+            for &c in &samples {
+                let (low, high) = a.carrying_mul_add(b, c, !c);
+                let b_digits = [b as u32, (b >> 32) as u32, (b >> 64) as u32, (b >> 96) as u32];
+                let mut expected = big_from_u128(a, 0);
+                expected.mul_digits(&b_digits);
+                expected.add(&big_from_u128(c, 0)).add(&big_from_u128(!c, 0));
+                assert_eq!(big_from_u128(low, high), expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_u128_div_rem_wide() {
+    let samples = [
+        1,
+        3,
+        0xffff_ffff_ffff_ffff,
+        0x1_0000_0000_0000_0001,
+        0x0123_4567_89ab_cdef_fedc_ba98_7654_3210,
+        1 << 127,
+        u128::MAX,
+    ];
+    for &rhs in &samples {
+        for &high in &[0, 1, rhs / 2, rhs - 1] {
+            for &low in &samples {
+                if high >= rhs {
+                    continue;
+                }
+                let (q, r) = low.div_rem_wide(rhs, high);
+                let mut big_q = Big32x40::from_small(0);
+                let mut big_r = Big32x40::from_small(0);
+                big_from_u128(low, high).div_rem(&big_from_u128(rhs, 0), &mut big_q, &mut big_r);
+                assert_eq!(big_from_u128(q, 0), big_q);
+                assert_eq!(big_from_u128(r, 0), big_r);
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
 #![allow(overflowing_literals)]
 
+use core::num::ParseFloatOptions;
+
 mod float;
 mod lemire;
 mod parse;
@@ -127,3 +129,88 @@ fn massive_exponent() {
     assert_eq!(format!("1e-{max}000").parse(), Ok(0.0));
     assert_eq!(format!("1e{max}000").parse(), Ok(f64::INFINITY));
 }
+
+#[test]
+fn from_ascii() {
+    assert_eq!(f64::from_ascii(b"1.5e3"), Ok(1500.0));
+    assert_eq!(f32::from_ascii(b"-inf"), Ok(f32::NEG_INFINITY));
+    assert!(f64::from_ascii(b"1.5\xff").is_err());
+    assert!(f64::from_ascii(b"").is_err());
+}
+
+#[test]
+fn parse_partial() {
+    let options = ParseFloatOptions::new();
+    assert_eq!(f64::parse_partial(b"1.5,2", options), Ok((1.5, 3)));
+    assert_eq!(f64::parse_partial(b"-.5e-1x", options), Ok((-0.05, 6)));
+    assert_eq!(f64::parse_partial(b"7e", options), Ok((7.0, 1)));
+    assert_eq!(f64::parse_partial(b"7e+", options), Ok((7.0, 1)));
+    assert_eq!(f64::parse_partial(b"3.", options), Ok((3.0, 2)));
+    assert_eq!(f64::parse_partial(b"0x1p3", options), Ok((0.0, 1)));
+    assert_eq!(f32::parse_partial(b"infinite", options), Ok((f32::INFINITY, 3)));
+    assert_eq!(f32::parse_partial(b"Infinity1", options), Ok((f32::INFINITY, 8)));
+    assert!(f32::parse_partial(b"nanx", options).unwrap().0.is_nan());
+    assert!(f64::parse_partial(b"", options).is_err());
+    assert!(f64::parse_partial(b"-", options).is_err());
+    assert!(f64::parse_partial(b".e1", options).is_err());
+
+    // The slow path only sees the consumed digits.
+    let long = format!("{}1 2", "1".repeat(800));
+    let (value, len) = f64::parse_partial(long.as_bytes(), options).unwrap();
+    assert_eq!((value, len), (long[..801].parse().unwrap(), 801));
+}
+
+#[test]
+fn decimal_point() {
+    let options = ParseFloatOptions::new().decimal_point(b',');
+    assert_eq!(f64::from_ascii_with(b"-12,25e1", options), Ok(-122.5));
+    assert_eq!(f64::parse_partial(b"1.5", options), Ok((1.0, 1)));
+
+    let input = format!("0.{}e-300", "7".repeat(100));
+    let expected: f64 = input.parse().unwrap();
+    assert_eq!(f64::from_ascii_with(input.replace('.', ",").as_bytes(), options), Ok(expected));
+    let input = format!("{}.{}", "9".repeat(30), "0".repeat(30));
+    let expected: f32 = input.parse().unwrap();
+    assert_eq!(f32::from_ascii_with(input.replace('.', ",").as_bytes(), options), Ok(expected));
+}
+
+#[test]
+#[should_panic]
+fn decimal_point_invalid() {
+    let _ = ParseFloatOptions::new().decimal_point(b'e');
+}
+
+#[test]
+fn hexadecimal() {
+    let options = ParseFloatOptions::new().hexadecimal(true);
+    let hex64 = |s: &str| f64::from_ascii_with(s.as_bytes(), options).map(f64::to_bits);
+    let hex32 = |s: &str| f32::from_ascii_with(s.as_bytes(), options).map(f32::to_bits);
+
+    assert_eq!(hex64("0x1.8p3"), Ok(12f64.to_bits()));
+    assert_eq!(hex64("-0XAp-1"), Ok((-5f64).to_bits()));
+    assert_eq!(hex64("0x.8"), Ok(0.5f64.to_bits()));
+    assert_eq!(hex64("0x10"), Ok(16f64.to_bits()));
+    assert_eq!(hex64("1.5"), Ok(1.5f64.to_bits()));
+    assert_eq!(hex64("0x1.fffffffffffffp1023"), Ok(f64::MAX.to_bits()));
+    assert_eq!(hex64("0x1.fffffffffffff8p1023"), Ok(f64::INFINITY.to_bits()));
+    assert_eq!(hex64("0x1p-1022"), Ok(f64::MIN_POSITIVE.to_bits()));
+    assert_eq!(hex64("0x0.fffffffffffff8p-1022"), Ok(f64::MIN_POSITIVE.to_bits()));
+    assert_eq!(hex64("0x1p-1074"), Ok(1));
+    assert_eq!(hex64("0x1.8p-1074"), Ok(2));
+    assert_eq!(hex64("0x1p-1075"), Ok(0));
+    assert_eq!(hex64("0x1.000001p-1075"), Ok(1));
+    assert_eq!(hex64("0x1p-2000"), Ok(0));
+    assert_eq!(hex64("0x1.00000000000008p0"), Ok(1f64.to_bits()));
+    assert_eq!(hex64("0x1.00000000000018p0"), Ok(1f64.to_bits() + 2));
+    assert_eq!(hex64("0x1.000000000000080000000000000001p0"), Ok(1f64.to_bits() + 1));
+    assert_eq!(hex64(&format!("0x1{}p-400", "0".repeat(100))), Ok(1f64.to_bits()));
+    assert_eq!(hex32("0x1.fffffep127"), Ok(f32::MAX.to_bits()));
+    assert_eq!(hex32("0x1p-149"), Ok(1));
+    assert_eq!(hex32("0x1.000001p0"), Ok(1f32.to_bits()));
+
+    assert_eq!(f64::parse_partial(b"0x1.8p", options), Ok((1.5, 5)));
+    assert_eq!(f64::parse_partial(b"0xg", options), Ok((0.0, 1)));
+    assert_eq!(f64::parse_partial(b"-0x1p1,", options), Ok((-2.0, 6)));
+    assert!(hex64("0x1.8p3").is_ok());
+    assert!(hex64("0x1.8q").is_err());
+}
@@ -397,6 +397,22 @@ fn test_borrowing_sub() {
                 assert_eq!((0 as $T).borrowing_sub($T::MIN, true), ($T::MAX, false));
             }
 
+            #[test]
+            fn test_carrying_mul_add() {
+                // The low half is unsigned; cast it back to compare against signed literals.
+                let signed = |(low, high): (_, $T)| (low as $T, high);
+                assert_eq!(signed((-1 as $T).widening_mul(1)), (-1, -1));
+                assert_eq!(signed((-1 as $T).widening_mul(-1)), (1, 0));
+                assert_eq!(signed(MIN.widening_mul(MIN)), (0, MAX / 2 + 1));
+                assert_eq!(signed(MIN.widening_mul(MAX)), (MIN, MIN / 2));
+                assert_eq!(signed(MAX.widening_mul(MAX)), (1, MAX / 2));
+                assert_eq!(signed((5 as $T).carrying_mul(-2, 10)), (0, 0));
+                assert_eq!(signed((-1 as $T).carrying_mul_add(-1, -1, -1)), (-1, -1));
+                assert_eq!(signed(MIN.carrying_mul_add(MIN, MIN, MIN)), (0, MAX / 2));
+                assert_eq!(signed(MAX.carrying_mul_add(MAX, MAX, MAX)), (-1, MAX / 2));
+                assert_eq!(signed(MIN.carrying_mul_add(MAX, MIN, MIN)), (MIN, MIN / 2 - 1));
+            }
+
             #[test]
             fn test_midpoint() {
                 assert_eq!(<$T>::midpoint(1, 3), 2);
@@ -207,6 +207,54 @@ fn test_empty() {
     test_parse::<u8>("", Err(IntErrorKind::Empty));
 }
 
+#[test]
+fn test_from_ascii() {
+    assert_eq!(u64::from_ascii(b"18446744073709551615"), Ok(u64::MAX));
+    assert_eq!(i8::from_ascii_radix(b"-80", 16), Ok(i8::MIN));
+    assert_eq!(usize::from_ascii(b"+42"), Ok(42));
+    assert_eq!(u8::from_ascii(b"\xff").unwrap_err().kind(), &IntErrorKind::InvalidDigit);
+    assert_eq!(u8::from_ascii(b"256").unwrap_err().kind(), &IntErrorKind::PosOverflow);
+    assert_eq!(i32::from_ascii(b"").unwrap_err().kind(), &IntErrorKind::Empty);
+}
+
+#[test]
+fn test_from_ascii_radix_partial() {
+    assert_eq!(u32::from_ascii_radix_partial(b"ff;", 16), Ok((255, &b";"[..])));
+    assert_eq!(i16::from_ascii_radix_partial(b"-12-3", 10), Ok((-12, &b"-3"[..])));
+    assert_eq!(u8::from_ascii_radix_partial(b"1012", 2), Ok((5, &b"2"[..])));
+    assert_eq!(isize::from_ascii_radix_partial(b"7", 10), Ok((7, &b""[..])));
+    assert_eq!(
+        u8::from_ascii_radix_partial(b"x", 10).unwrap_err().kind(),
+        &IntErrorKind::InvalidDigit
+    );
+    assert_eq!(
+        i8::from_ascii_radix_partial(b"-x", 10).unwrap_err().kind(),
+        &IntErrorKind::InvalidDigit
+    );
+    assert_eq!(
+        u8::from_ascii_radix_partial(b"300 ", 10).unwrap_err().kind(),
+        &IntErrorKind::PosOverflow
+    );
+}
+
+#[test]
+fn test_from_ascii_prefixed() {
+    assert_eq!(u32::from_ascii_prefixed(b"0xdead_beef"), Ok(0xdead_beef));
+    assert_eq!(i64::from_ascii_prefixed(b"-0o17"), Ok(-0o17));
+    assert_eq!(i8::from_ascii_prefixed(b"-0b1000_0000"), Ok(i8::MIN));
+    assert_eq!(u128::from_ascii_prefixed(b"1_000_000_"), Ok(1_000_000));
+    assert_eq!(u16::from_ascii_prefixed(b"0"), Ok(0));
+    assert_eq!(u16::from_ascii_prefixed(b"012"), Ok(12));
+    for invalid in [&b"0x"[..], b"_1", b"0x_1", b"0X1", b"0b2", b"1 ", b"-"] {
+        let err = u16::from_ascii_prefixed(invalid).unwrap_err();
+        assert_eq!(err.kind(), &IntErrorKind::InvalidDigit, "{invalid:?}");
+    }
+    assert_eq!(u8::from_ascii_prefixed(b"0x1_00").unwrap_err().kind(), &IntErrorKind::PosOverflow);
+    assert_eq!(u8::from_ascii_prefixed(b"").unwrap_err().kind(), &IntErrorKind::Empty);
+    // Separators are only accepted with `from_ascii_prefixed`.
+    assert!(u32::from_ascii(b"1_0").is_err());
+}
+
 #[test]
 fn test_infallible_try_from_int_error() {
     let func = |x: i8| -> Result<i32, TryFromIntError> { Ok(x.try_into()?) };
@@ -224,6 +272,18 @@ fn test_infallible_try_from_int_error() {
     }
 };
 
+const _TEST_CONST_FROM_ASCII: () = {
+    let Ok(-0x80) = i8::from_ascii_radix(b"-80", 16) else { panic!() };
+    let Ok(12345) = u64::from_ascii(b"12345") else { panic!() };
+    let Ok((255, [b';'])) = u32::from_ascii_radix_partial(b"ff;", 16) else { panic!() };
+    let Ok(0x7f) = u8::from_ascii_prefixed(b"0x7f") else { panic!() };
+    if let Err(e) = usize::from_ascii_prefixed(b"0x") {
+        let IntErrorKind::InvalidDigit = e.kind() else { panic!() };
+    } else {
+        panic!()
+    }
+};
+
 macro_rules! test_impl_from {
     ($fn_name:ident, bool, $target: ty) => {
         #[test]
@@ -282,6 +282,45 @@ fn test_borrowing_sub() {
                 assert_eq!($T::MAX.borrowing_sub($T::MAX, true), ($T::MAX, true));
             }
 
+            #[test]
+            fn test_carrying_mul_add() {
+                assert_eq!($T::MAX.widening_mul($T::MAX), (1, $T::MAX - 1));
+                assert_eq!($T::MAX.carrying_mul($T::MAX, $T::MAX), (0, $T::MAX));
+                assert_eq!($T::MAX.carrying_mul_add($T::MAX, $T::MAX, $T::MAX), ($T::MAX, $T::MAX));
+                assert_eq!($T::MAX.carrying_mul_add(0, $T::MAX, $T::MAX), ($T::MAX - 1, 1));
+                assert_eq!((3 as $T).carrying_mul_add(5, 7, 11), (33, 0));
+                assert_eq!(((1 as $T) << ($T::BITS - 1)).widening_mul(4), (0, 2));
+            }
+
+            #[test]
+            fn test_div_rem_wide() {
+                assert_eq!((33 as $T).div_rem_wide(0, 5), (6, 3));
+                assert_eq!((0 as $T).div_rem_wide(2, 4), (1 << ($T::BITS - 1), 0));
+                assert_eq!($T::MAX.div_rem_wide($T::MAX - 1, $T::MAX), ($T::MAX, $T::MAX - 1));
+
+                // `div_rem_wide` is the inverse of `carrying_mul`.
+                for &a in &[0, 1, 2, 0x5a, $T::MAX / 3, $T::MAX - 1, $T::MAX] {
+                    for &b in &[1, 3, 0x7f, $T::MAX / 5 + 1, $T::MAX] {
+                        for &c in &[0, b / 2, b - 1] {
+                            let (low, high) = a.carrying_mul(b, c);
+                            assert_eq!(low.div_rem_wide(high, b), (a, c));
+                        }
+                    }
+                }
+            }
+
+            #[test]
+            #[should_panic]
+            fn test_div_rem_wide_by_zero() {
+                let _ = (1 as $T).div_rem_wide(0, 0);
+            }
+
+            #[test]
+            #[should_panic]
+            fn test_div_rem_wide_overflow() {
+                let _ = (1 as $T).div_rem_wide(3, 3);
+            }
+
             #[test]
             fn test_midpoint() {
                 assert_eq!(<$T>::midpoint(1, 3), 2);
@@ -8,6 +8,7 @@
 mod lane_count;
 mod masks;
 mod ops;
+mod scan;
 mod select;
 mod swizzle_dyn;
 mod to_bytes;
@@ -0,0 +1,99 @@
+use crate::simd::{LaneCount, Mask, Simd, SimdElement, SupportedLaneCount};
+use core::ops::Add;
+
+impl<T, const N: usize> Simd<T, N>
+where
+    T: SimdElement,
+    LaneCount<N>: SupportedLaneCount,
+    Self: Add<Output = Self>,
+{
+    /// Returns the inclusive prefix sum of the vector: each element becomes the sum of itself
+    /// and all elements before it.
+    ///
+    /// Integer elements use wrapping addition, like the `+` operator on vectors.
+    ///
+    /// The sum is computed in `log2(N)` vector additions, by adding the vector to itself shifted
+    /// by 1, 2, 4, ... elements. For floating-point elements, the additions are therefore not
+    /// performed in sequential order, and the result may be rounded differently than a scalar
+    /// running sum.
+    ///
+    /// # Examples
+    /// ```
+    /// # #![feature(portable_simd)]
+    /// # #[cfg(feature = "as_crate")] use core_simd::simd;
+    /// # #[cfg(not(feature = "as_crate"))] use core::simd;
+    /// # use simd::Simd;
+    /// let x = Simd::from_array([1, 2, 3, 4, 5, 6, 7, 8]);
+    /// assert_eq!(x.prefix_sum(), Simd::from_array([1, 3, 6, 10, 15, 21, 28, 36]));
+    /// ```
+    #[inline]
+    #[must_use = "method returns a new vector and does not mutate the original value"]
+    pub fn prefix_sum(self) -> Self {
+        let mut sum = self;
+        macro_rules! step {
+            { $($offset:literal),* } => {
+                $(
+                if N > $offset {
+                    let shifted = sum.rotate_elements_right::<$offset>();
+                    sum = lanes_from::<T::Mask, N>($offset).select(sum + shifted, sum);
+                }
+                )*
+            }
+        }
+        step! { 1, 2, 4, 8, 16, 32 }
+        sum
+    }
+
+    /// Returns the segmented inclusive prefix sum of the vector.
+    ///
+    /// The vector is split into segments, each of which starts at an element that is enabled in
+    /// `segment_start` (the first element always starts a segment). Each element becomes the sum
+    /// of itself and all elements before it in the same segment.
+    ///
+    /// Rounding behaves as for [`Simd::prefix_sum`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #![feature(portable_simd)]
+    /// # #[cfg(feature = "as_crate")] use core_simd::simd;
+    /// # #[cfg(not(feature = "as_crate"))] use core::simd;
+    /// # use simd::{Simd, Mask};
+    /// let x = Simd::from_array([1, 2, 3, 4, 5, 6, 7, 8]);
+    /// let segment_start = Mask::from_array([false, false, true, false, false, false, true, true]);
+    /// assert_eq!(
+    ///     x.segmented_prefix_sum(segment_start),
+    ///     Simd::from_array([1, 3, 3, 7, 12, 18, 7, 8]),
+    /// );
+    /// ```
+    #[inline]
+    #[must_use = "method returns a new vector and does not mutate the original value"]
+    pub fn segmented_prefix_sum(self, segment_start: Mask<<T as SimdElement>::Mask, N>) -> Self {
+        let mut sum = self;
+        // Whether an element's partial sum already reaches back to the start of its segment.
+        let mut complete = segment_start;
+        macro_rules! step {
+            { $($offset:literal),* } => {
+                $(
+                if N > $offset {
+                    let shifted = sum.rotate_elements_right::<$offset>();
+                    let add = lanes_from::<T::Mask, N>($offset) & !complete;
+                    sum = add.select(sum + shifted, sum);
+                    complete |= Mask::from_bitmask(complete.to_bitmask() << $offset);
+                }
+                )*
+            }
+        }
+        step! { 1, 2, 4, 8, 16, 32 }
+        sum
+    }
+}
+
+/// Returns a mask enabling the elements at index `offset` and above.
+#[inline]
+fn lanes_from<M, const N: usize>(offset: usize) -> Mask<M, N>
+where
+    M: crate::simd::MaskElement,
+    LaneCount<N>: SupportedLaneCount,
+{
+    Mask::from_bitmask(u64::MAX << offset)
+}
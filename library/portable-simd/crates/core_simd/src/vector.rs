@@ -860,6 +860,87 @@ pub unsafe fn scatter_select_ptr(self, dest: Simd<*mut T, N>, enable: Mask<isize
         // Safety: The caller is responsible for upholding all invariants
         unsafe { core::intrinsics::simd::simd_scatter(self, dest, enable.to_int()) }
     }
+
+    /// Writes the enabled elements of the vector contiguously to the start of `slice`,
+    /// preserving their order, and returns the number of elements written.
+    ///
+    /// Writing stops early if `slice` is too short to hold every enabled element.
+    /// Elements of `slice` past the returned length are not accessed.
+    ///
+    /// This is the operation performed by the AVX-512 `vpcompress` family of instructions.
+    /// Note that the current implementation is a scalar loop on all targets.
+    ///
+    /// # Examples
+    /// ```
+    /// # #![feature(portable_simd)]
+    /// # #[cfg(feature = "as_crate")] use core_simd::simd;
+    /// # #[cfg(not(feature = "as_crate"))] use core::simd;
+    /// # use simd::{Simd, Mask};
+    /// let mut arr = [0i32; 4];
+    /// let values = Simd::from_array([-5, -4, -3, -2]);
+    /// let enable = Mask::from_array([true, false, true, true]);
+    ///
+    /// assert_eq!(values.compress_store(&mut arr, enable), 3);
+    /// assert_eq!(arr, [-5, -3, -2, 0]);
+    ///
+    /// assert_eq!(values.compress_store(&mut arr[..2], enable), 2);
+    /// assert_eq!(arr, [-5, -3, -2, 0]);
+    /// ```
+    #[inline]
+    pub fn compress_store(
+        self,
+        slice: &mut [T],
+        enable: Mask<<T as SimdElement>::Mask, N>,
+    ) -> usize {
+        let values = self.as_array();
+        let mut bitmask = enable.to_bitmask();
+        let mut written = 0;
+        while bitmask != 0 && written < slice.len() {
+            slice[written] = values[bitmask.trailing_zeros() as usize];
+            written += 1;
+            bitmask &= bitmask - 1;
+        }
+        written
+    }
+
+    /// Reads contiguous elements from the start of `slice` into the enabled elements of a
+    /// vector, preserving their order.
+    ///
+    /// The first element of `slice` is placed in the first enabled element, the second one in
+    /// the second enabled element, and so on. When an element is disabled, or `slice` runs out
+    /// before it is reached, the corresponding value from `or` is passed through.
+    ///
+    /// This is the operation performed by the AVX-512 `vpexpand` family of instructions.
+    /// Note that the current implementation is a scalar loop on all targets.
+    ///
+    /// # Examples
+    /// ```
+    /// # #![feature(portable_simd)]
+    /// # #[cfg(feature = "as_crate")] use core_simd::simd;
+    /// # #[cfg(not(feature = "as_crate"))] use core::simd;
+    /// # use simd::{Simd, Mask};
+    /// let vec: Vec<i32> = vec![10, 11];
+    /// let enable = Mask::from_array([false, true, true, true]);
+    /// let or = Simd::from_array([-5, -4, -3, -2]);
+    ///
+    /// let result = Simd::expand_load(&vec, enable, or);
+    /// assert_eq!(result, Simd::from_array([-5, 10, 11, -2]));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn expand_load(slice: &[T], enable: Mask<<T as SimdElement>::Mask, N>, or: Self) -> Self {
+        let mut result = or;
+        let values = result.as_mut_array();
+        let mut bitmask = enable.to_bitmask();
+        for &value in slice {
+            if bitmask == 0 {
+                break;
+            }
+            values[bitmask.trailing_zeros() as usize] = value;
+            bitmask &= bitmask - 1;
+        }
+        result
+    }
 }
 
 impl<T, const N: usize> Copy for Simd<T, N>
@@ -0,0 +1,119 @@
+#![feature(portable_simd)]
+use core_simd::simd::prelude::*;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+#[cfg(target_arch = "wasm32")]
+wasm_bindgen_test_configure!(run_in_browser);
+
+macro_rules! prefix_sum_tests {
+    { $($scalar:ident),* } => {
+        $(
+        mod $scalar {
+            use super::*;
+
+            test_helpers::test_lanes! {
+                fn prefix_sum<const LANES: usize>() {
+                    test_helpers::test_1(&|x: [$scalar; LANES]| {
+                        let mut expected = x;
+                        for i in 1..LANES {
+                            expected[i] = expected[i - 1].wrapping_add(x[i]);
+                        }
+                        test_helpers::prop_assert_biteq!(
+                            Simd::from_array(x).prefix_sum().to_array(),
+                            expected,
+                        );
+                        Ok(())
+                    });
+                }
+
+                fn segmented_prefix_sum<const LANES: usize>() {
+                    test_helpers::test_2(&|x: [$scalar; LANES], starts: u64| {
+                        let mut expected = x;
+                        for i in 1..LANES {
+                            if starts & (1 << i) == 0 {
+                                expected[i] = expected[i - 1].wrapping_add(x[i]);
+                            }
+                        }
+                        test_helpers::prop_assert_biteq!(
+                            Simd::from_array(x).segmented_prefix_sum(Mask::from_bitmask(starts)).to_array(),
+                            expected,
+                        );
+                        Ok(())
+                    });
+                }
+            }
+        }
+        )*
+    }
+}
+
+prefix_sum_tests! { i8, u16, i32, u64 }
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn float_prefix_sum() {
+    let x = f32x8::from_array([0.5, 1.0, -2.0, 4.0, 0.25, -0.5, 8.0, 1.0]);
+    assert_eq!(
+        x.prefix_sum(),
+        f32x8::from_array([0.5, 1.5, -0.5, 3.5, 3.75, 3.25, 11.25, 12.25])
+    );
+    let segment_start = Mask::from_array([false, true, false, false, true, false, false, false]);
+    assert_eq!(
+        x.segmented_prefix_sum(segment_start),
+        f32x8::from_array([0.5, 1.0, -1.0, 3.0, 0.25, -0.25, 7.75, 8.75])
+    );
+}
+
+test_helpers::test_lanes! {
+    fn compress_store<const LANES: usize>() {
+        test_helpers::test_2(&|x: [i32; LANES], enabled: u64| {
+            let enable = Mask::from_bitmask(enabled);
+            let expected: Vec<i32> = (0..LANES).filter(|&i| enabled & (1 << i) != 0).map(|i| x[i]).collect();
+
+            let mut out = [0; LANES];
+            let written = Simd::from_array(x).compress_store(&mut out, enable);
+            proptest::prop_assert_eq!(&out[..written], &expected[..]);
+            proptest::prop_assert!(out[written..].iter().all(|&v| v == 0));
+
+            // A short slice receives as many elements as fit.
+            let mut short = [0; 1];
+            let written = Simd::from_array(x).compress_store(&mut short, enable);
+            proptest::prop_assert_eq!(&short[..written], &expected[..expected.len().min(1)]);
+            Ok(())
+        });
+    }
+
+    fn expand_load<const LANES: usize>() {
+        test_helpers::test_3(&|x: [i32; LANES], or: [i32; LANES], enabled: u64| {
+            let enable = Mask::from_bitmask(enabled);
+            let len = LANES / 2;
+            let mut expected = or;
+            let mut next = 0;
+            for i in 0..LANES {
+                if enabled & (1 << i) != 0 && next < len {
+                    expected[i] = x[next];
+                    next += 1;
+                }
+            }
+            let result = Simd::expand_load(&x[..len], enable, Simd::from_array(or));
+            test_helpers::prop_assert_biteq!(result.to_array(), expected);
+            Ok(())
+        });
+    }
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn compress_expand_roundtrip() {
+    let x = u8x8::from_array([1, 2, 3, 4, 5, 6, 7, 8]);
+    let enable = Mask::from_array([false, true, true, false, true, false, false, true]);
+
+    let mut packed = [0; 8];
+    let written = x.compress_store(&mut packed, enable);
+    assert_eq!(&packed[..written], &[2, 3, 5, 8]);
+
+    let unpacked = u8x8::expand_load(&packed[..written], enable, u8x8::splat(0));
+    assert_eq!(unpacked, enable.select(x, u8x8::splat(0)));
+}
@@ -0,0 +1,140 @@
+#[cfg(all(feature = "as_crate", any(target_arch = "x86", target_arch = "x86_64")))]
+use std::is_x86_feature_detected;
+
+#[cfg(all(
+    not(feature = "as_crate"),
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+use crate::is_x86_feature_detected;
+
+use crate::simd::{LaneCount, SimdElement, SupportedLaneCount};
+
+/// A computation that is generic over the number of SIMD lanes, to be run by [`dispatch_lanes`].
+///
+/// Closures cannot be generic over a const parameter, so the computation is instead expressed
+/// as a type implementing this trait, usually a struct holding the computation's inputs.
+///
+/// [`run`](Self::run) should be marked `#[inline]`: it is only compiled with the detected CPU
+/// features enabled if it is inlined into [`dispatch_lanes`].
+pub trait LaneCountDispatch {
+    /// The result of the computation.
+    type Output;
+
+    /// Runs the computation using vectors of `N` elements.
+    fn run<const N: usize>(self) -> Self::Output
+    where
+        LaneCount<N>: SupportedLaneCount;
+}
+
+/// Returns the width in bytes of the widest SIMD registers supported by the current CPU.
+///
+/// On x86 and x86-64, this is 64 when AVX-512F is detected at runtime and 32 when AVX2 is.
+/// Otherwise it is 16, the width of the baseline vector extensions of most targets; on targets
+/// without any, vectors of that width are lowered to scalar operations.
+#[inline]
+pub fn vector_width_bytes() -> usize {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("avx512f") {
+            return 64;
+        }
+        if is_x86_feature_detected!("avx2") {
+            return 32;
+        }
+    }
+    16
+}
+
+/// Returns the number of elements of type `T` that fit in the widest SIMD registers supported by
+/// the current CPU, as used by [`dispatch_lanes`].
+///
+/// The result is a supported lane count: a power of two between 1 and 64.
+#[inline]
+pub fn preferred_lanes<T: SimdElement>() -> usize {
+    lanes_for::<T>(vector_width_bytes())
+}
+
+/// Runs `f` with the number of lanes best suited to the current CPU, chosen at runtime.
+///
+/// This selects `N` as [`preferred_lanes::<T>()`](preferred_lanes) and calls `f.run::<N>()`.
+/// On x86 and x86-64, the call is made from a function compiled with the detected AVX-512F or
+/// AVX2 target features enabled, so that an inlined `run` can use the wider registers even if
+/// the rest of the program is compiled for a baseline CPU. Elsewhere, `run` is called directly.
+///
+/// # Examples
+/// ```
+/// # #![feature(portable_simd)]
+/// # #[cfg(feature = "as_crate")] use core_simd::simd;
+/// # #[cfg(not(feature = "as_crate"))] use std::simd;
+/// # #[cfg(feature = "as_crate")] use std_float::{dispatch_lanes, LaneCountDispatch};
+/// # #[cfg(not(feature = "as_crate"))] use std::simd::{dispatch_lanes, LaneCountDispatch};
+/// use simd::prelude::*;
+/// use simd::{LaneCount, SupportedLaneCount};
+///
+/// struct Sum<'a>(&'a [f32]);
+///
+/// impl LaneCountDispatch for Sum<'_> {
+///     type Output = f32;
+///
+///     #[inline]
+///     fn run<const N: usize>(self) -> f32
+///     where
+///         LaneCount<N>: SupportedLaneCount,
+///     {
+///         let (prefix, middle, suffix) = self.0.as_simd::<N>();
+///         let sums = middle.iter().fold(Simd::splat(0.0), |acc, &x| acc + x);
+///         sums.reduce_sum() + prefix.iter().chain(suffix).sum::<f32>()
+///     }
+/// }
+///
+/// let values: Vec<f32> = (1..=100).map(|x| x as f32).collect();
+/// assert_eq!(dispatch_lanes::<f32, _>(Sum(&values)), 5050.0);
+/// ```
+#[inline]
+pub fn dispatch_lanes<T: SimdElement, F: LaneCountDispatch>(f: F) -> F::Output {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("avx512f") {
+            // SAFETY: AVX-512F is supported by the current CPU.
+            return unsafe { x86::run_avx512f::<T, F>(f) };
+        }
+        if is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 is supported by the current CPU.
+            return unsafe { x86::run_avx2::<T, F>(f) };
+        }
+    }
+    run_with_width::<T, F>(f, 16)
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86 {
+    use super::{run_with_width, LaneCountDispatch};
+    use crate::simd::SimdElement;
+
+    #[target_feature(enable = "avx512f")]
+    pub(super) unsafe fn run_avx512f<T: SimdElement, F: LaneCountDispatch>(f: F) -> F::Output {
+        run_with_width::<T, F>(f, 64)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn run_avx2<T: SimdElement, F: LaneCountDispatch>(f: F) -> F::Output {
+        run_with_width::<T, F>(f, 32)
+    }
+}
+
+fn lanes_for<T>(width_bytes: usize) -> usize {
+    (width_bytes / core::mem::size_of::<T>()).clamp(1, 64)
+}
+
+#[inline(always)]
+fn run_with_width<T, F: LaneCountDispatch>(f: F, width_bytes: usize) -> F::Output {
+    match lanes_for::<T>(width_bytes) {
+        1 => f.run::<1>(),
+        2 => f.run::<2>(),
+        4 => f.run::<4>(),
+        8 => f.run::<8>(),
+        16 => f.run::<16>(),
+        32 => f.run::<32>(),
+        _ => f.run::<64>(),
+    }
+}
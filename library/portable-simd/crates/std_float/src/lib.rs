@@ -1,5 +1,6 @@
 #![cfg_attr(
     feature = "as_crate",
+    feature(avx512_target_feature),
     feature(core_intrinsics),
     feature(portable_simd),
     allow(internal_features)
@@ -23,6 +24,10 @@ pub trait Sealed {}
 
 use crate::sealed::Sealed;
 
+mod dispatch;
+
+pub use dispatch::{dispatch_lanes, preferred_lanes, vector_width_bytes, LaneCountDispatch};
+
 /// This trait provides a possibly-temporary implementation of float functions
 /// that may, in the absence of hardware support, canonicalize to calling an
 /// operating system's `math.h` dynamically-loaded library (also known as a
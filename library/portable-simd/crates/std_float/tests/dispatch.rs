@@ -0,0 +1,67 @@
+#![feature(portable_simd)]
+
+use core_simd::simd::prelude::*;
+use core_simd::simd::{LaneCount, SupportedLaneCount};
+use std_float::{dispatch_lanes, preferred_lanes, vector_width_bytes, LaneCountDispatch};
+
+struct Lanes;
+
+impl LaneCountDispatch for Lanes {
+    type Output = usize;
+
+    #[inline]
+    fn run<const N: usize>(self) -> usize
+    where
+        LaneCount<N>: SupportedLaneCount,
+    {
+        N
+    }
+}
+
+struct DotProduct<'a>(&'a [f64], &'a [f64]);
+
+impl LaneCountDispatch for DotProduct<'_> {
+    type Output = f64;
+
+    #[inline]
+    fn run<const N: usize>(self) -> f64
+    where
+        LaneCount<N>: SupportedLaneCount,
+    {
+        let mut sums = Simd::<f64, N>::splat(0.0);
+        let mut a = self.0.chunks_exact(N);
+        let mut b = self.1.chunks_exact(N);
+        for (a, b) in (&mut a).zip(&mut b) {
+            sums += Simd::from_slice(a) * Simd::from_slice(b);
+        }
+        let tail: f64 = a
+            .remainder()
+            .iter()
+            .zip(b.remainder())
+            .map(|(a, b)| a * b)
+            .sum();
+        sums.reduce_sum() + tail
+    }
+}
+
+#[test]
+fn dispatch_uses_preferred_lanes() {
+    let width = vector_width_bytes();
+    assert!([16, 32, 64].contains(&width));
+
+    assert_eq!(preferred_lanes::<u8>(), width);
+    assert_eq!(preferred_lanes::<f32>(), width / 4);
+    assert_eq!(preferred_lanes::<f64>(), width / 8);
+
+    assert_eq!(dispatch_lanes::<u8, _>(Lanes), preferred_lanes::<u8>());
+    assert_eq!(dispatch_lanes::<u32, _>(Lanes), preferred_lanes::<u32>());
+    assert_eq!(dispatch_lanes::<f64, _>(Lanes), preferred_lanes::<f64>());
+}
+
+#[test]
+fn dispatch_matches_scalar() {
+    let a: Vec<f64> = (0..37).map(|x| x as f64).collect();
+    let b: Vec<f64> = (0..37).map(|x| (x % 5) as f64).collect();
+    let expected: f64 = a.iter().zip(&b).map(|(a, b)| a * b).sum();
+    assert_eq!(dispatch_lanes::<f64, _>(DotProduct(&a, &b)), expected);
+}
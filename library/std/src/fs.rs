@@ -10,6 +10,10 @@
 
 #[cfg(all(test, not(any(target_os = "emscripten", target_env = "sgx", target_os = "xous"))))]
 mod tests;
+mod walk;
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub use self::walk::{walk_dir, FilterEntry, WalkDir, WalkDirEntry, WalkDirIter};
 
 use crate::ffi::OsString;
 use crate::fmt;
@@ -229,6 +233,93 @@ pub struct DirBuilder {
     recursive: bool,
 }
 
+/// Options and flags which can be used to configure how a file is copied by
+/// [`copy_with`].
+///
+/// By default, files are cloned if the filesystem supports it, only the
+/// permission bits are preserved, and holes in sparse files are filled in,
+/// matching the behavior of [`copy`].
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_copy_options)]
+/// use std::fs::{self, CopyOptions, Reflink};
+///
+/// fn main() -> std::io::Result<()> {
+///     let mut options = CopyOptions::new();
+///     options.reflink(Reflink::Never).preserve_metadata(true).sparse(true);
+///     fs::copy_with("disk.img", "backup.img", &options)?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+#[unstable(feature = "fs_copy_options", issue = "none")]
+pub struct CopyOptions(fs_imp::CopyOptions);
+
+/// Whether [`copy_with`] may share the data of the copied file instead of
+/// duplicating it.
+///
+/// A reflink, or copy-on-write clone, makes the copy share storage with the
+/// original until either of them is modified. This makes copying large files
+/// nearly instant, but means the data is not physically duplicated.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[unstable(feature = "fs_copy_options", issue = "none")]
+pub enum Reflink {
+    /// Always copy the data.
+    #[unstable(feature = "fs_copy_options", issue = "none")]
+    Never,
+    /// Clone the file if the filesystem supports it, and copy the data otherwise.
+    #[default]
+    #[unstable(feature = "fs_copy_options", issue = "none")]
+    Auto,
+    /// Clone the file, and fail if that is not possible.
+    #[unstable(feature = "fs_copy_options", issue = "none")]
+    Always,
+}
+
+/// A file that atomically replaces another file once it is persisted.
+///
+/// Data written to a `PersistingFile` goes to a temporary file in the same
+/// directory as the target. Calling [`persist`] flushes the data to disk,
+/// renames the temporary file over the target and then syncs the directory,
+/// so that after a crash the target holds either its old or its new contents,
+/// never a mix of both. Dropping a `PersistingFile` without persisting it
+/// discards everything written and leaves the target untouched.
+///
+/// See [`write_atomic`] for a convenience function covering the common case.
+///
+/// [`persist`]: PersistingFile::persist
+///
+/// # Platform-specific behavior
+///
+/// On Linux the temporary file is created with `O_TMPFILE` where supported,
+/// so it has no name until [`persist`] links it into the directory. Other
+/// platforms use a uniquely named hidden file next to the target. Directory
+/// syncing is best-effort on platforms that cannot open directories as files.
+///
+/// The new file is created with default permissions; the permissions of a
+/// replaced file are not carried over.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_write_atomic)]
+/// use std::fs::PersistingFile;
+/// use std::io::Write;
+///
+/// fn main() -> std::io::Result<()> {
+///     let mut file = PersistingFile::create("config.toml")?;
+///     writeln!(file, "verbose = true")?;
+///     file.persist()?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "fs_write_atomic", issue = "none")]
+pub struct PersistingFile {
+    inner: fs_imp::PersistingFile,
+}
+
 /// Read the entire contents of a file into a bytes vector.
 ///
 /// This is a convenience function for using [`File::open`] and [`read_to_end`]
@@ -343,6 +434,47 @@ fn inner(path: &Path, contents: &[u8]) -> io::Result<()> {
     inner(path.as_ref(), contents.as_ref())
 }
 
+/// Atomically replace the entire contents of a file with a slice.
+///
+/// Unlike [`write`], which truncates the file in place, this function never
+/// leaves a partially written file behind: the contents are written to a
+/// temporary file which is synced and then renamed over `path`. Readers
+/// observe either the old or the new contents, and after this function
+/// returns the new contents survive a crash.
+///
+/// This is a convenience function for using [`PersistingFile::create`],
+/// [`write_all`] and [`PersistingFile::persist`].
+///
+/// [`write_all`]: Write::write_all
+///
+/// # Errors
+///
+/// This function will return an error if `path` does not name a file or its
+/// parent directory does not exist. Other errors may also be returned if
+/// writing, syncing or renaming the temporary file fails, in which case the
+/// original file is left untouched.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_write_atomic)]
+/// use std::fs;
+///
+/// fn main() -> std::io::Result<()> {
+///     fs::write_atomic("config.toml", "verbose = true\n")?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "fs_write_atomic", issue = "none")]
+pub fn write_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()> {
+    fn inner(path: &Path, contents: &[u8]) -> io::Result<()> {
+        let mut file = PersistingFile::create(path)?;
+        file.write_all(contents)?;
+        file.persist()
+    }
+    inner(path.as_ref(), contents.as_ref())
+}
+
 impl File {
     /// Attempts to open a file in read-only mode.
     ///
@@ -537,6 +669,196 @@ pub fn sync_data(&self) -> io::Result<()> {
         self.inner.datasync()
     }
 
+    /// Acquire an exclusive lock on the file. Blocks until the lock can be acquired.
+    ///
+    /// This acquires an exclusive lock; no other file handle to this file may acquire another
+    /// lock.
+    ///
+    /// Locks are associated with the underlying open file description rather than with this
+    /// `File` value, so handles obtained from [`try_clone`] share the lock. The lock is released
+    /// by [`unlock`] or once all handles to the open file description have been closed.
+    ///
+    /// If this file handle already holds a lock, the exact behavior is unspecified and
+    /// platform dependent, including the possibility that it will deadlock. Release any lock
+    /// with [`unlock`] before acquiring a lock of a different kind.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix with the `LOCK_EX`
+    /// flag, and the `LockFileEx` function on Windows with the `LOCKFILE_EXCLUSIVE_LOCK` flag.
+    /// Note that, this [may change in the future][changes].
+    ///
+    /// On Unix, the lock is advisory: it only excludes other handles that also use these locking
+    /// methods, and does not prevent reading or writing the file otherwise. On Windows, the lock
+    /// is mandatory: while an exclusive lock is held, reading or writing the file through any
+    /// other handle fails with `ERROR_LOCK_VIOLATION`. While a shared lock is held, writing to
+    /// the file through any handle fails, including the handle that holds the lock.
+    ///
+    /// On other platforms, and on Unix platforms without `flock`, this function returns an
+    /// error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`try_clone`]: File::try_clone
+    /// [`unlock`]: File::unlock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.txt")?;
+    ///     f.lock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn lock(&self) -> io::Result<()> {
+        self.inner.lock()
+    }
+
+    /// Acquire a shared lock on the file. Blocks until the lock can be acquired.
+    ///
+    /// This acquires a shared lock; more than one file handle may hold a shared lock,
+    /// but none may hold an exclusive lock at the same time.
+    ///
+    /// See [`lock`] for the scope of the lock, the behavior when a lock is already held, and
+    /// whether the lock is advisory or mandatory.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix with the `LOCK_SH`
+    /// flag, and the `LockFileEx` function on Windows. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`lock`]: File::lock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.txt")?;
+    ///     f.lock_shared()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.inner.lock_shared()
+    }
+
+    /// Acquire an exclusive lock on the file. Returns `Ok(false)` if the file is locked.
+    ///
+    /// This acquires an exclusive lock; no other file handle to this file may acquire another
+    /// lock. Unlike [`lock`], this never blocks: if another handle holds a lock,
+    /// `Ok(false)` is returned and no lock is acquired.
+    ///
+    /// See [`lock`] for the scope of the lock, the behavior when a lock is already held, and
+    /// whether the lock is advisory or mandatory.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix with the `LOCK_EX`
+    /// and `LOCK_NB` flags, and the `LockFileEx` function on Windows with the
+    /// `LOCKFILE_EXCLUSIVE_LOCK` and `LOCKFILE_FAIL_IMMEDIATELY` flags. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`lock`]: File::lock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.txt")?;
+    ///     if !f.try_lock()? {
+    ///         eprintln!("foo.txt is in use by another process");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.inner.try_lock()
+    }
+
+    /// Acquire a shared lock on the file. Returns `Ok(false)` if the file is locked.
+    ///
+    /// This acquires a shared lock; more than one file handle may hold a shared lock,
+    /// but none may hold an exclusive lock at the same time. If an exclusive lock is held,
+    /// `Ok(false)` is returned and no lock is acquired.
+    ///
+    /// See [`lock`] for the scope of the lock, the behavior when a lock is already held, and
+    /// whether the lock is advisory or mandatory.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix with the `LOCK_SH`
+    /// and `LOCK_NB` flags, and the `LockFileEx` function on Windows with the
+    /// `LOCKFILE_FAIL_IMMEDIATELY` flag. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`lock`]: File::lock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.txt")?;
+    ///     if f.try_lock_shared()? {
+    ///         // read the file
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.inner.try_lock_shared()
+    }
+
+    /// Release all locks on the file.
+    ///
+    /// All locks held by the open file description are released, including those acquired
+    /// through other handles to it. Unlocking a file that is not locked is not an error.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function on Unix with the `LOCK_UN`
+    /// flag, and the `UnlockFile` function on Windows. Note that, this
+    /// [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.txt")?;
+    ///     f.lock()?;
+    ///     f.unlock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn unlock(&self) -> io::Result<()> {
+        self.inner.unlock()
+    }
+
     /// Truncates or extends the underlying file, updating the size of
     /// this file to become `size`.
     ///
@@ -996,6 +1318,107 @@ fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
     }
 }
 
+impl PersistingFile {
+    /// Creates a temporary file that will replace `path` once persisted.
+    ///
+    /// The temporary file is opened for reading and writing. `path` itself
+    /// is not touched until [`persist`] is called and does not need to exist.
+    ///
+    /// [`persist`]: PersistingFile::persist
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `path` does not name a file, for
+    /// example because it ends in `..`, or if the temporary file cannot be
+    /// created in its parent directory.
+    #[unstable(feature = "fs_write_atomic", issue = "none")]
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<PersistingFile> {
+        fs_imp::PersistingFile::create(path.as_ref()).map(|inner| PersistingFile { inner })
+    }
+
+    /// Returns the temporary file the contents are written to.
+    ///
+    /// This can be used to query metadata or set permissions before the file
+    /// is persisted.
+    #[unstable(feature = "fs_write_atomic", issue = "none")]
+    pub fn as_file(&self) -> &File {
+        self.inner.file()
+    }
+
+    /// Returns the path this file will replace once persisted.
+    #[unstable(feature = "fs_write_atomic", issue = "none")]
+    pub fn target(&self) -> &Path {
+        self.inner.target()
+    }
+
+    /// Syncs the written data and atomically renames it over the target.
+    ///
+    /// Once this returns successfully, the new contents are durably stored at
+    /// the target path.
+    ///
+    /// # Errors
+    ///
+    /// If syncing or renaming fails the temporary file is removed and the
+    /// target is left untouched. An error while syncing the parent directory
+    /// is reported after the rename has already taken place.
+    #[unstable(feature = "fs_write_atomic", issue = "none")]
+    pub fn persist(self) -> io::Result<()> {
+        self.inner.persist()
+    }
+}
+
+#[unstable(feature = "fs_write_atomic", issue = "none")]
+impl fmt::Debug for PersistingFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PersistingFile")
+            .field("file", self.as_file())
+            .field("target", &self.target())
+            .finish()
+    }
+}
+
+#[unstable(feature = "fs_write_atomic", issue = "none")]
+impl Read for PersistingFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.as_file().read(buf)
+    }
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.as_file().read_vectored(bufs)
+    }
+    fn read_buf(&mut self, cursor: BorrowedCursor<'_>) -> io::Result<()> {
+        self.as_file().read_buf(cursor)
+    }
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        self.as_file().is_read_vectored()
+    }
+}
+
+#[unstable(feature = "fs_write_atomic", issue = "none")]
+impl Write for PersistingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.as_file().write(buf)
+    }
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.as_file().write_vectored(bufs)
+    }
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.as_file().is_write_vectored()
+    }
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.as_file().flush()
+    }
+}
+
+#[unstable(feature = "fs_write_atomic", issue = "none")]
+impl Seek for PersistingFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.as_file().seek(pos)
+    }
+}
+
 impl OpenOptions {
     /// Creates a blank new set of options ready for configuration.
     ///
@@ -2184,6 +2607,62 @@ pub fn copy<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<u64> {
     fs_imp::copy(from.as_ref(), to.as_ref())
 }
 
+/// Copies the contents of one file to another, as configured by `options`.
+///
+/// This behaves like [`copy`], but can clone the file instead of copying its
+/// data, preserve holes in sparse files, and preserve more metadata than the
+/// permission bits. See [`CopyOptions`] for the available options.
+///
+/// This function will **overwrite** the contents of `to`.
+///
+/// On success, the length of `from` is returned, which is the length of the
+/// `to` file as reported by `metadata`, even if no data had to be copied.
+///
+/// # Platform-specific behavior
+///
+/// On Linux (including Android), cloning uses the `FICLONE` ioctl, the data is
+/// otherwise copied with `copy_file_range(2)`, and holes are found with
+/// `lseek(2)` and `SEEK_DATA`. Preserving metadata copies the access and
+/// modification times and the extended attributes the destination accepts.
+///
+/// On other platforms, [`Reflink::Auto`] behaves like [`copy`], which clones
+/// the file on some of them, [`Reflink::Always`] always fails, holes are not
+/// preserved, and only the access and modification times are preserved in
+/// addition to the permissions.
+///
+/// Note that platform-specific behavior [may change in the future][changes].
+///
+/// [changes]: io#platform-specific-behavior
+///
+/// # Errors
+///
+/// This function will return an error in the situations [`copy`] does, and
+/// additionally if [`Reflink::Always`] was requested but the file could not
+/// be cloned, for example because `from` and `to` are on different
+/// filesystems.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_copy_options)]
+/// use std::fs::{self, CopyOptions, Reflink};
+///
+/// fn main() -> std::io::Result<()> {
+///     let mut options = CopyOptions::new();
+///     options.reflink(Reflink::Always);
+///     fs::copy_with("foo.txt", "bar.txt", &options)?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "fs_copy_options", issue = "none")]
+pub fn copy_with<P: AsRef<Path>, Q: AsRef<Path>>(
+    from: P,
+    to: Q,
+    options: &CopyOptions,
+) -> io::Result<u64> {
+    fs_imp::copy_with(from.as_ref(), to.as_ref(), &options.0)
+}
+
 /// Creates a new hard link on the filesystem.
 ///
 /// The `link` path will be a link pointing to the `original` path. Note that
@@ -2722,6 +3201,49 @@ fn as_inner_mut(&mut self) -> &mut fs_imp::DirBuilder {
     }
 }
 
+impl CopyOptions {
+    /// Creates a blank new set of options ready for configuration.
+    ///
+    /// The options default to [`Reflink::Auto`], not preserving metadata other
+    /// than the permission bits, and not preserving holes.
+    #[unstable(feature = "fs_copy_options", issue = "none")]
+    #[must_use]
+    pub fn new() -> CopyOptions {
+        CopyOptions(fs_imp::CopyOptions::new())
+    }
+
+    /// Sets whether the file may, must, or must not be cloned instead of copied.
+    #[unstable(feature = "fs_copy_options", issue = "none")]
+    pub fn reflink(&mut self, reflink: Reflink) -> &mut CopyOptions {
+        self.0.reflink(reflink);
+        self
+    }
+
+    /// Sets whether to preserve timestamps and extended attributes.
+    ///
+    /// The permission bits are always preserved. Ownership is never preserved.
+    #[unstable(feature = "fs_copy_options", issue = "none")]
+    pub fn preserve_metadata(&mut self, preserve_metadata: bool) -> &mut CopyOptions {
+        self.0.preserve_metadata(preserve_metadata);
+        self
+    }
+
+    /// Sets whether holes in a sparse `from` file are kept as holes in `to`,
+    /// instead of being filled with zeroes.
+    #[unstable(feature = "fs_copy_options", issue = "none")]
+    pub fn sparse(&mut self, sparse: bool) -> &mut CopyOptions {
+        self.0.sparse(sparse);
+        self
+    }
+}
+
+#[unstable(feature = "fs_copy_options", issue = "none")]
+impl Default for CopyOptions {
+    fn default() -> CopyOptions {
+        CopyOptions::new()
+    }
+}
+
 /// Returns `Ok(true)` if the path points at an existing entity.
 ///
 /// This function will traverse symbolic links to query information about the
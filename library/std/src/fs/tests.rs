@@ -1,7 +1,7 @@
 use crate::io::prelude::*;
 
 use crate::env;
-use crate::fs::{self, File, FileTimes, OpenOptions};
+use crate::fs::{self, CopyOptions, File, FileTimes, OpenOptions, PersistingFile, Reflink};
 use crate::io::{BorrowedBuf, ErrorKind, SeekFrom};
 use crate::mem::MaybeUninit;
 use crate::path::Path;
@@ -1803,3 +1803,313 @@ fn test_hidden_file_truncation() {
     let metadata = file.metadata().unwrap();
     assert_eq!(metadata.len(), 0);
 }
+
+#[test]
+fn write_atomic_replaces_contents() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("config.toml");
+
+    check!(fs::write_atomic(&path, b"old"));
+    assert_eq!(check!(fs::read(&path)), b"old");
+    check!(fs::write_atomic(&path, b"new contents"));
+    assert_eq!(check!(fs::read(&path)), b"new contents");
+
+    // No temporary files are left behind next to the target.
+    assert_eq!(check!(fs::read_dir(tmpdir.path())).count(), 1);
+}
+
+#[test]
+fn persisting_file_dropped_without_persist() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("config.toml");
+    check!(fs::write(&path, b"original"));
+
+    let mut file = check!(PersistingFile::create(&path));
+    assert_eq!(file.target(), path);
+    check!(file.write_all(b"discarded"));
+    drop(file);
+
+    assert_eq!(check!(fs::read(&path)), b"original");
+    assert_eq!(check!(fs::read_dir(tmpdir.path())).count(), 1);
+}
+
+#[test]
+fn persisting_file_read_back_before_persist() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("data.bin");
+
+    let mut file = check!(PersistingFile::create(&path));
+    check!(file.write_all(b"hello world"));
+    assert!(!path.exists());
+    check!(file.seek(SeekFrom::Start(6)));
+    let mut buf = String::new();
+    check!(file.read_to_string(&mut buf));
+    assert_eq!(buf, "world");
+    check!(file.persist());
+
+    assert_eq!(check!(fs::read_to_string(&path)), "hello world");
+}
+
+#[test]
+fn write_atomic_rejects_non_file_path() {
+    let tmpdir = tmpdir();
+    let err = fs::write_atomic(tmpdir.join(".."), b"nope").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+#[cfg(any(
+    windows,
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_vendor = "apple",
+))]
+fn file_lock_multiple_shared() {
+    let tmpdir = tmpdir();
+    let filename = &tmpdir.join("file_lock_multiple_shared_test.txt");
+    let f1 = check!(File::create(filename));
+    let f2 = check!(OpenOptions::new().write(true).open(filename));
+
+    // Check that we can acquire concurrent shared locks
+    check!(f1.lock_shared());
+    check!(f2.lock_shared());
+    check!(f1.unlock());
+    check!(f2.unlock());
+    assert!(check!(f1.try_lock_shared()));
+    assert!(check!(f2.try_lock_shared()));
+}
+
+#[test]
+#[cfg(any(
+    windows,
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_vendor = "apple",
+))]
+fn file_lock_blocking() {
+    let tmpdir = tmpdir();
+    let filename = &tmpdir.join("file_lock_blocking_test.txt");
+    let f1 = check!(File::create(filename));
+    let f2 = check!(OpenOptions::new().write(true).open(filename));
+
+    // Check that shared locks block exclusive locks
+    check!(f1.lock_shared());
+    assert!(!check!(f2.try_lock()));
+    check!(f1.unlock());
+
+    // Check that exclusive locks block shared locks
+    check!(f1.lock());
+    assert!(!check!(f2.try_lock_shared()));
+    check!(f1.unlock());
+
+    // Check that the lock is released once unlocked
+    assert!(check!(f2.try_lock()));
+}
+
+#[test]
+#[cfg(any(
+    windows,
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_vendor = "apple",
+))]
+fn file_lock_drop() {
+    let tmpdir = tmpdir();
+    let filename = &tmpdir.join("file_lock_dup_test.txt");
+    let f1 = check!(File::create(filename));
+    let f2 = check!(OpenOptions::new().write(true).open(filename));
+
+    // Check that locks are released when the File is dropped
+    check!(f1.lock_shared());
+    assert!(!check!(f2.try_lock()));
+    drop(f1);
+    assert!(check!(f2.try_lock()));
+}
+
+#[test]
+#[cfg(any(
+    windows,
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_vendor = "apple",
+))]
+fn file_unlock_unlocked() {
+    let tmpdir = tmpdir();
+    let filename = &tmpdir.join("file_unlock_unlocked_test.txt");
+    let f1 = check!(File::create(filename));
+    let f2 = check!(OpenOptions::new().write(true).open(filename));
+
+    // Check that unlocking a file that was never locked succeeds
+    check!(f1.unlock());
+
+    // Check that unlocking twice succeeds, and leaves the file unlocked
+    check!(f1.lock());
+    check!(f1.unlock());
+    check!(f1.unlock());
+    assert!(check!(f2.try_lock()));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn statx_extra_metadata() {
+    use crate::os::linux::fs::{metadata_with, MetadataExt, StatxMask};
+
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("statx_extra_metadata.txt");
+    check!(fs::write(&path, b"hello"));
+
+    let meta = check!(fs::metadata(&path));
+    let masked = check!(metadata_with(&path, StatxMask::MNT_ID));
+    // The fields `Metadata` relies on are always requested.
+    assert!(masked.is_file());
+    assert_eq!(masked.len(), 5);
+    assert_eq!(check!(masked.modified()), check!(meta.modified()));
+    // Both queries see the same mount, whether or not `statx` is available.
+    assert_eq!(meta.mount_id(), masked.mount_id());
+    if let Some(attrs) = meta.attributes() {
+        assert_ne!(attrs.is_immutable(), Some(true));
+        assert_eq!(attrs.bits() & !attrs.supported_bits(), 0);
+    }
+    // Direct I/O alignment is only reported when asked for.
+    assert_eq!(meta.dio_mem_align(), None);
+}
+
+#[test]
+fn copy_with_options() {
+    let tmpdir = tmpdir();
+    let input = tmpdir.join("in.txt");
+    let out = tmpdir.join("out.txt");
+    check!(fs::write(&input, b"hello world"));
+    let mtime = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+    check!(check!(File::options().write(true).open(&input)).set_modified(mtime));
+
+    let mut options = CopyOptions::new();
+    options.reflink(Reflink::Never).preserve_metadata(true);
+    assert_eq!(check!(fs::copy_with(&input, &out, &options)), 11);
+    assert_eq!(check!(fs::read(&out)), b"hello world");
+    assert_eq!(check!(check!(fs::metadata(&out)).modified()), mtime);
+
+    // Cloning may or may not be supported, but must never produce a bad copy.
+    options.reflink(Reflink::Always).preserve_metadata(false);
+    if fs::copy_with(&input, &out, &options).is_ok() {
+        assert_eq!(check!(fs::read(&out)), b"hello world");
+    }
+}
+
+#[test]
+fn copy_with_sparse() {
+    let tmpdir = tmpdir();
+    let input = tmpdir.join("in.img");
+    let out = tmpdir.join("out.img");
+
+    // Data surrounded by holes, with a trailing hole.
+    let mut file = check!(File::create(&input));
+    check!(file.seek(SeekFrom::Start(1 << 20)));
+    check!(file.write_all(b"data"));
+    check!(file.set_len(4 << 20));
+    drop(file);
+
+    let mut options = CopyOptions::new();
+    options.reflink(Reflink::Never).sparse(true);
+    assert_eq!(check!(fs::copy_with(&input, &out, &options)), 4 << 20);
+    assert_eq!(check!(fs::read(&input)), check!(fs::read(&out)));
+}
+
+#[test]
+#[cfg(unix)]
+fn copy_with_sparse_to_device() {
+    let tmpdir = tmpdir();
+    let input = tmpdir.join("in.img");
+    let mut file = check!(File::create(&input));
+    check!(file.write_all(b"data"));
+    check!(file.set_len(1 << 20));
+    drop(file);
+
+    // Like `fs::copy`, copying to a destination that can't seek works.
+    let mut options = CopyOptions::new();
+    options.sparse(true);
+    assert_eq!(check!(fs::copy_with(&input, "/dev/null", &options)), 1 << 20);
+}
+
+#[test]
+fn walk_dir_orders_and_depths() {
+    let tmpdir = tmpdir();
+    let root = tmpdir.join("walk");
+    check!(fs::create_dir_all(root.join("a/b")));
+    check!(fs::create_dir_all(root.join("c")));
+    check!(fs::write(root.join("a/b/file"), b""));
+    check!(fs::write(root.join("a/file"), b""));
+
+    let walk = |walk: fs::WalkDir| -> Vec<(String, usize)> {
+        walk.sort_by_file_name()
+            .into_iter()
+            .map(|entry| {
+                let entry = check!(entry);
+                let path = check!(entry.path().strip_prefix(&root)).to_owned();
+                (path.to_str().unwrap().replace('\\', "/"), entry.depth())
+            })
+            .collect()
+    };
+
+    let pre = walk(fs::walk_dir(&root));
+    let expected = [("", 0), ("a", 1), ("a/b", 2), ("a/b/file", 3), ("a/file", 2), ("c", 1)];
+    assert_eq!(pre, expected.map(|(p, d)| (p.to_owned(), d)));
+
+    let post = walk(fs::walk_dir(&root).contents_first(true).min_depth(1).max_depth(2));
+    let expected = [("a/b", 2), ("a/file", 2), ("a", 1), ("c", 1)];
+    assert_eq!(post, expected.map(|(p, d)| (p.to_owned(), d)));
+}
+
+#[test]
+fn walk_dir_prune() {
+    let tmpdir = tmpdir();
+    let root = tmpdir.join("walk");
+    check!(fs::create_dir_all(root.join("skip/inner")));
+    check!(fs::create_dir_all(root.join("keep/inner")));
+
+    let mut walk = fs::walk_dir(&root).min_depth(1).sort_by_file_name().into_iter();
+    let mut seen = Vec::new();
+    while let Some(entry) = walk.next() {
+        let entry = check!(entry);
+        if entry.file_name() == "skip" {
+            walk.skip_current_dir();
+        }
+        seen.push(entry.into_path());
+    }
+    assert_eq!(seen, [root.join("keep"), root.join("keep/inner"), root.join("skip")]);
+
+    let filtered: Vec<_> = fs::walk_dir(&root)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| entry.file_name() != "keep")
+        .map(|entry| check!(entry).into_path())
+        .collect();
+    assert_eq!(filtered, [root.join("skip"), root.join("skip/inner")]);
+}
+
+#[test]
+fn walk_dir_symlink_loop() {
+    let tmpdir = tmpdir();
+    if !got_symlink_permission(&tmpdir) {
+        return;
+    };
+    let root = tmpdir.join("walk");
+    check!(fs::create_dir_all(root.join("dir")));
+    check!(symlink_dir(&root, root.join("dir/loop")));
+
+    // Without following links the symlink is a leaf.
+    let entries: Vec<_> = fs::walk_dir(&root).into_iter().map(|entry| check!(entry)).collect();
+    assert_eq!(entries.len(), 3);
+    assert!(entries.iter().any(|entry| entry.path_is_symlink() && entry.depth() == 2));
+
+    // Following links detects the loop instead of recursing forever.
+    let errors: Vec<_> =
+        fs::walk_dir(&root).follow_links(true).into_iter().filter_map(Result::err).collect();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind(), ErrorKind::FilesystemLoop);
+}
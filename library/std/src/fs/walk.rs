@@ -0,0 +1,639 @@
+//! Recursive directory traversal.
+
+use super::{read_dir, DirEntry, FileType, Metadata, ReadDir};
+use crate::cmp::Ordering;
+use crate::ffi::OsStr;
+use crate::fmt;
+use crate::io;
+use crate::path::{Path, PathBuf};
+use crate::sys::fs as fs_imp;
+use crate::vec;
+
+/// Returns a builder for recursively walking the directory tree at `root`.
+///
+/// This is a shorthand for [`WalkDir::new`].
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_walk_dir)]
+/// use std::fs;
+///
+/// fn main() -> std::io::Result<()> {
+///     for entry in fs::walk_dir("src") {
+///         let entry = entry?;
+///         println!("{}", entry.path().display());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub fn walk_dir<P: AsRef<Path>>(root: P) -> WalkDir {
+    WalkDir::new(root)
+}
+
+/// A builder for recursively walking a directory tree.
+///
+/// Walking yields the root itself first, at depth 0, followed by the entries
+/// of every directory below it. Each directory is yielded before its contents
+/// unless [`contents_first`] is set. Errors, such as a directory that cannot
+/// be read, are yielded in place of the entry they concern, after which the
+/// walk continues.
+///
+/// By default, symbolic links are not followed (except for `root` itself),
+/// there is no depth limit, and the entries of a directory are yielded in the
+/// order the platform returns them.
+///
+/// [`contents_first`]: WalkDir::contents_first
+///
+/// # Platform-specific behavior
+///
+/// On most Unix platforms, directories are opened relative to their parent
+/// with `openat`, like [`remove_dir_all`] does, so a walk is not affected by
+/// concurrent renames of the directories above the one being read and does
+/// not follow a symlink that replaced a directory while the walk was running,
+/// unless symlinks are followed. Other platforms open directories by path.
+///
+/// [`remove_dir_all`]: super::remove_dir_all
+///
+/// A directory stays open until all of its entries have been walked, so a
+/// walk holds one open directory for each level of depth it is in, and a
+/// tree deeper than the limit of open files of the process cannot be walked
+/// to its bottom. [`max_depth`] bounds the number of open directories.
+///
+/// [`max_depth`]: WalkDir::max_depth
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_walk_dir)]
+/// use std::fs::WalkDir;
+///
+/// fn main() -> std::io::Result<()> {
+///     let mut walk = WalkDir::new("src").max_depth(3).sort_by_file_name().into_iter();
+///     while let Some(entry) = walk.next() {
+///         let entry = entry?;
+///         if entry.file_name() == "target" {
+///             walk.skip_current_dir();
+///             continue;
+///         }
+///         println!("{}", entry.path().display());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub struct WalkDir {
+    root: PathBuf,
+    options: WalkOptions,
+}
+
+struct WalkOptions {
+    min_depth: usize,
+    max_depth: usize,
+    follow_links: bool,
+    contents_first: bool,
+    sorter: Option<Box<dyn FnMut(&DirEntry, &DirEntry) -> Ordering + Send + Sync + 'static>>,
+}
+
+impl WalkDir {
+    /// Creates a builder for recursively walking the directory tree at `root`.
+    ///
+    /// If `root` is a symlink, it is always followed. If it is not a directory,
+    /// the walk only yields `root` itself.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn new<P: AsRef<Path>>(root: P) -> WalkDir {
+        WalkDir {
+            root: root.as_ref().to_path_buf(),
+            options: WalkOptions {
+                min_depth: 0,
+                max_depth: usize::MAX,
+                follow_links: false,
+                contents_first: false,
+                sorter: None,
+            },
+        }
+    }
+
+    /// Sets the minimum depth of the entries that are yielded.
+    ///
+    /// The root has depth 0, its entries depth 1, and so on. Shallower entries
+    /// are still walked, but not yielded. Setting this to 1 skips the root.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn min_depth(mut self, depth: usize) -> WalkDir {
+        self.options.min_depth = depth;
+        self
+    }
+
+    /// Sets the maximum depth of the entries that are yielded.
+    ///
+    /// Directories at this depth are yielded, but not descended into. Setting
+    /// this to 0 only yields the root, and to 1 behaves like [`read_dir`] plus
+    /// the root.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn max_depth(mut self, depth: usize) -> WalkDir {
+        self.options.max_depth = depth;
+        self
+    }
+
+    /// Sets whether symbolic links are followed.
+    ///
+    /// When followed, a symlink to a directory is walked like a directory, and
+    /// the entries yielded for symlinks describe their targets. A symlink that
+    /// leads back to one of its own ancestors is yielded as an error of kind
+    /// [`io::ErrorKind::FilesystemLoop`] instead of being descended into, and a
+    /// broken symlink is yielded as an error.
+    ///
+    /// Loops are detected by the device and inode numbers of the directories
+    /// on Unix, and by their canonical paths on other platforms.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn follow_links(mut self, yes: bool) -> WalkDir {
+        self.options.follow_links = yes;
+        self
+    }
+
+    /// Sets whether the contents of a directory are yielded before the
+    /// directory itself.
+    ///
+    /// This post-order walk is useful to process a tree bottom-up, e.g. to
+    /// remove it. [`WalkDirIter::skip_current_dir`] is of little use in this
+    /// mode, as directories are only yielded after having been walked.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn contents_first(mut self, yes: bool) -> WalkDir {
+        self.options.contents_first = yes;
+        self
+    }
+
+    /// Sorts the entries of each directory with the given comparison function
+    /// before yielding them.
+    ///
+    /// Sorting requires reading all entries of a directory before yielding
+    /// the first of them. Errors reading a directory are yielded before its
+    /// entries.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn sort_by<F>(mut self, cmp: F) -> WalkDir
+    where
+        F: FnMut(&DirEntry, &DirEntry) -> Ordering + Send + Sync + 'static,
+    {
+        self.options.sorter = Some(Box::new(cmp));
+        self
+    }
+
+    /// Sorts the entries of each directory by their file name.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn sort_by_file_name(self) -> WalkDir {
+        self.sort_by(|a, b| a.file_name().cmp(&b.file_name()))
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl fmt::Debug for WalkDir {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WalkDir")
+            .field("root", &self.root)
+            .field("min_depth", &self.options.min_depth)
+            .field("max_depth", &self.options.max_depth)
+            .field("follow_links", &self.options.follow_links)
+            .field("contents_first", &self.options.contents_first)
+            .field("sorted", &self.options.sorter.is_some())
+            .finish()
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl IntoIterator for WalkDir {
+    type Item = io::Result<WalkDirEntry>;
+    type IntoIter = WalkDirIter;
+
+    fn into_iter(self) -> WalkDirIter {
+        WalkDirIter {
+            options: self.options,
+            root: Some(self.root),
+            stack: Vec::new(),
+            descended: false,
+        }
+    }
+}
+
+/// An entry yielded by [`WalkDirIter`].
+///
+/// Unlike [`DirEntry`], this also describes the root of the walk, and knows
+/// its depth below the root.
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub struct WalkDirEntry {
+    path: PathBuf,
+    file_type: FileType,
+    depth: usize,
+    follow_link: bool,
+    // The metadata of the target, for the root and followed symlinks.
+    target_metadata: Option<Metadata>,
+    // `None` for the root of the walk.
+    entry: Option<DirEntry>,
+}
+
+impl WalkDirEntry {
+    fn root(path: PathBuf) -> io::Result<WalkDirEntry> {
+        let metadata = super::metadata(&path)?;
+        let follow_link = super::symlink_metadata(&path)?.file_type().is_symlink();
+        Ok(WalkDirEntry {
+            path,
+            file_type: metadata.file_type(),
+            depth: 0,
+            follow_link,
+            target_metadata: Some(metadata),
+            entry: None,
+        })
+    }
+
+    fn from_entry(entry: DirEntry, depth: usize, follow_links: bool) -> io::Result<WalkDirEntry> {
+        let path = entry.path();
+        let mut file_type = entry.file_type()?;
+        let follow_link = follow_links && file_type.is_symlink();
+        let mut target_metadata = None;
+        if follow_link {
+            let metadata = super::metadata(&path)?;
+            file_type = metadata.file_type();
+            target_metadata = Some(metadata);
+        }
+        Ok(WalkDirEntry {
+            path,
+            file_type,
+            depth,
+            follow_link,
+            target_metadata,
+            entry: Some(entry),
+        })
+    }
+
+    /// Returns the full path to this entry, which starts with the root of the walk.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the full path to this entry, consuming the entry.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    #[must_use]
+    pub fn into_path(self) -> PathBuf {
+        self.path
+    }
+
+    /// Returns the file name of this entry.
+    ///
+    /// For the root of the walk, this is the last component of the root path,
+    /// or the entire root path if it has no file name, e.g. for `..`.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    #[must_use]
+    pub fn file_name(&self) -> &OsStr {
+        self.path.file_name().unwrap_or(self.path.as_os_str())
+    }
+
+    /// Returns the depth of this entry below the root, which has depth 0.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the file type of this entry.
+    ///
+    /// If this entry is a symlink that was followed, this is the file type of
+    /// its target.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    #[must_use]
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Returns whether the path of this entry is a symlink, whether or not it
+    /// was followed.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    #[must_use]
+    pub fn path_is_symlink(&self) -> bool {
+        self.follow_link || self.file_type.is_symlink()
+    }
+
+    /// Queries the metadata of this entry.
+    ///
+    /// Symlinks are only traversed if they were followed by the walk.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn metadata(&self) -> io::Result<Metadata> {
+        match &self.entry {
+            Some(entry) if !self.follow_link => entry.metadata(),
+            _ => super::metadata(&self.path),
+        }
+    }
+
+    /// Identifies the directory this entry leads to, to detect loops.
+    #[cfg(unix)]
+    fn dir_id(&self) -> io::Result<DirId> {
+        use crate::os::unix::fs::MetadataExt;
+
+        let id = |metadata: &Metadata| (metadata.dev(), metadata.ino());
+        match &self.target_metadata {
+            Some(metadata) => Ok(id(metadata)),
+            None => self.metadata().map(|metadata| id(&metadata)),
+        }
+    }
+
+    /// Identifies the directory this entry leads to, to detect loops.
+    #[cfg(not(unix))]
+    fn dir_id(&self) -> io::Result<DirId> {
+        super::canonicalize(&self.path)
+    }
+
+    fn open(&self) -> io::Result<ReadDir> {
+        match &self.entry {
+            Some(entry) => fs_imp::readdir_entry(&entry.0, self.follow_link).map(ReadDir),
+            None => read_dir(&self.path),
+        }
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl fmt::Debug for WalkDirEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WalkDirEntry")
+            .field("path", &self.path)
+            .field("depth", &self.depth)
+            .field("file_type", &self.file_type)
+            .field("follow_link", &self.follow_link)
+            .finish()
+    }
+}
+
+/// An iterator over the entries of a directory tree.
+///
+/// This iterator is created by calling [`into_iter`] on a [`WalkDir`].
+///
+/// [`into_iter`]: IntoIterator::into_iter
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub struct WalkDirIter {
+    options: WalkOptions,
+    // The root of the walk, until it has been yielded.
+    root: Option<PathBuf>,
+    // The directories currently being walked, innermost last.
+    stack: Vec<Frame>,
+    // Whether the last entry yielded is the directory on top of `stack`.
+    descended: bool,
+}
+
+// The device and inode numbers of a directory.
+#[cfg(unix)]
+type DirId = (u64, u64);
+// The canonical path of a directory.
+#[cfg(not(unix))]
+type DirId = PathBuf;
+
+struct Frame {
+    entries: FrameEntries,
+    // The identity of the directory, to detect loops when following symlinks.
+    id: Option<DirId>,
+    // The directory itself, if it is yielded after its contents.
+    dir: Option<WalkDirEntry>,
+}
+
+enum FrameEntries {
+    Unsorted(ReadDir),
+    Sorted(vec::IntoIter<io::Result<DirEntry>>),
+}
+
+impl Iterator for FrameEntries {
+    type Item = io::Result<DirEntry>;
+
+    fn next(&mut self) -> Option<io::Result<DirEntry>> {
+        match self {
+            FrameEntries::Unsorted(read_dir) => read_dir.next(),
+            FrameEntries::Sorted(entries) => entries.next(),
+        }
+    }
+}
+
+impl WalkDirIter {
+    /// Skips the rest of the directory that was entered last.
+    ///
+    /// If the last entry yielded was a directory that is going to be walked,
+    /// its contents are skipped. Otherwise, the remaining entries of the
+    /// directory containing the last entry are skipped. This makes it possible
+    /// to prune subtrees from a walk.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_walk_dir)]
+    /// use std::fs;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let mut walk = fs::walk_dir(".").into_iter();
+    ///     while let Some(entry) = walk.next() {
+    ///         let entry = entry?;
+    ///         if entry.file_name() == ".git" {
+    ///             walk.skip_current_dir();
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn skip_current_dir(&mut self) {
+        if let Some(frame) = self.stack.pop() {
+            self.descended = false;
+            if let Some(dir) = frame.dir {
+                // The directory has not been yielded yet in a post-order walk.
+                self.stack.push(Frame {
+                    entries: FrameEntries::Sorted(Vec::new().into_iter()),
+                    id: None,
+                    dir: Some(dir),
+                });
+            }
+        }
+    }
+
+    /// Returns an iterator that does not yield, nor walk below, the entries
+    /// for which `predicate` returns `false`.
+    ///
+    /// Errors are always yielded. Note that in a [`contents_first`] walk the
+    /// contents of a directory are walked before the predicate sees it.
+    ///
+    /// [`contents_first`]: WalkDir::contents_first
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_walk_dir)]
+    /// use std::fs;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let walk = fs::walk_dir(".").into_iter();
+    ///     let hidden = |entry: &fs::WalkDirEntry| {
+    ///         entry.depth() > 0 && entry.file_name().as_encoded_bytes().starts_with(b".")
+    ///     };
+    ///     for entry in walk.filter_entry(|entry| !hidden(entry)) {
+    ///         println!("{}", entry?.path().display());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn filter_entry<P>(self, predicate: P) -> FilterEntry<P>
+    where
+        P: FnMut(&WalkDirEntry) -> bool,
+    {
+        FilterEntry { iter: self, predicate }
+    }
+
+    /// Decides what to do with a newly found entry, and returns it if it should
+    /// be yielded now.
+    fn handle_entry(&mut self, entry: WalkDirEntry) -> Option<io::Result<WalkDirEntry>> {
+        self.descended = false;
+        if entry.file_type.is_dir() && entry.depth < self.options.max_depth {
+            let id = if self.options.follow_links {
+                match entry.dir_id() {
+                    Ok(id) => Some(id),
+                    Err(e) => return Some(Err(e)),
+                }
+            } else {
+                None
+            };
+            if entry.follow_link && self.stack.iter().any(|frame| frame.id == id) {
+                return Some(Err(io::const_io_error!(
+                    io::ErrorKind::FilesystemLoop,
+                    "symlink leads to one of its ancestor directories",
+                )));
+            }
+            let read_dir = match entry.open() {
+                Ok(read_dir) => read_dir,
+                Err(e) => return Some(Err(e)),
+            };
+            let entries = match &mut self.options.sorter {
+                Some(sorter) => {
+                    let mut entries: Vec<_> = read_dir.collect();
+                    entries.sort_by(|a, b| match (a, b) {
+                        (Ok(a), Ok(b)) => sorter(a, b),
+                        (Err(_), Ok(_)) => Ordering::Less,
+                        (Ok(_), Err(_)) => Ordering::Greater,
+                        (Err(_), Err(_)) => Ordering::Equal,
+                    });
+                    FrameEntries::Sorted(entries.into_iter())
+                }
+                None => FrameEntries::Unsorted(read_dir),
+            };
+            if self.options.contents_first {
+                self.stack.push(Frame { entries, id, dir: Some(entry) });
+                return None;
+            }
+            self.stack.push(Frame { entries, id, dir: None });
+            self.descended = true;
+        }
+        if entry.depth < self.options.min_depth {
+            return None;
+        }
+        Some(Ok(entry))
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl Iterator for WalkDirIter {
+    type Item = io::Result<WalkDirEntry>;
+
+    fn next(&mut self) -> Option<io::Result<WalkDirEntry>> {
+        if let Some(root) = self.root.take() {
+            let entry = match WalkDirEntry::root(root) {
+                Ok(entry) => entry,
+                Err(e) => return Some(Err(e)),
+            };
+            if let Some(result) = self.handle_entry(entry) {
+                return Some(result);
+            }
+        }
+        loop {
+            let frame = self.stack.last_mut()?;
+            let depth = self.stack.len();
+            match frame.entries.next() {
+                Some(Ok(entry)) => {
+                    let entry = WalkDirEntry::from_entry(entry, depth, self.options.follow_links);
+                    let result = match entry {
+                        Ok(entry) => self.handle_entry(entry),
+                        Err(e) => Some(Err(e)),
+                    };
+                    if let Some(result) = result {
+                        return Some(result);
+                    }
+                }
+                Some(Err(e)) => {
+                    self.descended = false;
+                    return Some(Err(e));
+                }
+                None => {
+                    let frame = self.stack.pop().unwrap();
+                    self.descended = false;
+                    if let Some(dir) = frame.dir {
+                        if dir.depth >= self.options.min_depth {
+                            return Some(Ok(dir));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl fmt::Debug for WalkDirIter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WalkDirIter")
+            .field("root", &self.root)
+            .field("depth", &self.stack.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// An iterator over the entries of a directory tree, pruned by a predicate.
+///
+/// This iterator is created by [`WalkDirIter::filter_entry`].
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub struct FilterEntry<P> {
+    iter: WalkDirIter,
+    predicate: P,
+}
+
+impl<P> FilterEntry<P> {
+    /// Skips the rest of the directory that was entered last.
+    ///
+    /// See [`WalkDirIter::skip_current_dir`].
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn skip_current_dir(&mut self) {
+        self.iter.skip_current_dir();
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl<P> Iterator for FilterEntry<P>
+where
+    P: FnMut(&WalkDirEntry) -> bool,
+{
+    type Item = io::Result<WalkDirEntry>;
+
+    fn next(&mut self) -> Option<io::Result<WalkDirEntry>> {
+        loop {
+            let entry = match self.iter.next()? {
+                Ok(entry) => entry,
+                Err(e) => return Some(Err(e)),
+            };
+            if (self.predicate)(&entry) {
+                return Some(Ok(entry));
+            }
+            if self.iter.descended {
+                self.iter.skip_current_dir();
+            }
+        }
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl<P> fmt::Debug for FilterEntry<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FilterEntry").field("iter", &self.iter).finish_non_exhaustive()
+    }
+}
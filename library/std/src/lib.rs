@@ -278,6 +278,7 @@
 #![feature(allow_internal_unsafe)]
 #![feature(allow_internal_unstable)]
 #![feature(asm_experimental_arch)]
+#![feature(avx512_target_feature)]
 #![feature(c_unwind)]
 #![feature(cfg_sanitizer_cfi)]
 #![feature(cfg_target_thread_local)]
@@ -613,6 +614,10 @@ pub mod simd {
     #[doc(inline)]
     pub use crate::std_float::StdFloat;
     #[doc(inline)]
+    pub use crate::std_float::{
+        dispatch_lanes, preferred_lanes, vector_width_bytes, LaneCountDispatch,
+    };
+    #[doc(inline)]
     pub use core::simd::*;
 }
 
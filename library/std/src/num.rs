@@ -15,6 +15,8 @@
 pub use core::num::Wrapping;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use core::num::{FpCategory, ParseFloatError, ParseIntError, TryFromIntError};
+#[unstable(feature = "float_parse_options", issue = "none")]
+pub use core::num::ParseFloatOptions;
 
 #[unstable(
     feature = "nonzero_internals",
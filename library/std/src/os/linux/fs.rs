@@ -5,7 +5,11 @@
 #![stable(feature = "metadata_ext", since = "1.1.0")]
 
 use crate::fs::Metadata;
-use crate::sys_common::AsInner;
+use crate::io;
+use crate::ops::BitOr;
+use crate::path::Path;
+use crate::sys;
+use crate::sys_common::{AsInner, FromInner};
 
 #[allow(deprecated)]
 use crate::os::linux::raw;
@@ -323,6 +327,75 @@ pub trait MetadataExt {
     /// ```
     #[stable(feature = "metadata_ext2", since = "1.8.0")]
     fn st_blocks(&self) -> u64;
+    /// Returns the id of the mount containing the file, as found in the
+    /// `mount_id` field of `/proc/self/mountinfo`.
+    ///
+    /// Returns `None` if the metadata was not obtained through `statx`, or the
+    /// kernel (before Linux 5.8) did not report a mount id.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_statx)]
+    /// use std::fs;
+    /// use std::io;
+    /// use std::os::linux::fs::MetadataExt;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let meta = fs::metadata("some_file")?;
+    ///     if let Some(id) = meta.mount_id() {
+    ///         println!("mounted at mount {id}");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "linux_statx", issue = "none")]
+    fn mount_id(&self) -> Option<u64> {
+        None
+    }
+    /// Returns the `STATX_ATTR_*` attributes of the file, such as whether it
+    /// is immutable, append-only or compressed.
+    ///
+    /// Returns `None` if the metadata was not obtained through `statx`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_statx)]
+    /// use std::fs;
+    /// use std::io;
+    /// use std::os::linux::fs::MetadataExt;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let meta = fs::metadata("some_file")?;
+    ///     if let Some(attrs) = meta.attributes() {
+    ///         println!("immutable: {:?}", attrs.is_immutable());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "linux_statx", issue = "none")]
+    fn attributes(&self) -> Option<FileAttributes> {
+        None
+    }
+    /// Returns the alignment in bytes required for user memory buffers used
+    /// with direct I/O (`O_DIRECT`) on the file.
+    ///
+    /// This is only reported if [`StatxMask::DIOALIGN`] was requested through
+    /// [`metadata_with`], and the file supports direct I/O.
+    #[unstable(feature = "linux_statx", issue = "none")]
+    fn dio_mem_align(&self) -> Option<u32> {
+        None
+    }
+    /// Returns the alignment in bytes required for file offsets and I/O
+    /// segment lengths used with direct I/O (`O_DIRECT`) on the file.
+    ///
+    /// This is only reported if [`StatxMask::DIOALIGN`] was requested through
+    /// [`metadata_with`], and the file supports direct I/O.
+    #[unstable(feature = "linux_statx", issue = "none")]
+    fn dio_offset_align(&self) -> Option<u32> {
+        None
+    }
 }
 
 #[stable(feature = "metadata_ext", since = "1.1.0")]
@@ -401,4 +474,199 @@ fn st_blksize(&self) -> u64 {
     fn st_blocks(&self) -> u64 {
         self.as_inner().as_inner().st_blocks as u64
     }
+    fn mount_id(&self) -> Option<u64> {
+        self.as_inner().mount_id()
+    }
+    fn attributes(&self) -> Option<FileAttributes> {
+        let (attributes, supported) = self.as_inner().attributes()?;
+        Some(FileAttributes { attributes, supported })
+    }
+    fn dio_mem_align(&self) -> Option<u32> {
+        self.as_inner().dio_align().map(|(mem, _)| mem)
+    }
+    fn dio_offset_align(&self) -> Option<u32> {
+        self.as_inner().dio_align().map(|(_, offset)| offset)
+    }
+}
+
+/// The `STATX_ATTR_*` attributes of a file, as returned by
+/// [`MetadataExt::attributes`].
+///
+/// Each query returns `None` if the filesystem does not support the attribute,
+/// so "not set" can be told apart from "unknown".
+#[unstable(feature = "linux_statx", issue = "none")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FileAttributes {
+    attributes: u64,
+    supported: u64,
+}
+
+impl FileAttributes {
+    const COMPRESSED: u64 = 0x0000_0004;
+    const IMMUTABLE: u64 = 0x0000_0010;
+    const APPEND: u64 = 0x0000_0020;
+    const NODUMP: u64 = 0x0000_0040;
+    const ENCRYPTED: u64 = 0x0000_0800;
+    const AUTOMOUNT: u64 = 0x0000_1000;
+    const MOUNT_ROOT: u64 = 0x0000_2000;
+    const VERITY: u64 = 0x0010_0000;
+    const DAX: u64 = 0x0020_0000;
+
+    fn get(&self, attribute: u64) -> Option<bool> {
+        if self.supported & attribute != 0 { Some(self.attributes & attribute != 0) } else { None }
+    }
+
+    /// Returns whether the file is compressed by the filesystem.
+    #[unstable(feature = "linux_statx", issue = "none")]
+    pub fn is_compressed(&self) -> Option<bool> {
+        self.get(Self::COMPRESSED)
+    }
+
+    /// Returns whether the file cannot be modified, deleted or renamed.
+    #[unstable(feature = "linux_statx", issue = "none")]
+    pub fn is_immutable(&self) -> Option<bool> {
+        self.get(Self::IMMUTABLE)
+    }
+
+    /// Returns whether the file can only be opened in append mode for writing.
+    #[unstable(feature = "linux_statx", issue = "none")]
+    pub fn is_append_only(&self) -> Option<bool> {
+        self.get(Self::APPEND)
+    }
+
+    /// Returns whether the file is not a candidate for backup by `dump`.
+    #[unstable(feature = "linux_statx", issue = "none")]
+    pub fn is_nodump(&self) -> Option<bool> {
+        self.get(Self::NODUMP)
+    }
+
+    /// Returns whether the file requires a key to be decrypted by the filesystem.
+    #[unstable(feature = "linux_statx", issue = "none")]
+    pub fn is_encrypted(&self) -> Option<bool> {
+        self.get(Self::ENCRYPTED)
+    }
+
+    /// Returns whether the directory is an automount trigger.
+    #[unstable(feature = "linux_statx", issue = "none")]
+    pub fn is_automount(&self) -> Option<bool> {
+        self.get(Self::AUTOMOUNT)
+    }
+
+    /// Returns whether the file is the root of a mount.
+    #[unstable(feature = "linux_statx", issue = "none")]
+    pub fn is_mount_root(&self) -> Option<bool> {
+        self.get(Self::MOUNT_ROOT)
+    }
+
+    /// Returns whether the file has fs-verity enabled.
+    #[unstable(feature = "linux_statx", issue = "none")]
+    pub fn is_verity(&self) -> Option<bool> {
+        self.get(Self::VERITY)
+    }
+
+    /// Returns whether the file is in the DAX (CPU direct access) state.
+    #[unstable(feature = "linux_statx", issue = "none")]
+    pub fn is_dax(&self) -> Option<bool> {
+        self.get(Self::DAX)
+    }
+
+    /// Returns the raw `stx_attributes` field.
+    #[unstable(feature = "linux_statx", issue = "none")]
+    pub fn bits(&self) -> u64 {
+        self.attributes
+    }
+
+    /// Returns the raw `stx_attributes_mask` field, the attributes supported
+    /// by the filesystem.
+    #[unstable(feature = "linux_statx", issue = "none")]
+    pub fn supported_bits(&self) -> u64 {
+        self.supported
+    }
+}
+
+/// The set of fields to request from `statx` in [`metadata_with`].
+///
+/// Masks are combined with `|`. The fields in [`StatxMask::BASIC_STATS`] are
+/// always requested, as the accessors of [`Metadata`] rely on them. The kernel
+/// may return more fields than were requested if they come at no extra cost.
+#[unstable(feature = "linux_statx", issue = "none")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StatxMask(u32);
+
+impl StatxMask {
+    /// Everything `stat` returns. This is part of every mask.
+    #[unstable(feature = "linux_statx", issue = "none")]
+    pub const BASIC_STATS: StatxMask = StatxMask(0x0000_07ff);
+    /// The creation time, needed by [`Metadata::created`].
+    #[unstable(feature = "linux_statx", issue = "none")]
+    pub const BTIME: StatxMask = StatxMask(0x0000_0800);
+    /// The mount id, see [`MetadataExt::mount_id`].
+    #[unstable(feature = "linux_statx", issue = "none")]
+    pub const MNT_ID: StatxMask = StatxMask(0x0000_1000);
+    /// The direct I/O alignment, see [`MetadataExt::dio_mem_align`].
+    #[unstable(feature = "linux_statx", issue = "none")]
+    pub const DIOALIGN: StatxMask = StatxMask(0x0000_2000);
+
+    /// Creates a mask from raw `STATX_*` bits.
+    #[unstable(feature = "linux_statx", issue = "none")]
+    pub const fn from_bits(bits: u32) -> StatxMask {
+        StatxMask(bits)
+    }
+
+    /// Returns the raw `STATX_*` bits of this mask.
+    #[unstable(feature = "linux_statx", issue = "none")]
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+}
+
+#[unstable(feature = "linux_statx", issue = "none")]
+impl BitOr for StatxMask {
+    type Output = StatxMask;
+
+    fn bitor(self, rhs: StatxMask) -> StatxMask {
+        StatxMask(self.0 | rhs.0)
+    }
+}
+
+/// Queries the metadata of a file with the given extra fields, following
+/// symlinks.
+///
+/// This is like [`fs::metadata`], but lets callers choose which of the fields
+/// beyond those `stat` returns are requested: they can skip the ones they don't
+/// need, such as [`StatxMask::BTIME`], or ask for ones that are not fetched by
+/// default, such as [`StatxMask::DIOALIGN`]. The fields `stat` returns are
+/// always requested.
+///
+/// Without `statx` support in the kernel or libc this falls back to `stat`,
+/// and none of the `statx`-only fields are available. Fields that weren't
+/// requested or filled in are reported as missing by [`Metadata::created`] and
+/// the methods of [`MetadataExt`] that return an `Option`.
+///
+/// [`fs::metadata`]: crate::fs::metadata
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(linux_statx)]
+/// use std::io;
+/// use std::os::linux::fs::{metadata_with, MetadataExt, StatxMask};
+///
+/// fn main() -> io::Result<()> {
+///     let meta = metadata_with("some_file", StatxMask::DIOALIGN)?;
+///     println!("{:?}", meta.dio_mem_align());
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "linux_statx", issue = "none")]
+pub fn metadata_with<P: AsRef<Path>>(path: P, mask: StatxMask) -> io::Result<Metadata> {
+    let mask = mask | StatxMask::BASIC_STATS;
+    sys::fs::stat_with_mask(path.as_ref(), mask.bits(), true).map(Metadata::from_inner)
+}
+
+/// Like [`metadata_with`], but does not follow symlinks.
+#[unstable(feature = "linux_statx", issue = "none")]
+pub fn symlink_metadata_with<P: AsRef<Path>>(path: P, mask: StatxMask) -> io::Result<Metadata> {
+    let mask = mask | StatxMask::BASIC_STATS;
+    sys::fs::stat_with_mask(path.as_ref(), mask.bits(), false).map(Metadata::from_inner)
 }
@@ -101,7 +101,9 @@
 ))]
 use libc::{dirent64, fstat64, ftruncate64, lseek64, lstat64, off64_t, open64, stat64};
 
-pub use crate::sys_common::fs::try_exists;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub use crate::sys_common::fs::copy_with;
+pub use crate::sys_common::fs::{try_exists, CopyOptions};
 
 pub struct File(FileDesc);
 
@@ -139,6 +141,11 @@ struct StatxExtraFields {
         // This is needed to check if btime is supported by the filesystem.
         stx_mask: u32,
         stx_btime: libc::statx_timestamp,
+        stx_attributes: u64,
+        stx_attributes_mask: u64,
+        stx_mnt_id: u64,
+        stx_dio_mem_align: u32,
+        stx_dio_offset_align: u32,
         // With statx, we can overcome 32-bit `time_t` too.
         #[cfg(target_pointer_width = "32")]
         stx_atime: libc::statx_timestamp,
@@ -149,6 +156,10 @@ struct StatxExtraFields {
 
     }
 
+    // The fields requested by `stat`, `lstat` and friends. The mount id is
+    // always available without extra work, unlike e.g. `STATX_DIOALIGN`.
+    const STATX_DEFAULT_MASK: u32 = libc::STATX_ALL | libc::STATX_MNT_ID;
+
     // We prefer `statx` on Linux if available, which contains file creation time,
     // as well as 64-bit timestamps of all kinds.
     // Default `stat64` contains no creation time and may have 32-bit `time_t`.
@@ -234,6 +245,11 @@ fn statx(
         let extra = StatxExtraFields {
             stx_mask: buf.stx_mask,
             stx_btime: buf.stx_btime,
+            stx_attributes: buf.stx_attributes,
+            stx_attributes_mask: buf.stx_attributes_mask,
+            stx_mnt_id: buf.stx_mnt_id,
+            stx_dio_mem_align: buf.stx_dio_mem_align,
+            stx_dio_offset_align: buf.stx_dio_offset_align,
             // Store full times to avoid 32-bit `time_t` truncation.
             #[cfg(target_pointer_width = "32")]
             stx_atime: buf.stx_atime,
@@ -449,6 +465,52 @@ pub fn file_type(&self) -> FileType {
     }
 }
 
+// Fields only `statx` can provide, surfaced by `std::os::linux::fs::MetadataExt`.
+#[cfg(target_os = "linux")]
+cfg_has_statx! {{
+    impl FileAttr {
+        /// Returns `field` of the `statx` result if all of `mask` was filled in.
+        fn statx_field<T>(
+            &self,
+            mask: u32,
+            field: impl FnOnce(&StatxExtraFields) -> T,
+        ) -> Option<T> {
+            let ext = self.statx_extra_fields.as_ref()?;
+            if ext.stx_mask & mask == mask { Some(field(ext)) } else { None }
+        }
+
+        pub fn mount_id(&self) -> Option<u64> {
+            self.statx_field(libc::STATX_MNT_ID, |ext| ext.stx_mnt_id)
+        }
+
+        /// Returns the `STATX_ATTR_*` flags that are set, and those the filesystem supports.
+        pub fn attributes(&self) -> Option<(u64, u64)> {
+            self.statx_field(0, |ext| (ext.stx_attributes, ext.stx_attributes_mask))
+        }
+
+        /// Returns the memory and file offset alignment required for direct I/O.
+        pub fn dio_align(&self) -> Option<(u32, u32)> {
+            self.statx_field(libc::STATX_DIOALIGN, |ext| {
+                (ext.stx_dio_mem_align, ext.stx_dio_offset_align)
+            })
+        }
+    }
+} else {
+    impl FileAttr {
+        pub fn mount_id(&self) -> Option<u64> {
+            None
+        }
+
+        pub fn attributes(&self) -> Option<(u64, u64)> {
+            None
+        }
+
+        pub fn dio_align(&self) -> Option<(u32, u32)> {
+            None
+        }
+    }
+}}
+
 #[cfg(target_os = "netbsd")]
 impl FileAttr {
     pub fn modified(&self) -> io::Result<SystemTime> {
@@ -902,7 +964,7 @@ pub fn metadata(&self) -> io::Result<FileAttr> {
                 fd,
                 name,
                 libc::AT_SYMLINK_NOFOLLOW | libc::AT_STATX_SYNC_AS_STAT,
-                libc::STATX_ALL,
+                STATX_DEFAULT_MASK,
             ) } {
                 return ret;
             }
@@ -1178,7 +1240,7 @@ pub fn file_attr(&self) -> io::Result<FileAttr> {
                 fd,
                 c"".as_ptr() as *const c_char,
                 libc::AT_EMPTY_PATH | libc::AT_STATX_SYNC_AS_STAT,
-                libc::STATX_ALL,
+                STATX_DEFAULT_MASK,
             ) } {
                 return ret;
             }
@@ -1406,6 +1468,93 @@ pub fn set_times(&self, times: FileTimes) -> io::Result<()> {
     }
 }
 
+// `flock` locks belong to the open file description, so they are shared by
+// duplicated descriptors and only released once all of them are closed.
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_vendor = "apple",
+))]
+impl File {
+    pub fn lock(&self) -> io::Result<()> {
+        self.flock(libc::LOCK_EX).map(drop)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.flock(libc::LOCK_SH).map(drop)
+    }
+
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.flock(libc::LOCK_EX | libc::LOCK_NB)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.flock(libc::LOCK_SH | libc::LOCK_NB)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        self.flock(libc::LOCK_UN).map(drop)
+    }
+
+    /// Returns `Ok(false)` if a `LOCK_NB` request would have blocked.
+    fn flock(&self, operation: c_int) -> io::Result<bool> {
+        match cvt_r(|| unsafe { libc::flock(self.as_raw_fd(), operation) }) {
+            Ok(_) => Ok(true),
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_vendor = "apple",
+)))]
+impl File {
+    pub fn lock(&self) -> io::Result<()> {
+        Err(FLOCK_UNSUPPORTED)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        Err(FLOCK_UNSUPPORTED)
+    }
+
+    pub fn try_lock(&self) -> io::Result<bool> {
+        Err(FLOCK_UNSUPPORTED)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        Err(FLOCK_UNSUPPORTED)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        Err(FLOCK_UNSUPPORTED)
+    }
+}
+
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_vendor = "apple",
+)))]
+const FLOCK_UNSUPPORTED: io::Error = io::const_io_error!(
+    io::ErrorKind::Unsupported,
+    "file locking is not supported on this platform",
+);
+
 impl DirBuilder {
     pub fn new() -> DirBuilder {
         DirBuilder { mode: 0o777 }
@@ -1784,7 +1933,7 @@ pub fn stat(p: &Path) -> io::Result<FileAttr> {
                 libc::AT_FDCWD,
                 p.as_ptr(),
                 libc::AT_STATX_SYNC_AS_STAT,
-                libc::STATX_ALL,
+                STATX_DEFAULT_MASK,
             ) } {
                 return ret;
             }
@@ -1796,6 +1945,36 @@ pub fn stat(p: &Path) -> io::Result<FileAttr> {
     })
 }
 
+/// Like `stat`, but only asks `statx` for the fields in `mask`.
+///
+/// Without `statx` this is the same as `stat`, and none of the extra fields
+/// are available.
+#[cfg(target_os = "linux")]
+#[cfg_attr(not(target_env = "gnu"), allow(unused_variables))]
+pub fn stat_with_mask(p: &Path, mask: u32, follow_symlinks: bool) -> io::Result<FileAttr> {
+    run_path_with_cstr(p, &|p| {
+        cfg_has_statx! {
+            let flags = if follow_symlinks { 0 } else { libc::AT_SYMLINK_NOFOLLOW };
+            if let Some(ret) = unsafe { try_statx(
+                libc::AT_FDCWD,
+                p.as_ptr(),
+                flags | libc::AT_STATX_SYNC_AS_STAT,
+                mask,
+            ) } {
+                return ret;
+            }
+        }
+
+        let mut stat: stat64 = unsafe { mem::zeroed() };
+        if follow_symlinks {
+            cvt(unsafe { stat64(p.as_ptr(), &mut stat) })?;
+        } else {
+            cvt(unsafe { lstat64(p.as_ptr(), &mut stat) })?;
+        }
+        Ok(FileAttr::from_stat64(stat))
+    })
+}
+
 pub fn lstat(p: &Path) -> io::Result<FileAttr> {
     run_path_with_cstr(p, &|p| {
         cfg_has_statx! {
@@ -1803,7 +1982,7 @@ pub fn lstat(p: &Path) -> io::Result<FileAttr> {
                 libc::AT_FDCWD,
                 p.as_ptr(),
                 libc::AT_SYMLINK_NOFOLLOW | libc::AT_STATX_SYNC_AS_STAT,
-                libc::STATX_ALL,
+                STATX_DEFAULT_MASK,
             ) } {
                 return ret;
             }
@@ -1906,6 +2085,186 @@ pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
     }
 }
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn copy_with(from: &Path, to: &Path, options: &CopyOptions) -> io::Result<u64> {
+    use crate::fs::Reflink;
+    use crate::sys_common::fs::copy_times;
+
+    let (reader, reader_metadata) = open_from(from)?;
+    let len = reader_metadata.len();
+    let (writer, writer_metadata) = open_to_and_set_permissions(to, reader_metadata.clone())?;
+
+    let cloned = match options.reflink {
+        Reflink::Never => false,
+        Reflink::Auto => clone_file(&reader, &writer).is_ok(),
+        Reflink::Always => clone_file(&reader, &writer).map(|()| true)?,
+    };
+    let copied = if cloned {
+        len
+    } else if options.sparse && writer_metadata.is_file() {
+        // Other destinations, like pipes and devices, can neither seek nor have holes.
+        copy_sparse(&reader, &writer, len)?
+    } else {
+        kernel_copy(&reader, &writer, u64::MAX)?
+    };
+
+    if options.preserve_metadata {
+        copy_xattrs(&reader, &writer)?;
+        copy_times(&reader_metadata, &writer)?;
+    }
+    Ok(copied)
+}
+
+/// Shares the extents of `reader` with `writer` through the `FICLONE` ioctl,
+/// as supported by e.g. Btrfs, XFS and bcachefs.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn clone_file(reader: &crate::fs::File, writer: &crate::fs::File) -> io::Result<()> {
+    // `_IOW(0x94, 9, c_int)`, whose direction bits differ between architectures.
+    #[cfg(any(
+        target_arch = "mips",
+        target_arch = "mips32r6",
+        target_arch = "mips64",
+        target_arch = "mips64r6",
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "sparc",
+        target_arch = "sparc64",
+    ))]
+    const FICLONE: u32 = 0x8004_9409;
+    #[cfg(not(any(
+        target_arch = "mips",
+        target_arch = "mips32r6",
+        target_arch = "mips64",
+        target_arch = "mips64r6",
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "sparc",
+        target_arch = "sparc64",
+    )))]
+    const FICLONE: u32 = 0x4004_9409;
+
+    cvt(unsafe { libc::ioctl(writer.as_raw_fd(), FICLONE as _, reader.as_raw_fd()) })?;
+    Ok(())
+}
+
+/// Copies up to `max_len` bytes from the current offset of `reader` to the
+/// current offset of `writer`, using `copy_file_range` where possible.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn kernel_copy(
+    reader: &crate::fs::File,
+    writer: &crate::fs::File,
+    max_len: u64,
+) -> io::Result<u64> {
+    use super::kernel_copy::{copy_regular_files, CopyResult};
+    use crate::io::Read;
+
+    match copy_regular_files(reader.as_raw_fd(), writer.as_raw_fd(), max_len) {
+        CopyResult::Ended(bytes) => Ok(bytes),
+        CopyResult::Error(e, _) => Err(e),
+        CopyResult::Fallback(written) => {
+            let mut reader = reader.take(max_len - written);
+            let mut writer = writer;
+            io::copy::generic_copy(&mut reader, &mut writer).map(|bytes| bytes + written)
+        }
+    }
+}
+
+/// Copies only the data regions of `reader`, leaving holes in `writer` where
+/// `reader` has them.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn copy_sparse(reader: &crate::fs::File, writer: &crate::fs::File, len: u64) -> io::Result<u64> {
+    let (reader_fd, writer_fd) = (reader.as_raw_fd(), writer.as_raw_fd());
+    let seek = |fd, offset: u64, whence| {
+        cvt(unsafe { lseek64(fd, offset as off64_t, whence) }).map(|offset| offset as u64)
+    };
+
+    let mut pos = 0;
+    while pos < len {
+        let data = match seek(reader_fd, pos, libc::SEEK_DATA) {
+            Ok(data) => data,
+            // There is no more data after `pos`, only a trailing hole.
+            Err(e) if e.raw_os_error() == Some(libc::ENXIO) => break,
+            // The filesystem cannot report holes, so copy everything.
+            Err(e) if pos == 0 && e.raw_os_error() == Some(libc::EINVAL) => {
+                return kernel_copy(reader, writer, u64::MAX);
+            }
+            Err(e) => return Err(e),
+        };
+        let hole = seek(reader_fd, data, libc::SEEK_HOLE)?;
+        seek(reader_fd, data, libc::SEEK_SET)?;
+        seek(writer_fd, data, libc::SEEK_SET)?;
+        kernel_copy(reader, writer, hole - data)?;
+        pos = hole;
+    }
+    // Extend the destination over a trailing hole.
+    writer.set_len(len)?;
+    Ok(len)
+}
+
+/// Copies the extended attributes of `reader` to `writer`.
+///
+/// Attributes the destination refuses, such as those in privileged namespaces
+/// or on filesystems without extended attributes, are skipped.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn copy_xattrs(reader: &crate::fs::File, writer: &crate::fs::File) -> io::Result<()> {
+    // Calls `f` first to learn the size of the value, then to read it.
+    fn read_xattr(f: impl Fn(*mut libc::c_void, usize) -> isize) -> io::Result<Vec<u8>> {
+        let mut buf = Vec::<u8>::new();
+        loop {
+            let size = cvt(f(ptr::null_mut(), 0))? as usize;
+            if size == 0 {
+                // A read into an empty buffer would be another size query, so don't make one.
+                return Ok(buf);
+            }
+            buf.reserve(size);
+            match cvt(f(buf.as_mut_ptr().cast(), buf.capacity())) {
+                Ok(len) if len as usize <= buf.capacity() => {
+                    // SAFETY: the call wrote `len` bytes to the start of `buf`, which are
+                    // within its capacity.
+                    unsafe { buf.set_len(len as usize) };
+                    return Ok(buf);
+                }
+                // The value grew in between the two calls.
+                Ok(_) => continue,
+                Err(e) if e.raw_os_error() == Some(libc::ERANGE) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    let (reader_fd, writer_fd) = (reader.as_raw_fd(), writer.as_raw_fd());
+    let names =
+        match read_xattr(|buf, size| unsafe { libc::flistxattr(reader_fd, buf.cast(), size) }) {
+            Ok(names) => names,
+            Err(e) if e.raw_os_error() == Some(libc::ENOTSUP) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+    let mut rest = &names[..];
+    while let Ok(name) = CStr::from_bytes_until_nul(rest) {
+        rest = &rest[name.to_bytes_with_nul().len()..];
+        let value = match read_xattr(|buf, size| unsafe {
+            libc::fgetxattr(reader_fd, name.as_ptr(), buf, size)
+        }) {
+            Ok(value) => value,
+            Err(e) => match e.raw_os_error() {
+                // The attribute was removed since it was listed, or this user can't read it.
+                Some(libc::ENODATA | libc::EACCES | libc::EPERM) => continue,
+                _ => return Err(e),
+            },
+        };
+        let set = cvt(unsafe {
+            libc::fsetxattr(writer_fd, name.as_ptr(), value.as_ptr().cast(), value.len(), 0)
+        });
+        match set {
+            Ok(_) => {}
+            Err(e) if matches!(e.raw_os_error(), Some(libc::EPERM | libc::ENOTSUP)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
 #[cfg(target_vendor = "apple")]
 pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
     use crate::sync::atomic::{AtomicBool, Ordering};
@@ -1987,6 +2346,142 @@ fn fclonefileat(
     Ok(bytes_copied as u64)
 }
 
+/// A file that atomically replaces its target once persisted.
+///
+/// On Linux the contents are first written to an anonymous `O_TMPFILE` inode,
+/// so a crash before `persist` never leaves a stray temporary file behind. The
+/// inode is only given a name right before it is renamed over the target.
+pub struct PersistingFile {
+    file: crate::fs::File,
+    dir: PathBuf,
+    target: PathBuf,
+    // `None` while the file is still anonymous.
+    temp: Option<PathBuf>,
+}
+
+impl PersistingFile {
+    pub fn create(target: &Path) -> io::Result<PersistingFile> {
+        use crate::sys_common::fs::{create_temp_in, persist_target};
+
+        let (dir, name) = persist_target(target)?;
+        let target = target.to_path_buf();
+        #[cfg(all(target_os = "linux", not(miri)))]
+        if let Some(file) = open_anonymous_in(&dir)? {
+            return Ok(PersistingFile { file, dir, target, temp: None });
+        }
+        let (file, temp) = create_temp_in(&dir, name)?;
+        Ok(PersistingFile { file, dir, target, temp: Some(temp) })
+    }
+
+    pub fn file(&self) -> &crate::fs::File {
+        &self.file
+    }
+
+    pub fn target(&self) -> &Path {
+        &self.target
+    }
+
+    pub fn persist(mut self) -> io::Result<()> {
+        self.file.sync_data()?;
+        let temp = match self.temp.take() {
+            Some(temp) => temp,
+            None => self.link_anonymous()?,
+        };
+        if let Err(e) = rename(&temp, &self.target) {
+            let _ = unlink(&temp);
+            return Err(e);
+        }
+        // Make the rename itself durable.
+        let mut opts = OpenOptions::new();
+        opts.read(true);
+        File::open(&self.dir, &opts)?.fsync()
+    }
+
+    /// Gives the anonymous inode a temporary name in the target directory.
+    ///
+    /// `linkat` needs `/proc` to refer to the inode by descriptor; if that is
+    /// unavailable the contents are copied into a named temporary file instead.
+    #[cfg(all(target_os = "linux", not(miri)))]
+    fn link_anonymous(&self) -> io::Result<PathBuf> {
+        use crate::io::Seek;
+        use crate::sys_common::fs::{create_temp_in, persist_target, with_temp_path};
+
+        let (_, name) = persist_target(&self.target)?;
+        let fd_path = format!("/proc/self/fd/{}\0", self.file.as_raw_fd());
+        let fd_path = CStr::from_bytes_with_nul(fd_path.as_bytes()).unwrap();
+        let linked = with_temp_path(&self.dir, name, |temp| {
+            run_path_with_cstr(temp, &|temp| {
+                cvt(unsafe {
+                    libc::linkat(
+                        libc::AT_FDCWD,
+                        fd_path.as_ptr(),
+                        libc::AT_FDCWD,
+                        temp.as_ptr(),
+                        libc::AT_SYMLINK_FOLLOW,
+                    )
+                })
+            })
+        });
+        match linked {
+            Ok((_, temp)) => Ok(temp),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Err(e),
+            Err(_) => {
+                let (mut copy, temp) = create_temp_in(&self.dir, name)?;
+                let mut file = &self.file;
+                let mut copy_contents = || {
+                    file.seek(SeekFrom::Start(0))?;
+                    io::copy(&mut file, &mut copy)?;
+                    copy.sync_data()
+                };
+                match copy_contents() {
+                    Ok(()) => Ok(temp),
+                    Err(e) => {
+                        let _ = unlink(&temp);
+                        Err(e)
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(not(all(target_os = "linux", not(miri))))]
+    fn link_anonymous(&self) -> io::Result<PathBuf> {
+        unreachable!("anonymous temporary files are only created on Linux")
+    }
+}
+
+impl Drop for PersistingFile {
+    fn drop(&mut self) {
+        if let Some(temp) = self.temp.take() {
+            let _ = unlink(&temp);
+        }
+    }
+}
+
+/// Opens an unnamed regular file in `dir`, or returns `None` if the kernel or
+/// the filesystem does not support `O_TMPFILE`.
+#[cfg(all(target_os = "linux", not(miri)))]
+fn open_anonymous_in(dir: &Path) -> io::Result<Option<crate::fs::File>> {
+    let mut opts = OpenOptions::new();
+    opts.read(true);
+    opts.write(true);
+    opts.custom_flags(libc::O_TMPFILE);
+    opts.mode(0o666);
+    match File::open(dir, &opts) {
+        Ok(file) => Ok(Some(crate::fs::File::from_inner(file))),
+        // Kernels older than 3.11 see `O_DIRECTORY` and fail with `EISDIR`.
+        Err(e)
+            if matches!(
+                e.raw_os_error(),
+                Some(libc::EOPNOTSUPP | libc::EISDIR | libc::EINVAL)
+            ) =>
+        {
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
 pub fn chown(path: &Path, uid: u32, gid: u32) -> io::Result<()> {
     run_path_with_cstr(path, &|path| {
         cvt(unsafe { libc::chown(path.as_ptr(), uid as libc::uid_t, gid as libc::gid_t) })
@@ -2011,7 +2506,7 @@ pub fn chroot(dir: &Path) -> io::Result<()> {
     run_path_with_cstr(dir, &|dir| cvt(unsafe { libc::chroot(dir.as_ptr()) }).map(|_| ()))
 }
 
-pub use remove_dir_impl::remove_dir_all;
+pub use remove_dir_impl::{readdir_entry, remove_dir_all};
 
 // Fallback for REDOX, ESP-ID, Horizon, Vita and Miri
 #[cfg(any(
@@ -2023,7 +2518,7 @@ pub fn chroot(dir: &Path) -> io::Result<()> {
     miri
 ))]
 mod remove_dir_impl {
-    pub use crate::sys_common::fs::remove_dir_all;
+    pub use crate::sys_common::fs::{readdir_entry, remove_dir_all};
 }
 
 // Modern implementation using openat(), unlinkat() and fdopendir()
@@ -2107,7 +2602,7 @@ pub fn openat_nofollow_dironly(parent_fd: Option<RawFd>, p: &CStr) -> io::Result
         Ok(unsafe { OwnedFd::from_raw_fd(fd) })
     }
 
-    fn fdreaddir(dir_fd: OwnedFd) -> io::Result<(ReadDir, RawFd)> {
+    fn fdreaddir(dir_fd: OwnedFd, root: PathBuf) -> io::Result<(ReadDir, RawFd)> {
         let ptr = unsafe { fdopendir(dir_fd.as_raw_fd()) };
         if ptr.is_null() {
             return Err(io::Error::last_os_error());
@@ -2115,10 +2610,7 @@ fn fdreaddir(dir_fd: OwnedFd) -> io::Result<(ReadDir, RawFd)> {
         let dirp = Dir(ptr);
         // file descriptor is automatically closed by libc::closedir() now, so give up ownership
         let new_parent_fd = dir_fd.into_raw_fd();
-        // a valid root is not needed because we do not call any functions involving the full path
-        // of the `DirEntry`s.
-        let dummy_root = PathBuf::new();
-        let inner = InnerReadDir { dirp, root: dummy_root };
+        let inner = InnerReadDir { dirp, root };
         Ok((ReadDir::new(inner), new_parent_fd))
     }
 
@@ -2167,7 +2659,9 @@ fn remove_dir_all_recursive(parent_fd: Option<RawFd>, path: &CStr) -> io::Result
         };
 
         // open the directory passing ownership of the fd
-        let (dir, fd) = fdreaddir(fd)?;
+        // a valid root is not needed because we do not call any functions involving the full path
+        // of the `DirEntry`s.
+        let (dir, fd) = fdreaddir(fd, PathBuf::new())?;
         for child in dir {
             let child = child?;
             let child_name = child.name_cstr();
@@ -2207,11 +2701,67 @@ fn remove_dir_all_modern(p: &Path) -> io::Result<()> {
         }
     }
 
+    // Opens `entry` relative to the directory it was read from, so that walking a
+    // tree is not affected by concurrent renames of its ancestors, and does not
+    // follow a symlink that replaced a directory unless asked to.
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "solaris",
+        target_vendor = "apple",
+    ))]
+    fn readdir_entry_modern(entry: &DirEntry, follow_symlinks: bool) -> io::Result<ReadDir> {
+        let parent_fd = cvt(unsafe { libc::dirfd(entry.dir.dirp.0) })?;
+        let mut flags = libc::O_CLOEXEC | libc::O_RDONLY | libc::O_DIRECTORY;
+        if !follow_symlinks {
+            flags |= libc::O_NOFOLLOW;
+        }
+        let fd = cvt_r(|| unsafe { openat(parent_fd, entry.name_cstr().as_ptr(), flags) })?;
+        let (dir, _) = fdreaddir(unsafe { OwnedFd::from_raw_fd(fd) }, entry.path())?;
+        Ok(dir)
+    }
+
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "fuchsia",
+        target_os = "illumos",
+        target_os = "linux",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "solaris",
+        target_vendor = "apple",
+    )))]
+    fn readdir_entry_modern(entry: &DirEntry, follow_symlinks: bool) -> io::Result<ReadDir> {
+        crate::sys_common::fs::readdir_entry(entry, follow_symlinks)
+    }
+
     #[cfg(not(all(target_os = "macos", not(target_arch = "aarch64"))))]
     pub fn remove_dir_all(p: &Path) -> io::Result<()> {
         remove_dir_all_modern(p)
     }
 
+    #[cfg(not(all(target_os = "macos", not(target_arch = "aarch64"))))]
+    pub fn readdir_entry(entry: &DirEntry, follow_symlinks: bool) -> io::Result<ReadDir> {
+        readdir_entry_modern(entry, follow_symlinks)
+    }
+
+    #[cfg(all(target_os = "macos", not(target_arch = "aarch64")))]
+    pub fn readdir_entry(entry: &DirEntry, follow_symlinks: bool) -> io::Result<ReadDir> {
+        if macos_weak::has_openat() {
+            readdir_entry_modern(entry, follow_symlinks)
+        } else {
+            crate::sys_common::fs::readdir_entry(entry, follow_symlinks)
+        }
+    }
+
     #[cfg(all(target_os = "macos", not(target_arch = "aarch64")))]
     pub fn remove_dir_all(p: &Path) -> io::Result<()> {
         if macos_weak::has_openat() {
@@ -21,6 +21,8 @@
 use super::{api, to_u16s, IoResult};
 use crate::sys::path::maybe_verbatim;
 
+pub use crate::sys_common::fs::{copy_with, readdir_entry, CopyOptions, PersistingFile};
+
 pub struct File {
     handle: Handle,
 }
@@ -349,6 +351,66 @@ pub fn datasync(&self) -> io::Result<()> {
         self.fsync()
     }
 
+    fn acquire_lock(&self, flags: c::LOCK_FILE_FLAGS) -> io::Result<()> {
+        // Lock the whole file, regardless of its current or future size.
+        cvt(unsafe {
+            let mut overlapped = mem::zeroed();
+            c::LockFileEx(
+                self.handle.as_raw_handle(),
+                flags,
+                0,
+                u32::MAX,
+                u32::MAX,
+                &mut overlapped,
+            )
+        })?;
+        Ok(())
+    }
+
+    fn try_acquire_lock(&self, flags: c::LOCK_FILE_FLAGS) -> io::Result<bool> {
+        match self.acquire_lock(flags | c::LOCKFILE_FAIL_IMMEDIATELY) {
+            Ok(()) => Ok(true),
+            Err(err)
+                if err.raw_os_error() == Some(c::ERROR_IO_PENDING as i32)
+                    || err.raw_os_error() == Some(c::ERROR_LOCK_VIOLATION as i32) =>
+            {
+                Ok(false)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn lock(&self) -> io::Result<()> {
+        self.acquire_lock(c::LOCKFILE_EXCLUSIVE_LOCK)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.acquire_lock(0)
+    }
+
+    pub fn try_lock(&self) -> io::Result<bool> {
+        self.try_acquire_lock(c::LOCKFILE_EXCLUSIVE_LOCK)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.try_acquire_lock(0)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        // A handle may hold both an exclusive and a shared lock on the same
+        // range, in which case two unlock operations are needed to release it.
+        let handle = self.handle.as_raw_handle();
+        for _ in 0..2 {
+            match cvt(unsafe { c::UnlockFile(handle, 0, 0, u32::MAX, u32::MAX) }) {
+                Ok(_) => {}
+                // Nothing (more) is locked, which isn't an error.
+                Err(err) if err.raw_os_error() == Some(c::ERROR_NOT_LOCKED as i32) => return Ok(()),
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
     pub fn truncate(&self, size: u64) -> io::Result<()> {
         let info = c::FILE_END_OF_FILE_INFO { EndOfFile: size as i64 };
         api::set_file_information_by_handle(self.handle.as_raw_handle(), &info).io_result()
@@ -1,8 +1,10 @@
 #![allow(dead_code)] // not used on all platforms
 
+use crate::ffi::{OsStr, OsString};
 use crate::fs;
+use crate::hash::{BuildHasher, RandomState};
 use crate::io::{self, Error, ErrorKind};
-use crate::path::Path;
+use crate::path::{Path, PathBuf};
 
 pub(crate) const NOT_FILE_ERROR: Error = io::const_io_error!(
     ErrorKind::InvalidInput,
@@ -25,6 +27,67 @@ pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
     Ok(ret)
 }
 
+pub(crate) const REFLINK_UNSUPPORTED_ERROR: Error = io::const_io_error!(
+    ErrorKind::Unsupported,
+    "copy-on-write cloning of files is not supported on this platform",
+);
+
+/// The options of `fs::CopyOptions`, shared by all platforms.
+#[derive(Clone, Debug)]
+pub struct CopyOptions {
+    pub reflink: fs::Reflink,
+    pub preserve_metadata: bool,
+    pub sparse: bool,
+}
+
+impl CopyOptions {
+    pub fn new() -> CopyOptions {
+        CopyOptions { reflink: fs::Reflink::Auto, preserve_metadata: false, sparse: false }
+    }
+
+    pub fn reflink(&mut self, reflink: fs::Reflink) {
+        self.reflink = reflink;
+    }
+
+    pub fn preserve_metadata(&mut self, preserve_metadata: bool) {
+        self.preserve_metadata = preserve_metadata;
+    }
+
+    pub fn sparse(&mut self, sparse: bool) {
+        self.sparse = sparse;
+    }
+}
+
+/// Copies a file on platforms without a way to clone files or detect holes.
+///
+/// `Reflink::Auto` defers to the platform's `copy`, which may still clone
+/// the file on its own, while `Reflink::Never` always copies the data.
+pub fn copy_with(from: &Path, to: &Path, options: &CopyOptions) -> io::Result<u64> {
+    let copied = match options.reflink {
+        fs::Reflink::Always => return Err(REFLINK_UNSUPPORTED_ERROR),
+        fs::Reflink::Auto => crate::sys::fs::copy(from, to)?,
+        fs::Reflink::Never => copy(from, to)?,
+    };
+    if options.preserve_metadata {
+        let metadata = fs::metadata(from)?;
+        copy_times(&metadata, &fs::File::options().write(true).open(to)?)?;
+    }
+    Ok(copied)
+}
+
+/// Sets the access and modification times of `to` to those in `metadata`,
+/// skipping any the platform does not report.
+pub fn copy_times(metadata: &fs::Metadata, to: &fs::File) -> io::Result<()> {
+    let mut times = fs::FileTimes::new();
+    if let Ok(accessed) = metadata.accessed() {
+        times = times.set_accessed(accessed);
+    }
+    if let Ok(modified) = metadata.modified() {
+        times = times.set_modified(modified);
+    }
+    to.set_times(times)
+}
+
 pub fn remove_dir_all(path: &Path) -> io::Result<()> {
     let filetype = fs::symlink_metadata(path)?.file_type();
     if filetype.is_symlink() { fs::remove_file(path) } else { remove_dir_all_recursive(path) }
@@ -42,6 +105,18 @@ fn remove_dir_all_recursive(path: &Path) -> io::Result<()> {
     fs::remove_dir(path)
 }
 
+/// Opens the directory `entry` refers to, for walking a directory tree.
+///
+/// This generic version goes through the path of the entry, so it neither
+/// protects against the entry being replaced by a symlink in the meantime nor
+/// supports paths beyond the platform's maximum path length.
+pub fn readdir_entry(
+    entry: &crate::sys::fs::DirEntry,
+    _follow_symlinks: bool,
+) -> io::Result<crate::sys::fs::ReadDir> {
+    crate::sys::fs::readdir(&entry.path())
+}
+
 pub fn try_exists(path: &Path) -> io::Result<bool> {
     match fs::metadata(path) {
         Ok(_) => Ok(true),
@@ -49,3 +124,101 @@ pub fn try_exists(path: &Path) -> io::Result<bool> {
         Err(error) => Err(error),
     }
 }
+
+/// How many randomly named temporary files are tried before giving up.
+const TEMP_ATTEMPTS: u32 = 1 << 16;
+
+/// Splits the target of an atomic write into the directory the temporary file
+/// must live in and the file name the temporary name is derived from.
+pub fn persist_target(target: &Path) -> io::Result<(PathBuf, &OsStr)> {
+    let Some(name) = target.file_name() else {
+        return Err(io::const_io_error!(
+            ErrorKind::InvalidInput,
+            "the path to persist a file to must name a file",
+        ));
+    };
+    let dir = match target.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    Ok((dir, name))
+}
+
+/// Calls `f` with candidate temporary paths next to `name` in `dir` until it
+/// succeeds or fails with something other than `AlreadyExists`.
+pub fn with_temp_path<T>(
+    dir: &Path,
+    name: &OsStr,
+    mut f: impl FnMut(&Path) -> io::Result<T>,
+) -> io::Result<(T, PathBuf)> {
+    let state = RandomState::new();
+    for attempt in 0..TEMP_ATTEMPTS {
+        let mut temp_name = OsString::from(".");
+        temp_name.push(name);
+        temp_name.push(format!(".{:016x}.tmp", state.hash_one(attempt)));
+        let path = dir.join(temp_name);
+        match f(&path) {
+            Ok(t) => return Ok((t, path)),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::const_io_error!(ErrorKind::AlreadyExists, "too many temporary files exist"))
+}
+
+/// Creates a new, uniquely named file next to `name` in `dir`.
+pub fn create_temp_in(dir: &Path, name: &OsStr) -> io::Result<(fs::File, PathBuf)> {
+    with_temp_path(dir, name, |path| {
+        fs::File::options().read(true).write(true).create_new(true).open(path)
+    })
+}
+
+/// A file that atomically replaces its target once persisted, implemented
+/// with a named temporary file and a rename.
+///
+/// Platforms that cannot sync directories only get atomicity, not durability
+/// of the rename itself.
+pub struct PersistingFile {
+    file: fs::File,
+    dir: PathBuf,
+    target: PathBuf,
+    temp: Option<PathBuf>,
+}
+
+impl PersistingFile {
+    pub fn create(target: &Path) -> io::Result<PersistingFile> {
+        let (dir, name) = persist_target(target)?;
+        let (file, temp) = create_temp_in(&dir, name)?;
+        Ok(PersistingFile { file, dir, target: target.to_path_buf(), temp: Some(temp) })
+    }
+
+    pub fn file(&self) -> &fs::File {
+        &self.file
+    }
+
+    pub fn target(&self) -> &Path {
+        &self.target
+    }
+
+    pub fn persist(mut self) -> io::Result<()> {
+        self.file.sync_data()?;
+        let temp = self.temp.take().unwrap();
+        if let Err(e) = fs::rename(&temp, &self.target) {
+            let _ = fs::remove_file(&temp);
+            return Err(e);
+        }
+        // Not every platform can open a directory as a file; this is best-effort.
+        if let Ok(dir) = fs::File::open(&self.dir) {
+            let _ = dir.sync_all();
+        }
+        Ok(())
+    }
+}
+
+impl Drop for PersistingFile {
+    fn drop(&mut self) {
+        if let Some(temp) = self.temp.take() {
+            let _ = fs::remove_file(temp);
+        }
+    }
+}
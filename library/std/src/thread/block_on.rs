@@ -0,0 +1,87 @@
+use super::{current, park, Thread};
+use crate::future::Future;
+use crate::pin::pin;
+use crate::sync::Arc;
+use crate::task::{Context, Poll, Wake, Waker};
+
+/// Runs a future to completion on the current thread, blocking it while the
+/// future is pending.
+///
+/// This is a minimal executor: the future is polled on the calling thread,
+/// which is [parked][park] whenever the future returns [`Poll::Pending`], and
+/// [unparked][Thread::unpark] when the future's [`Waker`] is woken. It does not
+/// drive any I/O or timers itself, so the future must be woken by some other
+/// thread or by itself, as with any executor.
+///
+/// Since the waker unparks the calling thread, the future should not park or
+/// unpark that thread itself; doing so may cause spurious polls, but not
+/// incorrect behavior.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(thread_block_on)]
+/// use std::thread;
+///
+/// let output = thread::block_on(async { 40 + 2 });
+/// assert_eq!(output, 42);
+/// ```
+///
+/// Waiting for a value produced on another thread:
+///
+/// ```
+/// #![feature(thread_block_on)]
+/// use std::future;
+/// use std::sync::{Arc, Mutex};
+/// use std::task::{Poll, Waker};
+/// use std::thread;
+///
+/// let shared: Arc<Mutex<(Option<u32>, Option<Waker>)>> = Arc::default();
+///
+/// let producer = Arc::clone(&shared);
+/// thread::spawn(move || {
+///     let mut state = producer.lock().unwrap();
+///     state.0 = Some(7);
+///     if let Some(waker) = state.1.take() {
+///         waker.wake();
+///     }
+/// });
+///
+/// let value = thread::block_on(future::poll_fn(|cx| {
+///     let mut state = shared.lock().unwrap();
+///     match state.0 {
+///         Some(value) => Poll::Ready(value),
+///         None => {
+///             state.1 = Some(cx.waker().clone());
+///             Poll::Pending
+///         }
+///     }
+/// }));
+/// assert_eq!(value, 7);
+/// ```
+#[unstable(feature = "thread_block_on", issue = "none")]
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+
+        fn wake_by_ref(self: &Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            // A wakeup that happened since the last poll makes `park` return
+            // immediately, so none can be missed.
+            Poll::Pending => park(),
+        }
+    }
+}
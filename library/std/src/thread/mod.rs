@@ -185,6 +185,11 @@
 #[stable(feature = "scoped_threads", since = "1.63.0")]
 pub use scoped::{scope, Scope, ScopedJoinHandle};
 
+mod block_on;
+
+#[unstable(feature = "thread_block_on", issue = "none")]
+pub use block_on::block_on;
+
 ////////////////////////////////////////////////////////////////////////////////
 // Thread-local storage
 ////////////////////////////////////////////////////////////////////////////////
@@ -295,6 +295,39 @@ fn test_park_unpark_called_other_thread() {
     }
 }
 
+#[test]
+fn test_block_on() {
+    use crate::future;
+    use crate::sync::Mutex;
+    use crate::task::{Poll, Waker};
+
+    assert_eq!(thread::block_on(async { 1 + 1 }), 2);
+
+    // The future is completed by another thread, which wakes it up.
+    for _ in 0..10 {
+        let shared: Arc<Mutex<(bool, Option<Waker>)>> = Arc::default();
+        let shared2 = shared.clone();
+        let _guard = thread::spawn(move || {
+            super::sleep(Duration::from_millis(10));
+            let mut state = shared2.lock().unwrap();
+            state.0 = true;
+            if let Some(waker) = state.1.take() {
+                waker.wake();
+            }
+        });
+
+        thread::block_on(future::poll_fn(|cx| {
+            let mut state = shared.lock().unwrap();
+            if state.0 {
+                Poll::Ready(())
+            } else {
+                state.1 = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }));
+    }
+}
+
 #[test]
 fn test_park_timeout_unpark_before() {
     for _ in 0..10 {
@@ -1,7 +1,7 @@
 //! Benchmarking module.
 use super::{
     event::CompletedTest,
-    options::BenchMode,
+    options::{BenchMode, Capture},
     test_result::TestResult,
     types::{TestDesc, TestId},
     Sender,
@@ -11,6 +11,7 @@
 use std::cmp;
 use std::io;
 use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
@@ -23,6 +24,20 @@ pub fn black_box<T>(dummy: T) -> T {
     std::hint::black_box(dummy)
 }
 
+/// Number of samples taken in each round of measurements of a benchmark.
+pub(crate) const BENCH_SAMPLES: usize = 50;
+
+/// Options for running benchmarks and comparing them against earlier runs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BenchOptions {
+    /// How long to run each benchmark before measuring it.
+    pub warm_up_time: Option<Duration>,
+    /// File to save the results of the benchmarks to, as a baseline for later runs.
+    pub save_baseline: Option<PathBuf>,
+    /// Baseline file from an earlier run to compare the results of the benchmarks against.
+    pub compare_baseline: Option<PathBuf>,
+}
+
 /// Manager of the benchmarking runs.
 ///
 /// This is fed into functions marked with `#[bench]` to allow for
@@ -32,6 +47,9 @@ pub fn black_box<T>(dummy: T) -> T {
 pub struct Bencher {
     mode: BenchMode,
     summary: Option<stats::Summary>,
+    raw_summary: Option<stats::Summary>,
+    outliers: stats::Outliers,
+    warm_up: Option<Duration>,
     pub bytes: u64,
 }
 
@@ -46,7 +64,10 @@ pub fn iter<T, F>(&mut self, mut inner: F)
             return;
         }
 
-        self.summary = Some(iter(&mut inner));
+        let (summary, raw_summary, outliers) = iter_with_warm_up(&mut inner, self.warm_up);
+        self.summary = Some(summary);
+        self.raw_summary = Some(raw_summary);
+        self.outliers = outliers;
     }
 
     pub fn bench<F>(&mut self, mut f: F) -> Result<Option<stats::Summary>, String>
@@ -61,7 +82,25 @@ pub fn bench<F>(&mut self, mut f: F) -> Result<Option<stats::Summary>, String>
 #[derive(Debug, Clone, PartialEq)]
 pub struct BenchSamples {
     pub ns_iter_summ: stats::Summary,
+    /// Summary of the samples before they were winsorized, which `--bench-save`
+    /// and `--bench-compare` use.
+    pub ns_iter_raw_summ: stats::Summary,
     pub mb_s: usize,
+    /// Number of bytes processed by each iteration, as set in `Bencher::bytes`.
+    pub bytes: u64,
+    /// Samples outside of the Tukey fences, counted before the samples were winsorized.
+    pub outliers: stats::Outliers,
+    /// Change from the baseline passed with `--bench-compare`, if it has this benchmark.
+    pub change: Option<BenchChange>,
+}
+
+/// Change of the mean time per iteration of a benchmark from a baseline, in
+/// percent, along with its 95% confidence interval.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchChange {
+    pub pct: f64,
+    pub ci_low: f64,
+    pub ci_high: f64,
 }
 
 pub fn fmt_bench_samples(bs: &BenchSamples) -> String {
@@ -81,6 +120,26 @@ pub fn fmt_bench_samples(bs: &BenchSamples) -> String {
     if bs.mb_s != 0 {
         write!(output, " = {} MB/s", bs.mb_s).unwrap();
     }
+    let outliers = bs.outliers.total();
+    if outliers != 0 {
+        write!(
+            output,
+            "; {} outlier{} ({} mild, {} severe)",
+            outliers,
+            if outliers == 1 { "" } else { "s" },
+            bs.outliers.mild(),
+            bs.outliers.severe()
+        )
+        .unwrap();
+    }
+    if let Some(change) = bs.change {
+        write!(
+            output,
+            "; change: {:+.2}% [{:+.2}%, {:+.2}%]",
+            change.pct, change.ci_low, change.ci_high
+        )
+        .unwrap();
+    }
     output
 }
 
@@ -122,12 +181,42 @@ fn ns_iter_inner<T, F>(inner: &mut F, k: u64) -> u64
     start.elapsed().as_nanos() as u64
 }
 
+/// Runs `inner` repeatedly for at least `warm_up`, and returns the average
+/// time of a single iteration in nanoseconds.
+fn warm_up_inner<T, F>(inner: &mut F, warm_up: Duration) -> u64
+where
+    F: FnMut() -> T,
+{
+    let start = Instant::now();
+    let mut iters = 0;
+    let mut k = 1;
+    while start.elapsed() < warm_up {
+        ns_iter_inner(inner, k);
+        iters += k;
+        k = k.saturating_mul(2);
+    }
+    start.elapsed().as_nanos() as u64 / cmp::max(1, iters)
+}
+
 pub fn iter<T, F>(inner: &mut F) -> stats::Summary
 where
     F: FnMut() -> T,
 {
-    // Initial bench run to get ballpark figure.
-    let ns_single = ns_iter_inner(inner, 1);
+    iter_with_warm_up(inner, None).0
+}
+
+fn iter_with_warm_up<T, F>(
+    inner: &mut F,
+    warm_up: Option<Duration>,
+) -> (stats::Summary, stats::Summary, stats::Outliers)
+where
+    F: FnMut() -> T,
+{
+    // Initial bench run to get ballpark figure, or a better one after warming up.
+    let ns_single = match warm_up {
+        Some(warm_up) => warm_up_inner(inner, warm_up),
+        None => ns_iter_inner(inner, 1),
+    };
 
     // Try to estimate iter count for 1ms falling back to 1m
     // iterations if first run took < 1ns.
@@ -142,7 +231,7 @@ pub fn iter<T, F>(inner: &mut F) -> stats::Summary
     n = cmp::max(1, n);
 
     let mut total_run = Duration::new(0, 0);
-    let samples: &mut [f64] = &mut [0.0_f64; 50];
+    let samples: &mut [f64] = &mut [0.0_f64; BENCH_SAMPLES];
     loop {
         let loop_start = Instant::now();
 
@@ -158,6 +247,8 @@ pub fn iter<T, F>(inner: &mut F) -> stats::Summary
             *p = ns as f64 / (5 * n) as f64;
         }
 
+        let outliers5 = stats::Outliers::new(samples);
+        let raw5 = stats::Summary::new(samples);
         stats::winsorize(samples, 5.0);
         let summ5 = stats::Summary::new(samples);
 
@@ -169,13 +260,13 @@ pub fn iter<T, F>(inner: &mut F) -> stats::Summary
             && summ.median_abs_dev_pct < 1.0
             && summ.median - summ5.median < summ5.median_abs_dev
         {
-            return summ5;
+            return (summ5, raw5, outliers5);
         }
 
         total_run += loop_run;
         // Longest we ever run for is 3s.
         if total_run > Duration::from_secs(3) {
-            return summ5;
+            return (summ5, raw5, outliers5);
         }
 
         // If we overflow here just return the results so far. We check a
@@ -185,7 +276,7 @@ pub fn iter<T, F>(inner: &mut F) -> stats::Summary
         n = match n.checked_mul(10) {
             Some(_) => n * 2,
             None => {
-                return summ5;
+                return (summ5, raw5, outliers5);
             }
         };
     }
@@ -196,17 +287,33 @@ pub fn benchmark<F>(
     desc: TestDesc,
     monitor_ch: Sender<CompletedTest>,
     nocapture: bool,
+    capture: Capture,
+    warm_up: Option<Duration>,
     f: F,
 ) where
     F: FnMut(&mut Bencher) -> Result<(), String>,
 {
-    let mut bs = Bencher { mode: BenchMode::Auto, summary: None, bytes: 0 };
+    let mut bs = Bencher {
+        mode: BenchMode::Auto,
+        summary: None,
+        raw_summary: None,
+        outliers: Default::default(),
+        warm_up,
+        bytes: 0,
+    };
 
     let data = Arc::new(Mutex::new(Vec::new()));
 
-    if !nocapture {
-        io::set_output_capture(Some(data.clone()));
-    }
+    let fd_capture = match crate::start_capture(nocapture, capture, &data) {
+        Ok(fd_capture) => fd_capture,
+        Err(e) => {
+            let msg = format!("failed to capture the output of the benchmark: {e}");
+            let result = TestResult::TrFailedMsg(msg);
+            let message = CompletedTest::new(id, desc, result, None, Vec::new());
+            monitor_ch.send(message).unwrap();
+            return;
+        }
+    };
 
     let result = catch_unwind(AssertUnwindSafe(|| bs.bench(f)));
 
@@ -218,21 +325,38 @@ pub fn benchmark<F>(
             let ns_iter = cmp::max(ns_iter_summ.median as u64, 1);
             let mb_s = bs.bytes * 1000 / ns_iter;
 
-            let bs = BenchSamples { ns_iter_summ, mb_s: mb_s as usize };
+            let bs = BenchSamples {
+                ns_iter_summ,
+                ns_iter_raw_summ: bs.raw_summary.unwrap_or(ns_iter_summ),
+                mb_s: mb_s as usize,
+                bytes: bs.bytes,
+                outliers: bs.outliers,
+                change: None,
+            };
             TestResult::TrBench(bs)
         }
         Ok(Ok(None)) => {
             // iter not called, so no data.
             // FIXME: error in this case?
             let samples: &mut [f64] = &mut [0.0_f64; 1];
-            let bs = BenchSamples { ns_iter_summ: stats::Summary::new(samples), mb_s: 0 };
+            let bs = BenchSamples {
+                ns_iter_summ: stats::Summary::new(samples),
+                ns_iter_raw_summ: stats::Summary::new(samples),
+                mb_s: 0,
+                bytes: 0,
+                outliers: Default::default(),
+                change: None,
+            };
             TestResult::TrBench(bs)
         }
         Err(_) => TestResult::TrFailed,
         Ok(Err(_)) => TestResult::TrFailed,
     };
 
-    let stdout = data.lock().unwrap().to_vec();
+    let stdout = match fd_capture {
+        Some(fd_capture) => fd_capture.finish(),
+        None => data.lock().unwrap().to_vec(),
+    };
     let message = CompletedTest::new(id, desc, test_result, None, stdout);
     monitor_ch.send(message).unwrap();
 }
@@ -241,6 +365,13 @@ pub fn run_once<F>(f: F) -> Result<(), String>
 where
     F: FnMut(&mut Bencher) -> Result<(), String>,
 {
-    let mut bs = Bencher { mode: BenchMode::Single, summary: None, bytes: 0 };
+    let mut bs = Bencher {
+        mode: BenchMode::Single,
+        summary: None,
+        raw_summary: None,
+        outliers: Default::default(),
+        warm_up: None,
+        bytes: 0,
+    };
     bs.bench(f).map(|_| ())
 }
@@ -2,8 +2,13 @@
 
 use std::env;
 use std::path::PathBuf;
+use std::time::Duration;
 
-use super::options::{ColorConfig, Options, OutputFormat, RunIgnored};
+use super::bench::BenchOptions;
+use super::options::{
+    Capture, ColorConfig, Isolation, Options, OutputFormat, Partition, PartitionStrategy,
+    RunIgnored, TestOrder,
+};
 use super::time::TestTimeOptions;
 use std::io::{self, IsTerminal};
 
@@ -19,13 +24,31 @@ pub struct TestOpts {
     pub bench_benchmarks: bool,
     pub logfile: Option<PathBuf>,
     pub nocapture: bool,
+    /// How the output of tests is captured, unless `nocapture` is set.
+    pub capture: Capture,
     pub color: ColorConfig,
     pub format: OutputFormat,
     pub shuffle: bool,
     pub shuffle_seed: Option<u64>,
     pub test_threads: Option<usize>,
     pub skip: Vec<String>,
+    /// Only run the tests in one shard of the filtered test suite.
+    pub partition: Option<Partition>,
     pub time_options: Option<TestTimeOptions>,
+    /// Hard limit on the execution time of each test, after which it is stopped
+    /// and reported as failed.
+    pub test_timeout: Option<Duration>,
+    /// How many times a failed test is run again before it's reported as failed.
+    pub retries: usize,
+    /// Whether each test runs on its own thread or in its own process.
+    pub isolate: Isolation,
+    pub bench_options: BenchOptions,
+    /// File the outcome and duration of each test are saved to, and read back from.
+    pub state_file: Option<PathBuf>,
+    /// Only run the tests that failed in the run saved in `state_file`.
+    pub rerun_failed: bool,
+    /// Sort the tests based on the run saved in `state_file`.
+    pub order: Option<TestOrder>,
     /// Stop at first failing test.
     /// May run a few more tests due to threading, but will
     /// abort as soon as possible.
@@ -102,8 +125,9 @@ fn optgroups() -> getopts::Options {
             pretty = Print verbose output;
             terse  = Display one character per test;
             json   = Output a json document;
-            junit  = Output a JUnit document",
-            "pretty|terse|json|junit",
+            junit  = Output a JUnit document;
+            tap    = Output a TAP version 13 document",
+            "pretty|terse|json|junit|tap",
         )
         .optflag("", "show-output", "Show captured stdout of successful tests")
         .optopt(
@@ -142,6 +166,100 @@ fn optgroups() -> getopts::Options {
             `CRITICAL_TIME` here means the limit that should not be exceeded by test.
             ",
         )
+        .optopt(
+            "",
+            "test-timeout",
+            "Stop tests that run for longer than SECS seconds and report them as failed.
+
+            Tests run in a separate process (with panic=abort or --isolate=process)
+            are killed. Tests run in-process cannot be stopped, so the test run is
+            aborted after reporting the timed out test.
+
+            Individual tests can override this limit with `#[test_timeout = \"SECS\"]`.",
+            "SECS",
+        )
+        .optopt(
+            "",
+            "retries",
+            "Run failed tests again, up to N times. Tests that pass on a retry are
+            reported as flaky instead of failed.",
+            "N",
+        )
+        .optopt(
+            "",
+            "isolate",
+            "Configure how tests are isolated from each other:
+            thread  = run each test on its own thread (default, unless panic=abort);
+            process = run each test in its own child process, even when
+                      panic=unwind. At most --test-threads children run at once.",
+            "thread|process",
+        )
+        .optopt(
+            "",
+            "capture",
+            "Configure how the output of tests is captured:
+            sys = capture print! and eprint! of the test thread and the threads
+                  it spawns (default);
+            fd  = redirect the stdout and stderr file descriptors, which also
+                  captures raw writes and output of C code. Tests that run
+                  in-process are then run one at a time;
+            no  = don't capture output, like --nocapture.",
+            "sys|fd|no",
+        )
+        .optopt(
+            "",
+            "partition",
+            "Split the filtered tests into N shards and only run shard K (1 <= K <= N):
+            hash:K/N  = assign tests to shards by a hash of their name, so adding
+                        a test doesn't move the other ones;
+            count:K/N = deal tests out in name order, so shards are the same size.",
+            "hash:K/N|count:K/N",
+        )
+        .optopt(
+            "",
+            "bench-warm-up",
+            "Run each benchmark for SECS seconds (fractions are allowed) before
+            measuring it.",
+            "SECS",
+        )
+        .optopt(
+            "",
+            "bench-save",
+            "Save the results of the benchmarks to a baseline file at PATH.",
+            "PATH",
+        )
+        .optopt(
+            "",
+            "bench-compare",
+            "Compare the results of the benchmarks against a baseline file saved
+            with --bench-save, and report the change of each benchmark with its
+            95% confidence interval.",
+            "BASELINE",
+        )
+        .optopt(
+            "",
+            "state-file",
+            "Save the outcome and duration of each test to PATH, for --rerun-failed
+            and --order to use in later runs. Tests that aren't run keep their
+            previous record.",
+            "PATH",
+        )
+        .optflag(
+            "",
+            "rerun-failed",
+            "Only run the tests that failed in the last run saved in --state-file.",
+        )
+        .optopt(
+            "",
+            "order",
+            "Run tests in an order based on the last run saved in --state-file:
+            failed-first  = tests that failed first, then flaky ones;
+            slowest-first = the slowest tests first, so that they don't hold up
+                            the end of the run. Tests that haven't been run
+                            before go first.
+            With --shuffle, tests that rank the same are shuffled.",
+            "failed-first|slowest-first",
+        )
         .optflag("", "shuffle", "Run tests in random order")
         .optopt(
             "",
@@ -259,6 +377,14 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
     let force_run_in_process = unstable_optflag!(matches, allow_unstable, "force-run-in-process");
     let exclude_should_panic = unstable_optflag!(matches, allow_unstable, "exclude-should-panic");
     let time_options = get_time_options(&matches, allow_unstable)?;
+    let test_timeout = get_test_timeout(&matches, allow_unstable)?;
+    let retries = get_retries(&matches, allow_unstable)?;
+    let isolate = get_isolate(&matches, allow_unstable, force_run_in_process)?;
+    let partition = get_partition(&matches, allow_unstable)?;
+    let bench_options = get_bench_options(&matches, allow_unstable)?;
+    let state_file = unstable_optopt!(matches, allow_unstable, "state-file").map(PathBuf::from);
+    let rerun_failed = get_rerun_failed(&matches, allow_unstable, &state_file)?;
+    let order = get_order(&matches, allow_unstable, &state_file)?;
     let shuffle = get_shuffle(&matches, allow_unstable)?;
     let shuffle_seed = get_shuffle_seed(&matches, allow_unstable)?;
 
@@ -274,7 +400,8 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
     let logfile = get_log_file(&matches)?;
     let run_ignored = get_run_ignored(&matches, include_ignored)?;
     let filters = matches.free.clone();
-    let nocapture = get_nocapture(&matches)?;
+    let capture = get_capture(&matches, allow_unstable)?;
+    let nocapture = get_nocapture(&matches)? || capture.is_none();
     let test_threads = get_test_threads(&matches)?;
     let color = get_color_config(&matches)?;
     let format = get_format(&matches, quiet, allow_unstable)?;
@@ -292,13 +419,22 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
         bench_benchmarks,
         logfile,
         nocapture,
+        capture: capture.unwrap_or_default(),
         color,
         format,
         shuffle,
         shuffle_seed,
         test_threads,
         skip,
+        partition,
         time_options,
+        test_timeout,
+        retries,
+        isolate,
+        bench_options,
+        state_file,
+        rerun_failed,
+        order,
         options,
         fail_fast: false,
     };
@@ -336,6 +472,171 @@ fn get_time_options(
     Ok(options)
 }
 
+fn get_test_timeout(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<Option<Duration>> {
+    let test_timeout = match unstable_optopt!(matches, allow_unstable, "test-timeout") {
+        Some(n_str) => match n_str.parse::<u64>() {
+            Ok(0) => return Err("argument for --test-timeout must not be 0".to_string()),
+            Ok(n) => Some(Duration::from_secs(n)),
+            Err(e) => {
+                return Err(format!(
+                    "argument for --test-timeout must be a number > 0 \
+                     (error: {e})"
+                ));
+            }
+        },
+        None => None,
+    };
+
+    Ok(test_timeout)
+}
+
+fn get_retries(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<usize> {
+    let retries = match unstable_optopt!(matches, allow_unstable, "retries") {
+        Some(n_str) => match n_str.parse::<usize>() {
+            Ok(n) => n,
+            Err(e) => {
+                return Err(format!(
+                    "argument for --retries must be a number \
+                     (error: {e})"
+                ));
+            }
+        },
+        None => 0,
+    };
+
+    Ok(retries)
+}
+
+fn get_bench_options(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<BenchOptions> {
+    let warm_up_time = match unstable_optopt!(matches, allow_unstable, "bench-warm-up") {
+        Some(secs_str) => match secs_str.parse::<f64>().map(Duration::try_from_secs_f64) {
+            Ok(Ok(warm_up_time)) => Some(warm_up_time),
+            _ => {
+                return Err(format!(
+                    "argument for --bench-warm-up must be a non-negative number of seconds \
+                     (was {secs_str})"
+                ));
+            }
+        },
+        None => None,
+    };
+    let save_baseline = unstable_optopt!(matches, allow_unstable, "bench-save").map(PathBuf::from);
+    let compare_baseline =
+        unstable_optopt!(matches, allow_unstable, "bench-compare").map(PathBuf::from);
+
+    Ok(BenchOptions { warm_up_time, save_baseline, compare_baseline })
+}
+
+fn get_isolate(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+    force_run_in_process: bool,
+) -> OptPartRes<Isolation> {
+    let isolate = match unstable_optopt!(matches, allow_unstable, "isolate").as_deref() {
+        Some("thread") | None => Isolation::Thread,
+        Some("process") => Isolation::Process,
+        Some(v) => {
+            return Err(format!(
+                "argument for --isolate must be thread or process (was \
+                 {v})"
+            ));
+        }
+    };
+
+    if isolate == Isolation::Process && force_run_in_process {
+        return Err("--isolate=process can't be combined with --force-run-in-process".into());
+    }
+
+    Ok(isolate)
+}
+
+/// Returns `None` for `--capture=no`.
+fn get_capture(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<Option<Capture>> {
+    let capture = match unstable_optopt!(matches, allow_unstable, "capture").as_deref() {
+        Some("sys") | None => Some(Capture::Sys),
+        Some("fd") => Some(Capture::Fd),
+        Some("no") => None,
+        Some(v) => {
+            return Err(format!(
+                "argument for --capture must be sys, fd or no (was \
+                 {v})"
+            ));
+        }
+    };
+
+    Ok(capture)
+}
+
+fn get_rerun_failed(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+    state_file: &Option<PathBuf>,
+) -> OptPartRes<bool> {
+    let rerun_failed = unstable_optflag!(matches, allow_unstable, "rerun-failed");
+    if rerun_failed && state_file.is_none() {
+        return Err("--rerun-failed requires --state-file".into());
+    }
+
+    Ok(rerun_failed)
+}
+
+fn get_order(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+    state_file: &Option<PathBuf>,
+) -> OptPartRes<Option<TestOrder>> {
+    let order = match unstable_optopt!(matches, allow_unstable, "order").as_deref() {
+        None => return Ok(None),
+        Some("failed-first") => TestOrder::FailedFirst,
+        Some("slowest-first") => TestOrder::SlowestFirst,
+        Some(v) => {
+            return Err(format!(
+                "argument for --order must be failed-first or slowest-first (was \
+                 {v})"
+            ));
+        }
+    };
+
+    if state_file.is_none() {
+        return Err("--order requires --state-file".into());
+    }
+
+    Ok(Some(order))
+}
+
+fn get_partition(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<Option<Partition>> {
+    let partition = match unstable_optopt!(matches, allow_unstable, "partition") {
+        Some(partition) => partition,
+        None => return Ok(None),
+    };
+
+    let err = || {
+        format!(
+            "argument for --partition must be hash:K/N or count:K/N with 1 <= K <= N (was \
+             {partition})"
+        )
+    };
+    let (strategy, shard) = match partition.split_once(':') {
+        Some(("hash", shard)) => (PartitionStrategy::Hash, shard),
+        Some(("count", shard)) => (PartitionStrategy::Count, shard),
+        _ => return Err(err()),
+    };
+    let (shard, total) = shard.split_once('/').ok_or_else(err)?;
+    let shard = shard.parse::<usize>().map_err(|_| err())?;
+    let total = total.parse::<usize>().map_err(|_| err())?;
+    if shard == 0 || shard > total {
+        return Err(err());
+    }
+
+    Ok(Some(Partition { strategy, shard, total }))
+}
+
 fn get_shuffle(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<bool> {
     let mut shuffle = unstable_optflag!(matches, allow_unstable, "shuffle");
     if !shuffle && allow_unstable {
@@ -414,9 +715,15 @@ fn get_format(
             }
             OutputFormat::Junit
         }
+        Some("tap") => {
+            if !allow_unstable {
+                return Err("The \"tap\" format is only accepted on the nightly compiler with -Z unstable-options".into());
+            }
+            OutputFormat::Tap
+        }
         Some(v) => {
             return Err(format!(
-                "argument for --format must be pretty, terse, json or junit (was \
+                "argument for --format must be pretty, terse, json, junit or tap (was \
                  {v})"
             ));
         }
@@ -1,19 +1,30 @@
 //! Module providing interface for running tests in the console.
 
+use std::collections::HashMap;
 use std::fs::File;
 use std::io;
 use std::io::prelude::Write;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use super::{
     bench::fmt_bench_samples,
     cli::TestOpts,
     event::{CompletedTest, TestEvent},
-    filter_tests,
-    formatters::{JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TerseFormatter},
-    helpers::{concurrency::get_concurrency, metrics::MetricMap},
+    filter_tests_unpartitioned,
+    fixtures::{FixtureFailure, TestFixture},
+    formatters::{
+        JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TapFormatter,
+        TerseFormatter,
+    },
+    helpers::{
+        baseline::Baseline,
+        concurrency::get_concurrency,
+        metrics::MetricMap,
+        partition::assign_shards,
+        run_state::{RunState, TestOutcome},
+    },
     options::{Options, OutputFormat},
-    run_tests, term,
+    read_run_state, run_tests_with_run_state, term,
     test_result::TestResult,
     time::{TestExecTime, TestSuiteExecTime},
     types::{NamePadding, TestDesc, TestDescAndFn},
@@ -88,6 +99,19 @@ pub struct ConsoleTestState {
     pub not_failures: Vec<(TestDesc, Vec<u8>)>,
     pub ignores: Vec<(TestDesc, Vec<u8>)>,
     pub time_failures: Vec<(TestDesc, Vec<u8>)>,
+    /// Tests that only passed after being retried, with the number of attempts it took
+    /// and the output of all of them.
+    pub flaky: Vec<(TestDesc, usize, Vec<u8>)>,
+    /// Fixtures whose setup or teardown failed, which fails the whole run.
+    pub fixture_failures: Vec<FixtureFailure>,
+    /// Baseline to compare the benchmarks against, read from `--bench-compare`.
+    pub compare_baseline: Option<Baseline>,
+    /// Results of the benchmarks run so far, saved with `--bench-save`.
+    pub new_baseline: Baseline,
+    /// Outcomes and durations of the tests, read from and saved to `--state-file`.
+    pub run_state: Option<RunState>,
+    /// When each running test started, to record its duration in `run_state`.
+    pub test_starts: HashMap<String, Instant>,
     pub options: Options,
 }
 
@@ -97,6 +121,14 @@ pub fn new(opts: &TestOpts) -> io::Result<ConsoleTestState> {
             Some(ref path) => Some(File::create(path)?),
             None => None,
         };
+        let compare_baseline = match opts.bench_options.compare_baseline {
+            Some(ref path) => Some(Baseline::read(path)?),
+            None => None,
+        };
+        let run_state = match opts.state_file {
+            Some(ref path) => Some(RunState::read(path)?),
+            None => None,
+        };
 
         Ok(ConsoleTestState {
             log_out,
@@ -112,6 +144,12 @@ pub fn new(opts: &TestOpts) -> io::Result<ConsoleTestState> {
             not_failures: Vec::new(),
             ignores: Vec::new(),
             time_failures: Vec::new(),
+            flaky: Vec::new(),
+            fixture_failures: Vec::new(),
+            compare_baseline,
+            new_baseline: Baseline::new(),
+            run_state,
+            test_starts: HashMap::new(),
             options: opts.options,
         })
     }
@@ -154,6 +192,7 @@ pub fn write_log_result(
                     }
                     TestResult::TrBench(ref bs) => fmt_bench_samples(bs),
                     TestResult::TrTimedFail => "failed (time limit exceeded)".to_owned(),
+                    TestResult::TrFlaky(attempts) => format!("ok (flaky, {attempts} attempts)"),
                 },
                 name,
             )
@@ -164,6 +203,12 @@ pub fn write_log_result(
         self.write_log(|| "\n")
     }
 
+    /// Whether the run succeeded: no test failed, and all fixtures were set up
+    /// and torn down.
+    pub fn is_success(&self) -> bool {
+        self.failed == 0 && self.fixture_failures.is_empty()
+    }
+
     fn current_test_count(&self) -> usize {
         self.passed + self.failed + self.ignored + self.measured
     }
@@ -177,18 +222,35 @@ pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Res
     };
 
     let mut out: Box<dyn OutputFormatter> = match opts.format {
-        OutputFormat::Pretty | OutputFormat::Junit => {
+        OutputFormat::Pretty | OutputFormat::Junit | OutputFormat::Tap => {
             Box::new(PrettyFormatter::new(output, false, 0, false, None))
         }
         OutputFormat::Terse => Box::new(TerseFormatter::new(output, false, 0, false)),
-        OutputFormat::Json => Box::new(JsonFormatter::new(output)),
+        OutputFormat::Json => Box::new(JsonFormatter::new(output, opts.partition)),
     };
     let mut st = ConsoleTestDiscoveryState::new(opts)?;
 
+    let run_state = read_run_state(opts)?;
+    let tests = filter_tests_unpartitioned(opts, tests);
+    let shards = match opts.partition {
+        Some(partition) => assign_shards(partition, &tests).into_iter().map(Some).collect(),
+        None => vec![None; tests.len()],
+    };
+    // The JSON listing has every test with its shard, so that orchestrators can check
+    // that the shards cover all tests. Other formats only list the selected shard.
+    let listed = |shard: Option<usize>| {
+        opts.format == OutputFormat::Json || shard == opts.partition.map(|p| p.shard)
+    };
+
     out.write_discovery_start()?;
-    for test in filter_tests(opts, tests).into_iter() {
+    for (test, shard) in tests.into_iter().zip(shards) {
         use crate::TestFn::*;
 
+        // `--rerun-failed` applies after the tests are split into shards, like in a run.
+        if !listed(shard) || (opts.rerun_failed && !run_state.failed(test.desc.name.as_slice())) {
+            continue;
+        }
+
         let TestDescAndFn { desc, testfn } = test;
 
         let fntype = match testfn {
@@ -204,7 +266,7 @@ pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Res
 
         st.ignored += if desc.ignore { 1 } else { 0 };
 
-        out.write_test_discovered(&desc, fntype)?;
+        out.write_test_discovered(&desc, fntype, shard)?;
         st.write_log(|| format!("{fntype} {}\n", desc.name))?;
     }
 
@@ -220,6 +282,11 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
             st.passed += 1;
             st.not_failures.push((test, stdout));
         }
+        TestResult::TrFlaky(attempts) => {
+            st.passed += 1;
+            st.flaky.push((test.clone(), attempts, stdout.clone()));
+            st.not_failures.push((test, stdout));
+        }
         TestResult::TrIgnored => {
             st.ignored += 1;
             st.ignores.push((test, stdout));
@@ -230,6 +297,7 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
                 bs.ns_iter_summ.median,
                 bs.ns_iter_summ.max - bs.ns_iter_summ.min,
             );
+            st.new_baseline.insert(test.name.as_slice(), &bs.ns_iter_raw_summ);
             st.measured += 1
         }
         TestResult::TrFailed => {
@@ -249,6 +317,30 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
     }
 }
 
+// Records the outcome and duration of a test in the state saved to `--state-file`.
+fn record_run_state(st: &mut ConsoleTestState, completed_test: &CompletedTest) {
+    let name = completed_test.desc.name.as_slice();
+    let start = st.test_starts.remove(name);
+    let Some(ref mut run_state) = st.run_state else {
+        return;
+    };
+    let outcome = match completed_test.result {
+        TestResult::TrOk | TestResult::TrBench(_) => TestOutcome::Passed,
+        TestResult::TrFlaky(_) => TestOutcome::Flaky,
+        TestResult::TrFailed | TestResult::TrFailedMsg(_) | TestResult::TrTimedFail => {
+            TestOutcome::Failed
+        }
+        // Ignored tests keep the record of the last time they were run.
+        TestResult::TrIgnored => return,
+    };
+    let duration = match (&completed_test.exec_time, start) {
+        (Some(exec_time), _) => exec_time.0,
+        (None, Some(start)) => start.elapsed(),
+        (None, None) => Duration::ZERO,
+    };
+    run_state.record(name, outcome, duration);
+}
+
 // Handler for events that occur during test execution.
 // It is provided as a callback to the `run_tests` function.
 fn on_test_event(
@@ -264,9 +356,26 @@ fn on_test_event(
         TestEvent::TeFilteredOut(filtered_out) => {
             st.filtered_out = filtered_out;
         }
-        TestEvent::TeWait(ref test) => out.write_test_start(test)?,
+        TestEvent::TeWait(ref test) => {
+            if st.run_state.is_some() && is_instant_supported() {
+                st.test_starts.insert(test.name.as_slice().to_owned(), Instant::now());
+            }
+            out.write_test_start(test)?;
+        }
         TestEvent::TeTimeout(ref test) => out.write_timeout(test)?,
-        TestEvent::TeResult(completed_test) => {
+        TestEvent::TeFixtureFailed(failure) => {
+            st.write_log(|| format!("{failure}\n"))?;
+            out.write_fixture_failure(&failure)?;
+            st.fixture_failures.push(failure);
+        }
+        TestEvent::TeResult(mut completed_test) => {
+            if let (Some(baseline), TestResult::TrBench(bs)) =
+                (&st.compare_baseline, &mut completed_test.result)
+            {
+                bs.change =
+                    baseline.compare(completed_test.desc.name.as_slice(), &bs.ns_iter_raw_summ);
+            }
+
             let test = &completed_test.desc;
             let result = &completed_test.result;
             let exec_time = &completed_test.exec_time;
@@ -274,6 +383,7 @@ fn on_test_event(
 
             st.write_log_result(test, result, exec_time.as_ref())?;
             out.write_result(test, result, exec_time.as_ref(), stdout, st)?;
+            record_run_state(st, &completed_test);
             handle_test_result(st, completed_test);
         }
     }
@@ -284,6 +394,16 @@ fn on_test_event(
 /// A simple console test runner.
 /// Runs provided tests reporting process and results to the stdout.
 pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Result<bool> {
+    run_tests_console_with_fixtures(opts, tests, &[])
+}
+
+/// Like `run_tests_console`, but also sets up and tears down the given fixtures
+/// around the tests in their modules.
+pub fn run_tests_console_with_fixtures(
+    opts: &TestOpts,
+    tests: Vec<TestDescAndFn>,
+    fixtures: &[TestFixture],
+) -> io::Result<bool> {
     let output = match term::stdout() {
         None => OutputLocation::Raw(io::stdout()),
         Some(t) => OutputLocation::Pretty(t),
@@ -308,25 +428,38 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
         OutputFormat::Terse => {
             Box::new(TerseFormatter::new(output, opts.use_color(), max_name_len, is_multithreaded))
         }
-        OutputFormat::Json => Box::new(JsonFormatter::new(output)),
+        OutputFormat::Json => Box::new(JsonFormatter::new(output, opts.partition)),
         OutputFormat::Junit => Box::new(JunitFormatter::new(output)),
+        OutputFormat::Tap => Box::new(TapFormatter::new(output)),
     };
     let mut st = ConsoleTestState::new(opts)?;
+    // The state of the last run picks and orders the tests, while `st` records this run.
+    let last_run_state = st.run_state.clone().unwrap_or_default();
 
-    // Prevent the usage of `Instant` in some cases:
-    // - It's currently not supported for wasm targets.
-    let is_instant_unsupported =
-        (cfg!(target_family = "wasm") && !cfg!(target_os = "wasi")) || cfg!(target_os = "zkvm");
-
-    let start_time = (!is_instant_unsupported).then(Instant::now);
-    run_tests(opts, tests, |x| on_test_event(&x, &mut st, &mut *out))?;
+    let start_time = is_instant_supported().then(Instant::now);
+    run_tests_with_run_state(opts, tests, fixtures, &last_run_state, |x| {
+        on_test_event(&x, &mut st, &mut *out)
+    })?;
     st.exec_time = start_time.map(|t| TestSuiteExecTime(t.elapsed()));
 
     assert!(opts.fail_fast || st.current_test_count() == st.total);
 
+    if let Some(ref path) = opts.bench_options.save_baseline {
+        st.new_baseline.write(path)?;
+    }
+    if let (Some(path), Some(run_state)) = (&opts.state_file, &st.run_state) {
+        run_state.write(path)?;
+    }
+
     out.write_run_finish(&st)
 }
 
+// Prevent the usage of `Instant` in some cases:
+// - It's currently not supported for wasm targets.
+fn is_instant_supported() -> bool {
+    !((cfg!(target_family = "wasm") && !cfg!(target_os = "wasi")) || cfg!(target_os = "zkvm"))
+}
+
 // Calculates padding for given test description.
 fn len_if_padded(t: &TestDescAndFn) -> usize {
     match t.testfn.padding() {
@@ -0,0 +1,178 @@
+//! Setup and teardown functions shared by the tests in a module.
+
+use std::any::Any;
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use super::types::TestDesc;
+
+/// Functions to run once before, and once after, the tests in a module.
+///
+/// The tests in scope of a fixture are those whose name starts with its
+/// `module_path`, e.g. `net::server` applies to `net::server::accepts` and
+/// `net::server::tls::handshake`. An empty `module_path` applies to all tests.
+///
+/// Fixtures run in the process that runs their tests: once in the test binary
+/// when tests run on threads, or once in each child process when they run in
+/// separate processes (with panic=abort or `--isolate=process`).
+///
+/// Fixtures are only registered by custom harnesses (`harness = false`) that call
+/// [`test_main_with_fixtures`], [`test_main_static_with_fixtures`] or
+/// [`test_main_static_abort_with_fixtures`] themselves. The harness that `rustc --test`
+/// generates for `#[test]` functions doesn't register any.
+///
+/// [`test_main_with_fixtures`]: crate::test_main_with_fixtures
+/// [`test_main_static_with_fixtures`]: crate::test_main_static_with_fixtures
+/// [`test_main_static_abort_with_fixtures`]: crate::test_main_static_abort_with_fixtures
+#[derive(Clone, Copy, Debug)]
+pub struct TestFixture {
+    pub module_path: &'static str,
+    /// Runs before the first test in scope. If it fails, the tests in scope
+    /// aren't run and are reported as failed.
+    pub setup: Option<fn() -> Result<(), String>>,
+    /// Runs after the last test in scope, if `setup` succeeded. It doesn't run if the
+    /// run stops while tests may still be running, after a hard timeout or with
+    /// `--fail-fast`, since those tests might still use what the fixture set up.
+    pub teardown: Option<fn() -> Result<(), String>>,
+}
+
+impl TestFixture {
+    /// The module path of the fixture, or `crate` for a fixture of all tests.
+    pub fn scope(&self) -> &'static str {
+        scope(self.module_path)
+    }
+
+    /// Whether the test called `test_name` is in scope of this fixture.
+    pub fn applies_to(&self, test_name: &str) -> bool {
+        self.module_path.is_empty()
+            || test_name.strip_prefix(self.module_path).is_some_and(|rest| rest.starts_with("::"))
+    }
+}
+
+/// Which function of a fixture failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FixtureStage {
+    Setup,
+    Teardown,
+}
+
+impl fmt::Display for FixtureStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixtureStage::Setup => f.write_str("setup"),
+            FixtureStage::Teardown => f.write_str("teardown"),
+        }
+    }
+}
+
+/// The failed setup or teardown of a fixture, reported as an error of the whole suite.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FixtureFailure {
+    pub module_path: &'static str,
+    pub stage: FixtureStage,
+    pub message: String,
+}
+
+impl FixtureFailure {
+    /// The module path of the fixture, or `crate` for a fixture of all tests.
+    pub fn scope(&self) -> &'static str {
+        scope(self.module_path)
+    }
+}
+
+impl fmt::Display for FixtureFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} of fixture `{}` failed: {}", self.stage, self.scope(), self.message)
+    }
+}
+
+/// The fixtures that were set up in this process.
+pub(crate) struct ActiveFixtures {
+    /// Fixtures to tear down at the end of the run, outermost first.
+    set_up: Vec<TestFixture>,
+    /// Fixtures whose setup failed.
+    failed: Vec<TestFixture>,
+}
+
+impl ActiveFixtures {
+    /// Sets up the fixtures that apply to any of `tests`, outer modules first.
+    /// A fixture nested in a module whose setup failed isn't set up at all.
+    pub(crate) fn set_up<'a>(
+        fixtures: &[TestFixture],
+        tests: impl Iterator<Item = &'a TestDesc> + Clone,
+    ) -> (ActiveFixtures, Vec<FixtureFailure>) {
+        let mut in_scope: Vec<_> = fixtures
+            .iter()
+            .filter(|fixture| tests.clone().any(|desc| fixture.applies_to(desc.name.as_slice())))
+            .collect();
+        in_scope.sort_by_key(|fixture| fixture.module_path.len());
+
+        let mut active = ActiveFixtures { set_up: Vec::new(), failed: Vec::new() };
+        let mut failures = Vec::new();
+        for &fixture in in_scope {
+            if active.failed.iter().any(|failed| failed.applies_to(fixture.module_path)) {
+                continue;
+            }
+            match fixture.setup.map(run_fixture_fn).unwrap_or(Ok(())) {
+                Ok(()) => active.set_up.push(fixture),
+                Err(message) => {
+                    active.failed.push(fixture);
+                    failures.push(FixtureFailure {
+                        module_path: fixture.module_path,
+                        stage: FixtureStage::Setup,
+                        message,
+                    });
+                }
+            }
+        }
+        (active, failures)
+    }
+
+    /// The failed setup of a fixture that the test called `test_name` is in scope of.
+    pub(crate) fn failed_setup(&self, test_name: &str) -> Option<&'static str> {
+        self.failed
+            .iter()
+            .find(|fixture| fixture.applies_to(test_name))
+            .map(|fixture| fixture.scope())
+    }
+
+    /// Tears down the fixtures that were set up, inner modules first.
+    pub(crate) fn tear_down(self) -> Vec<FixtureFailure> {
+        let mut failures = Vec::new();
+        for fixture in self.set_up.into_iter().rev() {
+            if let Some(Err(message)) = fixture.teardown.map(run_fixture_fn) {
+                failures.push(FixtureFailure {
+                    module_path: fixture.module_path,
+                    stage: FixtureStage::Teardown,
+                    message,
+                });
+            }
+        }
+        failures
+    }
+}
+
+fn scope(module_path: &'static str) -> &'static str {
+    match module_path {
+        "" => "crate",
+        module_path => module_path,
+    }
+}
+
+fn run_fixture_fn(f: fn() -> Result<(), String>) -> Result<(), String> {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(payload) => Err(panic_message(&*payload)),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let message = payload
+        .downcast_ref::<String>()
+        .map(|e| &**e)
+        .or_else(|| payload.downcast_ref::<&'static str>().copied());
+    match message {
+        Some(message) => format!("panicked: {message}"),
+        None => "panicked".to_string(),
+    }
+}
@@ -3,18 +3,39 @@
 use super::OutputFormatter;
 use crate::{
     console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation},
+    fixtures::FixtureFailure,
+    options::Partition,
+    stats,
     test_result::TestResult,
     time,
     types::TestDesc,
 };
 
+/// The version of the JSON event schema, reported by the first event of the output.
+///
+/// Bump this when changing the meaning of, or removing, any existing field or event.
+/// Adding fields or events is backwards compatible and doesn't need a new version.
+/// The schema is documented in `src/doc/rustc/src/tests/index.md`.
+pub(crate) const JSON_SCHEMA_VERSION: u32 = 1;
+
 pub(crate) struct JsonFormatter<T> {
     out: OutputLocation<T>,
+    partition: Option<Partition>,
 }
 
 impl<T: Write> JsonFormatter<T> {
-    pub fn new(out: OutputLocation<T>) -> Self {
-        Self { out }
+    pub fn new(out: OutputLocation<T>, partition: Option<Partition>) -> Self {
+        Self { out, partition }
+    }
+
+    /// The `shard` and `shard_count` fields of the suite events, if a partition was selected.
+    fn partition_json(&self) -> String {
+        match self.partition {
+            Some(Partition { shard, total, .. }) => {
+                format!(r#", "shard": {shard}, "shard_count": {total}"#)
+            }
+            None => String::new(),
+        }
     }
 
     fn writeln_message(&mut self, s: &str) -> io::Result<()> {
@@ -58,10 +79,18 @@ fn write_event(
 
 impl<T: Write> OutputFormatter for JsonFormatter<T> {
     fn write_discovery_start(&mut self) -> io::Result<()> {
-        self.writeln_message(concat!(r#"{ "type": "suite", "event": "discovery" }"#, "\n"))
+        let newline = "\n";
+        self.writeln_message(&format!(
+            r#"{{ "type": "suite", "event": "discovery", "schema_version": {JSON_SCHEMA_VERSION} }}{newline}"#
+        ))
     }
 
-    fn write_test_discovered(&mut self, desc: &TestDesc, test_type: &str) -> io::Result<()> {
+    fn write_test_discovered(
+        &mut self,
+        desc: &TestDesc,
+        test_type: &str,
+        shard: Option<usize>,
+    ) -> io::Result<()> {
         let TestDesc {
             name,
             ignore,
@@ -77,10 +106,12 @@ fn write_test_discovered(&mut self, desc: &TestDesc, test_type: &str) -> io::Res
         let name = EscapedString(name.as_slice());
         let ignore_message = ignore_message.unwrap_or("");
         let source_path = EscapedString(source_file);
+        let shard_json =
+            if let Some(shard) = shard { format!(r#", "shard": {shard}"#) } else { String::new() };
         let newline = "\n";
 
         self.writeln_message(&format!(
-            r#"{{ "type": "{test_type}", "event": "discovered", "name": "{name}", "ignore": {ignore}, "ignore_message": "{ignore_message}", "source_path": "{source_path}", "start_line": {start_line}, "start_col": {start_col}, "end_line": {end_line}, "end_col": {end_col} }}{newline}"#
+            r#"{{ "type": "{test_type}", "event": "discovered", "name": "{name}", "ignore": {ignore}, "ignore_message": "{ignore_message}", "source_path": "{source_path}", "start_line": {start_line}, "start_col": {start_col}, "end_line": {end_line}, "end_col": {end_col}{shard_json} }}{newline}"#
         ))
     }
 
@@ -88,9 +119,10 @@ fn write_discovery_finish(&mut self, state: &ConsoleTestDiscoveryState) -> io::R
         let ConsoleTestDiscoveryState { tests, benchmarks, ignored, .. } = state;
 
         let total = tests + benchmarks;
+        let partition_json = self.partition_json();
         let newline = "\n";
         self.writeln_message(&format!(
-            r#"{{ "type": "suite", "event": "completed", "tests": {tests}, "benchmarks": {benchmarks}, "total": {total}, "ignored": {ignored} }}{newline}"#
+            r#"{{ "type": "suite", "event": "completed", "tests": {tests}, "benchmarks": {benchmarks}, "total": {total}, "ignored": {ignored}{partition_json} }}{newline}"#
             ))
     }
 
@@ -100,9 +132,10 @@ fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> i
         } else {
             String::new()
         };
+        let partition_json = self.partition_json();
         let newline = "\n";
         self.writeln_message(&format!(
-            r#"{{ "type": "suite", "event": "started", "test_count": {test_count}{shuffle_seed_json} }}{newline}"#
+            r#"{{ "type": "suite", "event": "started", "schema_version": {JSON_SCHEMA_VERSION}, "test_count": {test_count}{shuffle_seed_json}{partition_json} }}{newline}"#
             ))
     }
 
@@ -122,7 +155,7 @@ fn write_result(
         stdout: &[u8],
         state: &ConsoleTestState,
     ) -> io::Result<()> {
-        let display_stdout = state.options.display_output || *result != TestResult::TrOk;
+        let display_stdout = state.options.display_output || !matches!(*result, TestResult::TrOk);
         let stdout = if display_stdout && !stdout.is_empty() {
             Some(String::from_utf8_lossy(stdout))
         } else {
@@ -133,6 +166,15 @@ fn write_result(
                 self.write_event("test", desc.name.as_slice(), "ok", exec_time, stdout, None)
             }
 
+            TestResult::TrFlaky(attempts) => self.write_event(
+                "test",
+                desc.name.as_slice(),
+                "ok",
+                exec_time,
+                stdout,
+                Some(&*format!(r#""flaky": true, "attempts": {attempts}"#)),
+            ),
+
             TestResult::TrFailed => {
                 self.write_event("test", desc.name.as_slice(), "failed", exec_time, stdout, None)
             }
@@ -173,7 +215,19 @@ fn write_result(
                 let mbps = if bs.mb_s == 0 {
                     String::new()
                 } else {
-                    format!(r#", "mib_per_second": {}"#, bs.mb_s)
+                    format!(r#", "mib_per_second": {}, "bytes": {}"#, bs.mb_s, bs.bytes)
+                };
+                let stats::Outliers { low_severe, low_mild, high_mild, high_severe } = bs.outliers;
+                let outliers = format!(
+                    ", \"outliers\": {{ \"low_severe\": {low_severe}, \"low_mild\": {low_mild}, \
+                     \"high_mild\": {high_mild}, \"high_severe\": {high_severe} }}",
+                );
+                let change = match bs.change {
+                    Some(change) => format!(
+                        r#", "change": {{ "percent": {}, "ci_low": {}, "ci_high": {} }}"#,
+                        change.pct, change.ci_low, change.ci_high
+                    ),
+                    None => String::new(),
                 };
                 let name = EscapedString(desc.name.as_slice());
 
@@ -181,7 +235,7 @@ fn write_result(
                     "{{ \"type\": \"bench\", \
                      \"name\": \"{name}\", \
                      \"median\": {median}, \
-                     \"deviation\": {deviation}{mbps} }}\n",
+                     \"deviation\": {deviation}{mbps}{outliers}{change} }}\n",
                 ))
             }
         }
@@ -195,8 +249,19 @@ fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
         ))
     }
 
+    fn write_fixture_failure(&mut self, failure: &FixtureFailure) -> io::Result<()> {
+        let name = EscapedString(failure.module_path);
+        let stage = failure.stage;
+        let message = EscapedString(&failure.message);
+        let newline = "\n";
+        self.writeln_message(&format!(
+            r#"{{ "type": "fixture", "event": "failed", "name": "{name}", "stage": "{stage}", "message": "{message}" }}{newline}"#,
+        ))
+    }
+
     fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
-        let event = if state.failed == 0 { "ok" } else { "failed" };
+        let success = state.is_success();
+        let event = if success { "ok" } else { "failed" };
         let passed = state.passed;
         let failed = state.failed;
         let ignored = state.ignored;
@@ -207,13 +272,23 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         } else {
             String::from("")
         };
+        let flaky_json = if state.flaky.is_empty() {
+            String::new()
+        } else {
+            format!(r#", "flaky": {}"#, state.flaky.len())
+        };
+        let fixture_failures_json = if state.fixture_failures.is_empty() {
+            String::new()
+        } else {
+            format!(r#", "fixture_failures": {}"#, state.fixture_failures.len())
+        };
         let newline = "\n";
 
         self.writeln_message(&format!(
-            r#"{{ "type": "suite", "event": "{event}", "passed": {passed}, "failed": {failed}, "ignored": {ignored}, "measured": {measured}, "filtered_out": {filtered_out}{exec_time_json} }}{newline}"#
+            r#"{{ "type": "suite", "event": "{event}", "passed": {passed}, "failed": {failed}, "ignored": {ignored}, "measured": {measured}, "filtered_out": {filtered_out}{flaky_json}{fixture_failures_json}{exec_time_json} }}{newline}"#
         ))?;
 
-        Ok(state.failed == 0)
+        Ok(success)
     }
 }
 
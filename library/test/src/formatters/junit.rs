@@ -4,6 +4,7 @@
 use super::OutputFormatter;
 use crate::{
     console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation},
+    fixtures::FixtureFailure,
     test_result::TestResult,
     time,
     types::{TestDesc, TestType},
@@ -38,12 +39,34 @@ fn str_to_cdata(s: &str) -> String {
     format!("<![CDATA[{}]]>", escaped_output)
 }
 
+/// Escapes a string for use in a double-quoted XML attribute. Newlines are escaped too,
+/// to keep all the output on one line.
+fn escape_attr(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\n' => escaped.push_str("&#xA;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 impl<T: Write> OutputFormatter for JunitFormatter<T> {
     fn write_discovery_start(&mut self) -> io::Result<()> {
         Err(io::Error::new(io::ErrorKind::NotFound, "Not yet implemented!"))
     }
 
-    fn write_test_discovered(&mut self, _desc: &TestDesc, _test_type: &str) -> io::Result<()> {
+    fn write_test_discovered(
+        &mut self,
+        _desc: &TestDesc,
+        _test_type: &str,
+        _shard: Option<usize>,
+    ) -> io::Result<()> {
         Err(io::Error::new(io::ErrorKind::NotFound, "Not yet implemented!"))
     }
 
@@ -85,22 +108,53 @@ fn write_result(
         self.results.push((desc.clone(), result.clone(), duration, stdout.to_vec()));
         Ok(())
     }
+    fn write_fixture_failure(&mut self, _failure: &FixtureFailure) -> io::Result<()> {
+        // Fixture failures are written as errors of the test suite when the run is complete.
+        Ok(())
+    }
+
     fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         self.write_message("<testsuites>")?;
 
         self.write_message(&format!(
             "<testsuite name=\"test\" package=\"test\" id=\"0\" \
-             errors=\"0\" \
+             errors=\"{}\" \
              failures=\"{}\" \
              tests=\"{}\" \
              skipped=\"{}\" \
              >",
-            state.failed, state.total, state.ignored
+            state.fixture_failures.len(),
+            state.failed,
+            state.total,
+            state.ignored
         ))?;
+        for failure in &state.fixture_failures {
+            self.write_message(&format!(
+                "<testcase classname=\"{}\" name=\"{}\" time=\"0\">\
+                 <error message=\"{}\" type=\"fixture\"/></testcase>",
+                failure.scope(),
+                failure.stage,
+                escape_attr(&failure.message)
+            ))?;
+        }
         for (desc, result, duration, stdout) in std::mem::take(&mut self.results) {
             let (class_name, test_name) = parse_class_name(&desc);
             match result {
-                TestResult::TrIgnored => { /* no-op */ }
+                TestResult::TrIgnored => {
+                    self.write_message(&format!(
+                        "<testcase classname=\"{}\" \
+                         name=\"{}\" time=\"{}\">",
+                        class_name,
+                        test_name,
+                        duration.as_secs_f64()
+                    ))?;
+                    match desc.ignore_message {
+                        Some(m) => self
+                            .write_message(&format!("<skipped message=\"{}\"/>", escape_attr(m)))?,
+                        None => self.write_message("<skipped/>")?,
+                    }
+                    self.write_message("</testcase>")?;
+                }
                 TestResult::TrFailed => {
                     self.write_message(&format!(
                         "<testcase classname=\"{}\" \
@@ -126,7 +180,10 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
                         test_name,
                         duration.as_secs_f64()
                     ))?;
-                    self.write_message(&format!("<failure message=\"{m}\" type=\"assert\"/>"))?;
+                    self.write_message(&format!(
+                        "<failure message=\"{}\" type=\"assert\"/>",
+                        escape_attr(m)
+                    ))?;
                     if !stdout.is_empty() {
                         self.write_message("<system-out>")?;
                         self.write_message(&str_to_cdata(&String::from_utf8_lossy(&stdout)))?;
@@ -144,6 +201,11 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
                         duration.as_secs_f64()
                     ))?;
                     self.write_message("<failure type=\"timeout\"/>")?;
+                    if !stdout.is_empty() {
+                        self.write_message("<system-out>")?;
+                        self.write_message(&str_to_cdata(&String::from_utf8_lossy(&stdout)))?;
+                        self.write_message("</system-out>")?;
+                    }
                     self.write_message("</testcase>")?;
                 }
 
@@ -155,6 +217,26 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
                     ))?;
                 }
 
+                TestResult::TrFlaky(attempts) => {
+                    self.write_message(&format!(
+                        "<testcase classname=\"{}\" \
+                         name=\"{}\" time=\"{}\">",
+                        class_name,
+                        test_name,
+                        duration.as_secs_f64()
+                    ))?;
+                    // A test that passed on a rerun, in the format used by Maven Surefire.
+                    self.write_message(&format!(
+                        "<flakyFailure message=\"passed after {attempts} attempts\" type=\"assert\"/>"
+                    ))?;
+                    if !stdout.is_empty() && state.options.display_output {
+                        self.write_message("<system-out>")?;
+                        self.write_message(&str_to_cdata(&String::from_utf8_lossy(&stdout)))?;
+                        self.write_message("</system-out>")?;
+                    }
+                    self.write_message("</testcase>")?;
+                }
+
                 TestResult::TrOk => {
                     self.write_message(&format!(
                         "<testcase classname=\"{}\" \
@@ -181,7 +263,7 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
 
         self.out.write_all(b"\n")?;
 
-        Ok(state.failed == 0)
+        Ok(state.is_success())
     }
 }
 
@@ -2,6 +2,7 @@
 
 use crate::{
     console::{ConsoleTestDiscoveryState, ConsoleTestState},
+    fixtures::FixtureFailure,
     test_result::TestResult,
     time,
     types::{TestDesc, TestName},
@@ -10,21 +11,29 @@
 mod json;
 mod junit;
 mod pretty;
+mod tap;
 mod terse;
 
 pub(crate) use self::json::JsonFormatter;
 pub(crate) use self::junit::JunitFormatter;
 pub(crate) use self::pretty::PrettyFormatter;
+pub(crate) use self::tap::TapFormatter;
 pub(crate) use self::terse::TerseFormatter;
 
 pub(crate) trait OutputFormatter {
     fn write_discovery_start(&mut self) -> io::Result<()>;
-    fn write_test_discovered(&mut self, desc: &TestDesc, test_type: &str) -> io::Result<()>;
+    fn write_test_discovered(
+        &mut self,
+        desc: &TestDesc,
+        test_type: &str,
+        shard: Option<usize>,
+    ) -> io::Result<()>;
     fn write_discovery_finish(&mut self, state: &ConsoleTestDiscoveryState) -> io::Result<()>;
 
     fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()>;
     fn write_test_start(&mut self, desc: &TestDesc) -> io::Result<()>;
     fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()>;
+    fn write_fixture_failure(&mut self, failure: &FixtureFailure) -> io::Result<()>;
     fn write_result(
         &mut self,
         desc: &TestDesc,
@@ -4,6 +4,7 @@
 use crate::{
     bench::fmt_bench_samples,
     console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation},
+    fixtures::FixtureFailure,
     term,
     test_result::TestResult,
     time,
@@ -57,6 +58,10 @@ pub fn write_time_failed(&mut self) -> io::Result<()> {
         self.write_short_result("FAILED (time limit exceeded)", term::color::RED)
     }
 
+    pub fn write_flaky(&mut self, attempts: usize) -> io::Result<()> {
+        self.write_short_result(&format!("ok (flaky, {attempts} attempts)"), term::color::YELLOW)
+    }
+
     pub fn write_bench(&mut self) -> io::Result<()> {
         self.write_pretty("bench", term::color::CYAN)
     }
@@ -168,6 +173,40 @@ pub fn write_time_failures(&mut self, state: &ConsoleTestState) -> io::Result<()
         self.write_results(&state.time_failures, "failures (time limit exceeded)")
     }
 
+    pub fn write_flaky_tests(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_plain("\nflaky tests:\n")?;
+        let mut flaky: Vec<_> = state.flaky.iter().collect();
+        flaky.sort_by(|(a, ..), (b, ..)| a.name.as_slice().cmp(b.name.as_slice()));
+
+        // Show the output of the failed attempts, so that the flakiness can be diagnosed.
+        let mut stdouts = String::new();
+        for (desc, _, stdout) in &flaky {
+            if !stdout.is_empty() {
+                stdouts.push_str(&format!("---- {} stdout ----\n", desc.name));
+                stdouts.push_str(&String::from_utf8_lossy(stdout));
+                stdouts.push('\n');
+            }
+        }
+        if !stdouts.is_empty() {
+            self.write_plain("\n")?;
+            self.write_plain(&stdouts)?;
+            self.write_plain("\nflaky tests:\n")?;
+        }
+
+        for (desc, attempts, _) in flaky {
+            self.write_plain(&format!("    {} ({attempts} attempts)\n", desc.name))?;
+        }
+        Ok(())
+    }
+
+    pub fn write_fixture_failures(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_plain("\nfixture failures:\n")?;
+        for failure in &state.fixture_failures {
+            self.write_plain(format!("    {failure}\n"))?;
+        }
+        Ok(())
+    }
+
     fn write_test_name(&mut self, desc: &TestDesc) -> io::Result<()> {
         let name = desc.padded_name(self.max_name_len, desc.name.padding());
         if let Some(test_mode) = desc.test_mode() {
@@ -185,7 +224,12 @@ fn write_discovery_start(&mut self) -> io::Result<()> {
         Ok(())
     }
 
-    fn write_test_discovered(&mut self, desc: &TestDesc, test_type: &str) -> io::Result<()> {
+    fn write_test_discovered(
+        &mut self,
+        desc: &TestDesc,
+        test_type: &str,
+        _shard: Option<usize>,
+    ) -> io::Result<()> {
         self.write_plain(format!("{}: {test_type}\n", desc.name))
     }
 
@@ -244,6 +288,7 @@ fn write_result(
 
         match *result {
             TestResult::TrOk => self.write_ok()?,
+            TestResult::TrFlaky(attempts) => self.write_flaky(attempts)?,
             TestResult::TrFailed | TestResult::TrFailedMsg(_) => self.write_failed()?,
             TestResult::TrIgnored => self.write_ignored(desc.ignore_message)?,
             TestResult::TrBench(ref bs) => {
@@ -265,11 +310,17 @@ fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
         ))
     }
 
+    fn write_fixture_failure(&mut self, failure: &FixtureFailure) -> io::Result<()> {
+        self.write_plain(format!("fixture {} {} ... ", failure.scope(), failure.stage))?;
+        self.write_failed()?;
+        self.write_plain("\n")
+    }
+
     fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         if state.options.display_output {
             self.write_successes(state)?;
         }
-        let success = state.failed == 0;
+        let success = state.is_success();
         if !success {
             if !state.failures.is_empty() {
                 self.write_failures(state)?;
@@ -278,6 +329,14 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
             if !state.time_failures.is_empty() {
                 self.write_time_failures(state)?;
             }
+
+            if !state.fixture_failures.is_empty() {
+                self.write_fixture_failures(state)?;
+            }
+        }
+
+        if !state.flaky.is_empty() {
+            self.write_flaky_tests(state)?;
         }
 
         self.write_plain("\ntest result: ")?;
@@ -296,6 +355,18 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
 
         self.write_plain(s)?;
 
+        if !state.flaky.is_empty() {
+            self.write_plain(format!("; {} flaky", state.flaky.len()))?;
+        }
+
+        let fixture_failures = state.fixture_failures.len();
+        if fixture_failures != 0 {
+            self.write_plain(format!(
+                "; {fixture_failures} fixture{} failed",
+                if fixture_failures == 1 { "" } else { "s" }
+            ))?;
+        }
+
         if let Some(ref exec_time) = state.exec_time {
             let time_str = format!("; finished in {exec_time}");
             self.write_plain(time_str)?;
@@ -0,0 +1,213 @@
+use std::{io, io::prelude::Write};
+
+use super::OutputFormatter;
+use crate::{
+    bench::fmt_bench_samples,
+    console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation},
+    fixtures::FixtureFailure,
+    test_result::TestResult,
+    time,
+    types::TestDesc,
+};
+
+/// Writes test results in the TAP version 13 format. Details about a result are
+/// written as a YAML block following its test line.
+///
+/// See <https://testanything.org/tap-version-13-specification.html>.
+pub(crate) struct TapFormatter<T> {
+    out: OutputLocation<T>,
+    test_number: usize,
+}
+
+impl<T: Write> TapFormatter<T> {
+    pub fn new(out: OutputLocation<T>) -> Self {
+        Self { out, test_number: 0 }
+    }
+
+    #[cfg(test)]
+    pub fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
+    fn write_plain<S: AsRef<str>>(&mut self, s: S) -> io::Result<()> {
+        let s = s.as_ref();
+        self.out.write_all(s.as_bytes())?;
+        self.out.flush()
+    }
+
+    fn write_test_line(&mut self, ok: bool, desc: &TestDesc, directive: &str) -> io::Result<()> {
+        self.test_number += 1;
+        let status = if ok { "ok" } else { "not ok" };
+        // `#` starts a directive, so it has to be escaped in the description.
+        let name = desc.name.as_slice().replace('\\', "\\\\").replace('#', "\\#");
+        self.write_plain(format!("{status} {} - {name}{directive}\n", self.test_number))
+    }
+
+    /// Writes a YAML block with the given fields, skipping it entirely if there are none.
+    fn write_yaml_block(&mut self, fields: &[(&str, YamlValue<'_>)]) -> io::Result<()> {
+        if fields.is_empty() {
+            return Ok(());
+        }
+
+        let mut block = String::from("  ---\n");
+        for (key, value) in fields {
+            match value {
+                YamlValue::Number(n) => block.push_str(&format!("  {key}: {n}\n")),
+                YamlValue::Text(text) => {
+                    // Use a literal block scalar, so that the text doesn't need any escaping.
+                    block.push_str(&format!("  {key}: |-\n"));
+                    for line in text.lines() {
+                        if line.is_empty() {
+                            block.push('\n');
+                        } else {
+                            block.push_str(&format!("    {line}\n"));
+                        }
+                    }
+                }
+            }
+        }
+        block.push_str("  ...\n");
+        self.write_plain(block)
+    }
+}
+
+enum YamlValue<'a> {
+    Number(String),
+    Text(&'a str),
+}
+
+impl<T: Write> OutputFormatter for TapFormatter<T> {
+    fn write_discovery_start(&mut self) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::NotFound, "Not yet implemented!"))
+    }
+
+    fn write_test_discovered(
+        &mut self,
+        _desc: &TestDesc,
+        _test_type: &str,
+        _shard: Option<usize>,
+    ) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::NotFound, "Not yet implemented!"))
+    }
+
+    fn write_discovery_finish(&mut self, _state: &ConsoleTestDiscoveryState) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::NotFound, "Not yet implemented!"))
+    }
+
+    fn write_run_start(&mut self, test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        self.write_plain(format!("TAP version 13\n1..{test_count}\n"))?;
+        if let Some(shuffle_seed) = shuffle_seed {
+            self.write_plain(format!("# shuffle seed: {shuffle_seed}\n"))?;
+        }
+        Ok(())
+    }
+
+    fn write_test_start(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // Tests are only numbered once they finish, so nothing is written here.
+        Ok(())
+    }
+
+    fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
+        self.write_plain(format!(
+            "# test {} has been running for over {} seconds\n",
+            desc.name,
+            time::TEST_WARN_TIMEOUT_S
+        ))
+    }
+
+    fn write_fixture_failure(&mut self, failure: &FixtureFailure) -> io::Result<()> {
+        // Fixtures aren't part of the plan, so their failures are written as diagnostics.
+        let mut diagnostic = String::new();
+        for line in failure.to_string().lines() {
+            diagnostic.push_str(&format!("# {line}\n"));
+        }
+        self.write_plain(diagnostic)
+    }
+
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        exec_time: Option<&time::TestExecTime>,
+        stdout: &[u8],
+        state: &ConsoleTestState,
+    ) -> io::Result<()> {
+        let stdout = String::from_utf8_lossy(stdout);
+        let mut fields = Vec::new();
+        if let Some(exec_time) = exec_time {
+            let duration_ms = exec_time.0.as_secs_f64() * 1000.0;
+            fields.push(("duration_ms", YamlValue::Number(format!("{duration_ms:.3}"))));
+        }
+
+        match *result {
+            TestResult::TrOk | TestResult::TrFlaky(_) => {
+                self.write_test_line(true, desc, "")?;
+                if let TestResult::TrFlaky(attempts) = *result {
+                    fields.push(("attempts", YamlValue::Number(attempts.to_string())));
+                }
+                if state.options.display_output && !stdout.is_empty() {
+                    fields.push(("output", YamlValue::Text(&stdout)));
+                }
+            }
+            TestResult::TrIgnored => {
+                let directive = match desc.ignore_message {
+                    Some(message) => format!(" # SKIP {message}"),
+                    None => " # SKIP".to_string(),
+                };
+                self.write_test_line(true, desc, &directive)?;
+            }
+            TestResult::TrBench(ref bs) => {
+                self.write_test_line(true, desc, "")?;
+                let samples = fmt_bench_samples(bs);
+                return self.write_yaml_block(&[("bench", YamlValue::Text(&samples))]);
+            }
+            TestResult::TrFailed | TestResult::TrFailedMsg(_) | TestResult::TrTimedFail => {
+                self.write_test_line(false, desc, "")?;
+                match result {
+                    TestResult::TrFailedMsg(message) => {
+                        fields.push(("message", YamlValue::Text(message)))
+                    }
+                    TestResult::TrTimedFail => {
+                        fields.push(("message", YamlValue::Text("time limit exceeded")))
+                    }
+                    _ => {}
+                }
+                if !stdout.is_empty() {
+                    fields.push(("output", YamlValue::Text(&stdout)));
+                }
+            }
+        }
+
+        self.write_yaml_block(&fields)
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        let success = state.is_success();
+        let mut summary = format!(
+            "# test result: {}. {} passed; {} failed; {} ignored; {} measured; {} filtered out",
+            if success { "ok" } else { "FAILED" },
+            state.passed,
+            state.failed,
+            state.ignored,
+            state.measured,
+            state.filtered_out
+        );
+        if !state.flaky.is_empty() {
+            summary.push_str(&format!("; {} flaky", state.flaky.len()));
+        }
+        let fixture_failures = state.fixture_failures.len();
+        if fixture_failures != 0 {
+            summary.push_str(&format!(
+                "; {fixture_failures} fixture{} failed",
+                if fixture_failures == 1 { "" } else { "s" }
+            ));
+        }
+        if let Some(ref exec_time) = state.exec_time {
+            summary.push_str(&format!("; finished in {exec_time}"));
+        }
+        summary.push('\n');
+        self.write_plain(summary)?;
+
+        Ok(success)
+    }
+}
@@ -4,6 +4,7 @@
 use crate::{
     bench::fmt_bench_samples,
     console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation},
+    fixtures::FixtureFailure,
     term,
     test_result::TestResult,
     time,
@@ -65,6 +66,17 @@ pub fn write_failed(&mut self, name: &str) -> io::Result<()> {
         self.write_plain("\n")
     }
 
+    pub fn write_flaky(&mut self, name: &str, attempts: usize) -> io::Result<()> {
+        // Like failures, flaky tests get their own line so that they stand out.
+        if self.test_column != 0 {
+            self.write_progress()?;
+        }
+        self.test_count += 1;
+        self.write_plain(format!("{name} --- "))?;
+        self.write_pretty("flaky", term::color::YELLOW)?;
+        self.write_plain(format!(" ({attempts} attempts)\n"))
+    }
+
     pub fn write_ignored(&mut self) -> io::Result<()> {
         self.write_short_result("i", term::color::YELLOW)
     }
@@ -149,6 +161,14 @@ pub fn write_outputs(&mut self, state: &ConsoleTestState) -> io::Result<()> {
         Ok(())
     }
 
+    pub fn write_fixture_failures(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_plain("\nfixture failures:\n")?;
+        for failure in &state.fixture_failures {
+            self.write_plain(format!("    {failure}\n"))?;
+        }
+        Ok(())
+    }
+
     pub fn write_failures(&mut self, state: &ConsoleTestState) -> io::Result<()> {
         self.write_plain("\nfailures:\n")?;
         let mut failures = Vec::new();
@@ -192,7 +212,12 @@ fn write_discovery_start(&mut self) -> io::Result<()> {
         Ok(())
     }
 
-    fn write_test_discovered(&mut self, desc: &TestDesc, test_type: &str) -> io::Result<()> {
+    fn write_test_discovered(
+        &mut self,
+        desc: &TestDesc,
+        test_type: &str,
+        _shard: Option<usize>,
+    ) -> io::Result<()> {
         self.write_plain(format!("{}: {test_type}\n", desc.name))
     }
 
@@ -233,6 +258,7 @@ fn write_result(
     ) -> io::Result<()> {
         match *result {
             TestResult::TrOk => self.write_ok(),
+            TestResult::TrFlaky(attempts) => self.write_flaky(desc.name.as_slice(), attempts),
             TestResult::TrFailed | TestResult::TrFailedMsg(_) | TestResult::TrTimedFail => {
                 self.write_failed(desc.name.as_slice())
             }
@@ -255,14 +281,22 @@ fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
         ))
     }
 
+    fn write_fixture_failure(&mut self, _failure: &FixtureFailure) -> io::Result<()> {
+        // Fixture failures are only listed at the end of the run.
+        Ok(())
+    }
+
     fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         if state.options.display_output {
             self.write_outputs(state)?;
         }
-        let success = state.failed == 0;
-        if !success {
+        let success = state.is_success();
+        if state.failed != 0 {
             self.write_failures(state)?;
         }
+        if !state.fixture_failures.is_empty() {
+            self.write_fixture_failures(state)?;
+        }
 
         self.write_plain("\ntest result: ")?;
 
@@ -280,6 +314,18 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
 
         self.write_plain(s)?;
 
+        if !state.flaky.is_empty() {
+            self.write_plain(format!("; {} flaky", state.flaky.len()))?;
+        }
+
+        let fixture_failures = state.fixture_failures.len();
+        if fixture_failures != 0 {
+            self.write_plain(format!(
+                "; {fixture_failures} fixture{} failed",
+                if fixture_failures == 1 { "" } else { "s" }
+            ))?;
+        }
+
         if let Some(ref exec_time) = state.exec_time {
             let time_str = format!("; finished in {exec_time}");
             self.write_plain(time_str)?;
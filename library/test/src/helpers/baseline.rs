@@ -0,0 +1,129 @@
+//! Benchmark baselines, to compare benchmark results against earlier runs.
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::bench::{BenchChange, BENCH_SAMPLES};
+use crate::stats::Summary;
+
+const BASELINE_HEADER: &str = "# libtest benchmark baseline v1";
+
+/// Mean and variance of the samples of the time per iteration of a benchmark,
+/// in nanoseconds.
+#[derive(Clone, PartialEq, Debug, Copy)]
+struct BaselineEntry {
+    mean: f64,
+    var: f64,
+}
+
+/// Results of the benchmarks of a test run, by benchmark name.
+///
+/// Baselines are saved as text files with one benchmark per line, holding
+/// the mean and variance of its samples and its name, separated by tabs.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Baseline(BTreeMap<String, BaselineEntry>);
+
+impl Baseline {
+    pub fn new() -> Baseline {
+        Baseline(BTreeMap::new())
+    }
+
+    pub fn read(path: &Path) -> io::Result<Baseline> {
+        let contents = fs::read_to_string(path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("failed to read benchmark baseline {}: {e}", path.display()),
+            )
+        })?;
+        let mut lines = contents.lines().enumerate();
+        if lines.next().map(|(_, header)| header) != Some(BASELINE_HEADER) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} is not a benchmark baseline", path.display()),
+            ));
+        }
+
+        let mut baseline = Baseline::new();
+        for (i, line) in lines {
+            let mut fields = line.splitn(3, '\t');
+            let entry = match (fields.next(), fields.next(), fields.next()) {
+                (Some(mean), Some(var), Some(name)) => match (mean.parse(), var.parse()) {
+                    (Ok(mean), Ok(var)) => Some((name, BaselineEntry { mean, var })),
+                    _ => None,
+                },
+                _ => None,
+            };
+            let Some((name, entry)) = entry else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}:{}: malformed benchmark baseline entry", path.display(), i + 1),
+                ));
+            };
+            baseline.0.insert(name.to_owned(), entry);
+        }
+
+        Ok(baseline)
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut contents = format!("{BASELINE_HEADER}\n");
+        for (name, entry) in &self.0 {
+            contents.push_str(&format!("{}\t{}\t{name}\n", entry.mean, entry.var));
+        }
+        fs::write(path, contents)
+    }
+
+    /// Insert the result of the benchmark called `name`, given by the summary of
+    /// its samples before they were winsorized.
+    pub fn insert(&mut self, name: &str, summ: &Summary) {
+        self.0.insert(name.to_owned(), BaselineEntry { mean: summ.mean, var: summ.var });
+    }
+
+    /// Compare the result of the benchmark called `name` to its result in the
+    /// baseline, given by the summary of its samples before they were winsorized.
+    ///
+    /// The confidence interval of the difference of the means is Welch's t
+    /// interval. Winsorizing would shrink the variance of the samples, and so
+    /// the interval, which is why both sides use the samples as measured. Each
+    /// sample is treated as an independent measurement, even though it is
+    /// itself the mean over many iterations.
+    pub fn compare(&self, name: &str, summ: &Summary) -> Option<BenchChange> {
+        let baseline = self.0.get(name)?;
+        if baseline.mean <= 0.0 {
+            return None;
+        }
+
+        let n = BENCH_SAMPLES as f64;
+        let diff = summ.mean - baseline.mean;
+        let (old_var, new_var) = (baseline.var / n, summ.var / n);
+        let std_err = (old_var + new_var).sqrt();
+        let margin = if std_err > 0.0 {
+            // Welch-Satterthwaite approximation of the degrees of freedom.
+            let df =
+                (old_var + new_var).powi(2) / ((old_var.powi(2) + new_var.powi(2)) / (n - 1.0));
+            t_975(df) * std_err
+        } else {
+            0.0
+        };
+        let pct = |ns: f64| ns / baseline.mean * 100.0;
+        Some(BenchChange {
+            pct: pct(diff),
+            ci_low: pct(diff - margin),
+            ci_high: pct(diff + margin),
+        })
+    }
+}
+
+/// The 97.5th percentile of Student's t-distribution with `df` degrees of
+/// freedom, using the Cornish-Fisher expansion around the normal distribution.
+/// This is accurate to well within 0.1% for the degrees of freedom that
+/// `BENCH_SAMPLES` samples give.
+fn t_975(df: f64) -> f64 {
+    // The 97.5th percentile of the standard normal distribution.
+    let z: f64 = 1.959964;
+    let (z3, z5, z7) = (z.powi(3), z.powi(5), z.powi(7));
+    z + (z3 + z) / (4.0 * df)
+        + (5.0 * z5 + 16.0 * z3 + 3.0 * z) / (96.0 * df.powi(2))
+        + (3.0 * z7 + 19.0 * z5 + 17.0 * z3 - 15.0 * z) / (384.0 * df.powi(3))
+}
@@ -0,0 +1,151 @@
+//! Capture of the output of a test at the level of file descriptors, which,
+//! unlike `io::set_output_capture`, also catches output of threads that don't
+//! inherit the capture, raw writes to stdout and stderr, and output of C code.
+
+use std::io::{self, Write};
+use std::thread;
+
+#[cfg(unix)]
+use std::io::Read;
+#[cfg(unix)]
+use std::os::fd::{AsFd, AsRawFd, FromRawFd, OwnedFd};
+#[cfg(unix)]
+use std::sync::Mutex;
+
+/// The original stdout and stderr of the process while a capture is running.
+#[cfg(unix)]
+static SAVED_FDS: Mutex<Option<(OwnedFd, OwnedFd)>> = Mutex::new(None);
+
+/// Redirects the stdout and stderr of the whole process to a pipe until `finish` is called.
+///
+/// This captures everything the process writes in the meantime, so only one test at a
+/// time can be captured.
+pub(crate) struct FdCapture {
+    reader: thread::JoinHandle<Vec<u8>>,
+}
+
+impl FdCapture {
+    #[cfg(unix)]
+    pub(crate) fn start() -> io::Result<FdCapture> {
+        let (read, write) = pipe()?;
+        // Output buffered before the redirection isn't part of the test's output.
+        io::stdout().flush()?;
+        let saved_stdout = io::stdout().as_fd().try_clone_to_owned()?;
+        let saved_stderr = io::stderr().as_fd().try_clone_to_owned()?;
+
+        let mut read = std::fs::File::from(read);
+        let reader = thread::spawn(move || {
+            let mut output = Vec::new();
+            let _ = read.read_to_end(&mut output);
+            output
+        });
+
+        *SAVED_FDS.lock().unwrap_or_else(|e| e.into_inner()) = Some((saved_stdout, saved_stderr));
+        let redirected =
+            dup2(&write, libc::STDOUT_FILENO).and_then(|()| dup2(&write, libc::STDERR_FILENO));
+        // Once stdout and stderr are restored, no write end of the pipe is left open,
+        // which ends the reader thread.
+        drop(write);
+        if let Err(e) = redirected {
+            restore();
+            let _ = reader.join();
+            return Err(e);
+        }
+
+        Ok(FdCapture { reader })
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn start() -> io::Result<FdCapture> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "capturing the output of tests at the fd level is not supported on this platform",
+        ))
+    }
+
+    /// Stops the capture and returns everything written to stdout and stderr since it started.
+    pub(crate) fn finish(self) -> Vec<u8> {
+        let _ = io::stdout().flush();
+        restore();
+        self.reader.join().unwrap_or_default()
+    }
+}
+
+/// Points stdout and stderr back to where they were before the running capture, if any.
+///
+/// This doesn't flush stdout, so it can't block on a test that's stuck while holding
+/// the lock of stdout.
+pub(crate) fn restore() {
+    #[cfg(unix)]
+    if let Some((stdout, stderr)) = SAVED_FDS.lock().unwrap_or_else(|e| e.into_inner()).take() {
+        let _ = dup2(&stdout, libc::STDOUT_FILENO);
+        let _ = dup2(&stderr, libc::STDERR_FILENO);
+    }
+}
+
+/// Creates a pipe whose ends are closed on `exec`, so that processes spawned by the test
+/// inherit the redirected stdout and stderr, but not the pipe: a child holding the write end
+/// would keep the reader thread from finishing.
+#[cfg(any(
+    target_os = "android",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+fn pipe() -> io::Result<(OwnedFd, OwnedFd)> {
+    let mut fds = [0; 2];
+    // SAFETY: `fds` has room for the two file descriptors written by `pipe2`.
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: `pipe2` succeeded, so both file descriptors are open and owned by nobody else.
+    Ok(unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) })
+}
+
+/// Creates a pipe whose ends are closed on `exec`.
+///
+/// Without `pipe2`, a process spawned by another thread between `pipe` and `fcntl` can still
+/// inherit the pipe.
+#[cfg(all(
+    unix,
+    not(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "linux",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ))
+))]
+fn pipe() -> io::Result<(OwnedFd, OwnedFd)> {
+    let mut fds = [0; 2];
+    // SAFETY: `fds` has room for the two file descriptors written by `pipe`.
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: `pipe` succeeded, so both file descriptors are open and owned by nobody else.
+    let (read, write) = unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+    for fd in [&read, &write] {
+        // SAFETY: `fd` is an open file descriptor.
+        if unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok((read, write))
+}
+
+#[cfg(unix)]
+fn dup2(fd: &OwnedFd, target: libc::c_int) -> io::Result<()> {
+    loop {
+        // SAFETY: `fd` is an open file descriptor, and `target` is one of the standard ones.
+        if unsafe { libc::dup2(fd.as_raw_fd(), target) } != -1 {
+            return Ok(());
+        }
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+}
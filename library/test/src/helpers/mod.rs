@@ -1,6 +1,10 @@
 //! Module with common helpers not directly related to tests
 //! but used in `libtest`.
 
+pub mod baseline;
 pub mod concurrency;
+pub mod fd_capture;
 pub mod metrics;
+pub mod partition;
+pub mod run_state;
 pub mod shuffle;
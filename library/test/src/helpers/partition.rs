@@ -0,0 +1,43 @@
+use crate::options::{Partition, PartitionStrategy};
+use crate::types::TestDescAndFn;
+
+/// Keeps only the tests that belong to the shard selected by `partition`.
+pub fn partition_tests(partition: Partition, tests: &mut Vec<TestDescAndFn>) {
+    let shards = assign_shards(partition, tests);
+    let mut shards = shards.into_iter();
+    tests.retain(|_| shards.next() == Some(partition.shard));
+}
+
+/// Returns the 1-based shard of every test, out of `partition.total` shards.
+///
+/// The assignment only depends on the names of the tests, so every machine
+/// running the same test binary with the same filters computes the same split.
+pub(crate) fn assign_shards(partition: Partition, tests: &[TestDescAndFn]) -> Vec<usize> {
+    let total = partition.total as u64;
+    match partition.strategy {
+        PartitionStrategy::Hash => tests
+            .iter()
+            .map(|test| (fnv1a(test.desc.name.as_slice()) % total) as usize + 1)
+            .collect(),
+        PartitionStrategy::Count => {
+            // Deal the tests out round-robin in name order, so that shards differ
+            // in size by at most one test.
+            let mut by_name: Vec<usize> = (0..tests.len()).collect();
+            by_name.sort_by_key(|&i| tests[i].desc.name.as_slice());
+            let mut shards = vec![0; tests.len()];
+            for (position, i) in by_name.into_iter().enumerate() {
+                shards[i] = (position as u64 % total) as usize + 1;
+            }
+            shards
+        }
+    }
+}
+
+// `DefaultHasher` isn't guaranteed to be stable across Rust releases, but machines
+// sharing a test run may use binaries built by different toolchains.
+fn fnv1a(s: &str) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    s.bytes().fold(OFFSET_BASIS, |hash, byte| (hash ^ byte as u64).wrapping_mul(PRIME))
+}
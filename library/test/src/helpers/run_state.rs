@@ -0,0 +1,180 @@
+//! Outcomes and durations of tests saved across runs, to rerun or prioritize
+//! the tests that failed last time.
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::options::TestOrder;
+use crate::types::{TestDescAndFn, TestId};
+
+const RUN_STATE_HEADER: &str = "# libtest run state v1";
+
+/// How a test ended the last time it was run.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum TestOutcome {
+    Failed,
+    Flaky,
+    Passed,
+}
+
+impl TestOutcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            TestOutcome::Failed => "failed",
+            TestOutcome::Flaky => "flaky",
+            TestOutcome::Passed => "passed",
+        }
+    }
+
+    fn parse(outcome: &str) -> Option<TestOutcome> {
+        match outcome {
+            "failed" => Some(TestOutcome::Failed),
+            "flaky" => Some(TestOutcome::Flaky),
+            "passed" => Some(TestOutcome::Passed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct TestRecord {
+    outcome: TestOutcome,
+    duration: Duration,
+}
+
+/// The last recorded outcome and duration of each test, by test name.
+///
+/// The state is saved as a text file with one test per line, holding its
+/// outcome, its duration in seconds and its name, separated by tabs. Line
+/// breaks and backslashes in names are escaped with a backslash.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct RunState(BTreeMap<String, TestRecord>);
+
+impl RunState {
+    pub fn new() -> RunState {
+        RunState(BTreeMap::new())
+    }
+
+    /// Reads the state saved at `path`, or returns an empty state if there's no such file yet.
+    pub fn read(path: &Path) -> io::Result<RunState> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(RunState::new()),
+            Err(e) => {
+                return Err(io::Error::new(
+                    e.kind(),
+                    format!("failed to read test run state {}: {e}", path.display()),
+                ));
+            }
+        };
+        let mut lines = contents.lines().enumerate();
+        if lines.next().map(|(_, header)| header) != Some(RUN_STATE_HEADER) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} is not a test run state file", path.display()),
+            ));
+        }
+
+        let mut state = RunState::new();
+        for (i, line) in lines {
+            let mut fields = line.splitn(3, '\t');
+            let record = match (fields.next(), fields.next(), fields.next()) {
+                (Some(outcome), Some(secs), Some(name)) => {
+                    let outcome = TestOutcome::parse(outcome);
+                    let duration =
+                        secs.parse().ok().and_then(|s| Duration::try_from_secs_f64(s).ok());
+                    match (outcome, duration, unescape_name(name)) {
+                        (Some(outcome), Some(duration), Some(name)) => {
+                            Some((name, TestRecord { outcome, duration }))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            let Some((name, record)) = record else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}:{}: malformed test run state entry", path.display(), i + 1),
+                ));
+            };
+            state.0.insert(name, record);
+        }
+
+        Ok(state)
+    }
+
+    /// Saves the state to `path`. The file is replaced atomically, so that a run
+    /// that is interrupted while saving doesn't leave a truncated state behind.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut contents = format!("{RUN_STATE_HEADER}\n");
+        for (name, record) in &self.0 {
+            let outcome = record.outcome.as_str();
+            let secs = record.duration.as_secs_f64();
+            contents.push_str(&format!("{outcome}\t{secs}\t{}\n", escape_name(name)));
+        }
+        fs::write_atomic(path, contents)
+    }
+
+    /// Record how the test called `name` ended, replacing its previous record.
+    pub fn record(&mut self, name: &str, outcome: TestOutcome, duration: Duration) {
+        self.0.insert(name.to_owned(), TestRecord { outcome, duration });
+    }
+
+    /// Whether the test called `name` failed the last time it was run.
+    pub fn failed(&self, name: &str) -> bool {
+        self.0.get(name).is_some_and(|record| record.outcome == TestOutcome::Failed)
+    }
+
+    /// Sorts `tests` by their last recorded outcome or duration. The sort is stable, so
+    /// tests that rank the same stay in their previous, possibly shuffled, order.
+    pub fn order_tests(&self, order: TestOrder, tests: &mut [(TestId, TestDescAndFn)]) {
+        let record = |test: &(TestId, TestDescAndFn)| self.0.get(test.1.desc.name.as_slice());
+        match order {
+            // Tests without a record haven't failed yet, so they rank like passed tests.
+            TestOrder::FailedFirst => tests.sort_by_key(|test| {
+                record(test).map_or(TestOutcome::Passed, |record| record.outcome)
+            }),
+            // Tests without a record may be slow, so they run before all others.
+            TestOrder::SlowestFirst => tests.sort_by_key(|test| {
+                Reverse(record(test).map_or(Duration::MAX, |record| record.duration))
+            }),
+        }
+    }
+}
+
+/// Escapes the line breaks in a test name, which would otherwise end its entry,
+/// and the backslashes that introduce the escapes.
+fn escape_name(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Reverses `escape_name`, or returns `None` if `name` has an invalid escape.
+fn unescape_name(name: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(name.len());
+    let mut chars = name.chars();
+    while let Some(c) = chars.next() {
+        unescaped.push(match c {
+            '\\' => match chars.next()? {
+                '\\' => '\\',
+                'n' => '\n',
+                'r' => '\r',
+                _ => return None,
+            },
+            c => c,
+        });
+    }
+    Some(unescaped)
+}
@@ -22,13 +22,18 @@
 #![feature(staged_api)]
 #![feature(process_exitcode_internals)]
 #![feature(panic_can_unwind)]
+#![feature(fs_write_atomic)]
 #![feature(test)]
 #![allow(internal_features)]
 
 // Public reexports
-pub use self::bench::{black_box, Bencher};
-pub use self::console::run_tests_console;
-pub use self::options::{ColorConfig, Options, OutputFormat, RunIgnored, ShouldPanic};
+pub use self::bench::{black_box, BenchOptions, Bencher};
+pub use self::console::{run_tests_console, run_tests_console_with_fixtures};
+pub use self::fixtures::TestFixture;
+pub use self::options::{
+    Capture, ColorConfig, Isolation, Options, OutputFormat, Partition, PartitionStrategy,
+    RunIgnored, ShouldPanic, TestOrder,
+};
 pub use self::types::TestName::*;
 pub use self::types::*;
 pub use self::ColorConfig::*;
@@ -70,6 +75,7 @@ pub mod test {
 mod cli;
 mod console;
 mod event;
+mod fixtures;
 mod formatters;
 mod helpers;
 mod options;
@@ -84,7 +90,11 @@ pub mod test {
 
 use core::any::Any;
 use event::{CompletedTest, TestEvent};
+use fixtures::ActiveFixtures;
 use helpers::concurrency::get_concurrency;
+use helpers::fd_capture::{self, FdCapture};
+use helpers::partition::partition_tests;
+use helpers::run_state::RunState;
 use helpers::shuffle::{get_shuffle_seed, shuffle_tests};
 use options::RunStrategy;
 use test_result::*;
@@ -99,6 +109,29 @@ pub mod test {
 // The default console test runner. It accepts the command line
 // arguments and a vector of test_descs.
 pub fn test_main(args: &[String], tests: Vec<TestDescAndFn>, options: Option<Options>) {
+    test_main_with_fixtures(args, tests, &[], options)
+}
+
+/// Like `test_main`, but also sets up and tears down the given fixtures around
+/// the tests in their modules.
+pub fn test_main_with_fixtures(
+    args: &[String],
+    tests: Vec<TestDescAndFn>,
+    fixtures: &[TestFixture],
+    options: Option<Options>,
+) {
+    test_main_impl(args, tests, fixtures, options, false)
+}
+
+// `spawns_children` is set by the entry points that can run a single test in a child
+// process, which `--isolate=process` relies on.
+fn test_main_impl(
+    args: &[String],
+    tests: Vec<TestDescAndFn>,
+    fixtures: &[TestFixture],
+    options: Option<Options>,
+    spawns_children: bool,
+) {
     let mut opts = match cli::parse_opts(args) {
         Some(Ok(o)) => o,
         Some(Err(msg)) => {
@@ -110,6 +143,16 @@ pub fn test_main(args: &[String], tests: Vec<TestDescAndFn>, options: Option<Opt
     if let Some(options) = options {
         opts.options = options;
     }
+    if let Err(msg) = check_isolation(&opts, spawns_children) {
+        eprintln!("error: {msg}");
+        process::exit(ERROR_EXIT_CODE);
+    }
+    if env::var_os(SECONDARY_TEST_INVOKER_VAR).is_some() {
+        // Only the static entry points know how to run a single test in a child process.
+        // Bail out instead of running the whole suite again in every child.
+        eprintln!("error: this test harness can't run tests in separate processes");
+        process::exit(ERROR_EXIT_CODE);
+    }
     if opts.list {
         if let Err(e) = console::list_tests_console(&opts, tests) {
             eprintln!("error: io error when listing tests: {e:?}");
@@ -139,7 +182,7 @@ pub fn test_main(args: &[String], tests: Vec<TestDescAndFn>, options: Option<Opt
             });
             panic::set_hook(hook);
         }
-        let res = console::run_tests_console(&opts, tests);
+        let res = console::run_tests_console_with_fixtures(&opts, tests, fixtures);
         // Prevent Valgrind from reporting reachable blocks in users' unit tests.
         drop(panic::take_hook());
         match res {
@@ -159,9 +202,17 @@ pub fn test_main(args: &[String], tests: Vec<TestDescAndFn>, options: Option<Opt
 /// This is the entry point for the main function generated by `rustc --test`
 /// when panic=unwind.
 pub fn test_main_static(tests: &[&TestDescAndFn]) {
+    test_main_static_with_fixtures(tests, &[])
+}
+
+/// Like `test_main_static`, but also sets up and tears down the given fixtures
+/// around the tests in their modules.
+pub fn test_main_static_with_fixtures(tests: &[&TestDescAndFn], fixtures: &[TestFixture]) {
+    run_spawned_secondary_test_if_requested(tests, fixtures);
+
     let args = env::args().collect::<Vec<_>>();
     let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
-    test_main(&args, owned_tests, None)
+    test_main_impl(&args, owned_tests, fixtures, None, true)
 }
 
 /// A variant optimized for invocation with a static test vector.
@@ -173,8 +224,39 @@ pub fn test_main_static(tests: &[&TestDescAndFn]) {
 /// This is the entry point for the main function generated by `rustc --test`
 /// when panic=abort.
 pub fn test_main_static_abort(tests: &[&TestDescAndFn]) {
-    // If we're being run in SpawnedSecondary mode, run the test here. run_test
-    // will then exit the process.
+    test_main_static_abort_with_fixtures(tests, &[])
+}
+
+/// Like `test_main_static_abort`, but also sets up and tears down the given fixtures
+/// around the tests in their modules, in the child process of each test.
+pub fn test_main_static_abort_with_fixtures(tests: &[&TestDescAndFn], fixtures: &[TestFixture]) {
+    run_spawned_secondary_test_if_requested(tests, fixtures);
+
+    let args = env::args().collect::<Vec<_>>();
+    let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
+    let options = Some(Options::new().panic_abort(true));
+    test_main_impl(&args, owned_tests, fixtures, options, true)
+}
+
+/// Checks that the tests can run with the isolation requested by `--isolate`.
+fn check_isolation(opts: &TestOpts, spawns_children: bool) -> Result<(), String> {
+    if opts.isolate == Isolation::Process && !spawns_children {
+        // A child process would start the whole harness again instead of running the test.
+        return Err("--isolate=process is only supported by test harnesses generated by \
+                    `rustc --test`"
+            .into());
+    }
+
+    Ok(())
+}
+
+/// If we're being run in SpawnedSecondary mode, run the requested test here.
+/// This then exits the process instead of returning.
+///
+/// Test binaries spawn themselves in this mode when built with panic=abort, or
+/// when `--isolate=process` is passed. The fixtures of the test are set up and
+/// torn down in the child process.
+fn run_spawned_secondary_test_if_requested(tests: &[&TestDescAndFn], fixtures: &[TestFixture]) {
     if let Ok(name) = env::var(SECONDARY_TEST_INVOKER_VAR) {
         env::remove_var(SECONDARY_TEST_INVOKER_VAR);
 
@@ -196,17 +278,20 @@ pub fn test_main_static_abort(tests: &[&TestDescAndFn]) {
                 if runnable_test.is_dynamic() {
                     panic!("only static tests are supported");
                 }
-                run_test_in_spawned_subprocess(desc, runnable_test);
+                let (fixtures, failures) = ActiveFixtures::set_up(fixtures, [&desc].into_iter());
+                if let Some(failure) = failures.first() {
+                    // The parent only sees the exit status, so the failure ends up in
+                    // the output of the test.
+                    eprintln!("{failure}");
+                    process::abort();
+                }
+                run_test_in_spawned_subprocess(desc, runnable_test, fixtures);
             }
             Runnable::Bench(_) => {
                 panic!("benchmarks should not be executed into child processes")
             }
         }
     }
-
-    let args = env::args().collect::<Vec<_>>();
-    let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
-    test_main(&args, owned_tests, Some(Options::new().panic_abort(true)))
 }
 
 /// Clones static values for putting into a dynamic vector, which test_main()
@@ -221,6 +306,16 @@ fn make_owned_test(test: &&TestDescAndFn) -> TestDescAndFn {
     }
 }
 
+/// Clones a static test, so that it can be run again. Dynamic tests can't be cloned.
+fn clone_static_test(test: &TestDescAndFn) -> Option<TestDescAndFn> {
+    let testfn = match test.testfn {
+        StaticTestFn(f) => StaticTestFn(f),
+        StaticBenchAsTestFn(f) => StaticBenchAsTestFn(f),
+        _ => return None,
+    };
+    Some(TestDescAndFn { desc: test.desc.clone(), testfn })
+}
+
 /// Invoked when unit tests terminate. Returns `Result::Err` if the test is
 /// considered a failure. By default, invokes `report()` and checks for a `0`
 /// result.
@@ -261,6 +356,36 @@ fn total_len(&self) -> usize {
 pub fn run_tests<F>(
     opts: &TestOpts,
     tests: Vec<TestDescAndFn>,
+    notify_about_test_event: F,
+) -> io::Result<()>
+where
+    F: FnMut(TestEvent) -> io::Result<()>,
+{
+    run_tests_with_fixtures(opts, tests, &[], notify_about_test_event)
+}
+
+/// Like `run_tests`, but also sets up the given fixtures before running the tests
+/// in their modules, and tears them down once all tests finished.
+pub fn run_tests_with_fixtures<F>(
+    opts: &TestOpts,
+    tests: Vec<TestDescAndFn>,
+    fixtures: &[TestFixture],
+    notify_about_test_event: F,
+) -> io::Result<()>
+where
+    F: FnMut(TestEvent) -> io::Result<()>,
+{
+    let run_state = read_run_state(opts)?;
+    run_tests_with_run_state(opts, tests, fixtures, &run_state, notify_about_test_event)
+}
+
+/// Like `run_tests_with_fixtures`, with the state of the last run already read from
+/// `--state-file`.
+pub(crate) fn run_tests_with_run_state<F>(
+    opts: &TestOpts,
+    tests: Vec<TestDescAndFn>,
+    fixtures: &[TestFixture],
+    run_state: &RunState,
     mut notify_about_test_event: F,
 ) -> io::Result<()>
 where
@@ -272,6 +397,26 @@ pub fn run_tests<F>(
 
     struct RunningTest {
         join_handle: Option<thread::JoinHandle<()>>,
+        hard_timeout: Option<HardTimeout>,
+    }
+
+    // The hard time limit of a test that runs in-process. Subprocesses are killed by
+    // `spawn_test_subprocess` itself, but threads can't be, so a test that exceeds
+    // its limit ends the whole run.
+    struct HardTimeout {
+        desc: TestDesc,
+        timeout: Duration,
+        deadline: Instant,
+    }
+
+    impl HardTimeout {
+        fn into_event(self, id: TestId) -> TestEvent {
+            let mut message = time::hard_timeout_message(self.timeout);
+            message.push_str("the test can't be stopped in-process, so the run was aborted\n");
+            let completed_test =
+                CompletedTest::new(id, self.desc, TrTimedFail, None, message.into_bytes());
+            TestEvent::TeResult(completed_test)
+        }
     }
 
     impl RunningTest {
@@ -290,6 +435,64 @@ fn join(self, completed_test: &mut CompletedTest) {
     // Use a deterministic hasher
     type TestMap = HashMap<TestId, RunningTest, BuildHasherDefault<DefaultHasher>>;
 
+    // Failed tests are run again, up to `max` times. Only static tests can be retried,
+    // since running a dynamic test consumes it. The output of the failed attempts is kept
+    // and reported along with the last one.
+    struct Retries {
+        max: usize,
+        tests: HashMap<TestId, TestDescAndFn, BuildHasherDefault<DefaultHasher>>,
+        attempts: HashMap<TestId, usize, BuildHasherDefault<DefaultHasher>>,
+        output: HashMap<TestId, Vec<u8>, BuildHasherDefault<DefaultHasher>>,
+    }
+
+    impl Retries {
+        fn new(max: usize, tests: &VecDeque<(TestId, TestDescAndFn)>) -> Retries {
+            let tests = if max == 0 {
+                HashMap::default()
+            } else {
+                tests
+                    .iter()
+                    .filter_map(|(id, test)| Some((*id, clone_static_test(test)?)))
+                    .collect()
+            };
+            Retries { max, tests, attempts: HashMap::default(), output: HashMap::default() }
+        }
+
+        // Whether the test has run before, and so has already been announced.
+        fn is_retry(&self, id: TestId) -> bool {
+            self.attempts.contains_key(&id)
+        }
+
+        // Returns the test to run again if it failed and may be retried. Marks tests
+        // that only passed after being retried as flaky.
+        fn retry(&mut self, completed_test: &mut CompletedTest) -> Option<(TestId, TestDescAndFn)> {
+            let id = completed_test.id;
+            let attempts = self.attempts.get(&id).copied().unwrap_or(1);
+            match completed_test.result {
+                TrFailed | TrFailedMsg(_) | TrTimedFail if attempts <= self.max => {
+                    if let Some(test) = self.tests.get(&id).and_then(clone_static_test) {
+                        self.attempts.insert(id, attempts + 1);
+                        let output = self.output.entry(id).or_default();
+                        output.extend(format!("---- attempt {attempts} failed ----\n").bytes());
+                        output.append(&mut completed_test.stdout);
+                        if let TrFailedMsg(ref msg) = completed_test.result {
+                            output.extend(format!("note: {msg}\n").bytes());
+                        }
+                        return Some((id, test));
+                    }
+                }
+                TrOk if attempts > 1 => completed_test.result = TrFlaky(attempts),
+                _ => {}
+            }
+            if let Some(mut output) = self.output.remove(&id) {
+                output.extend(format!("---- attempt {attempts} ----\n").bytes());
+                output.append(&mut completed_test.stdout);
+                completed_test.stdout = output;
+            }
+            None
+        }
+    }
+
     struct TimeoutEntry {
         id: TestId,
         desc: TestDesc,
@@ -301,6 +504,7 @@ struct TimeoutEntry {
     let mut filtered = FilteredTests { tests: Vec::new(), benches: Vec::new(), next_id: 0 };
 
     let mut filtered_tests = filter_tests(opts, tests);
+    retain_failed_tests(opts, run_state, &mut filtered_tests);
     if !opts.bench_benchmarks {
         filtered_tests = convert_benchmarks_to_tests(filtered_tests);
     }
@@ -334,21 +538,92 @@ struct TimeoutEntry {
     if let Some(shuffle_seed) = shuffle_seed {
         shuffle_tests(shuffle_seed, &mut remaining);
     }
+    if let Some(order) = opts.order {
+        run_state.order_tests(order, &mut remaining);
+    }
     // Store the tests in a VecDeque so we can efficiently remove the first element to run the
     // tests in the order they were passed (unless shuffled).
     let mut remaining = VecDeque::from(remaining);
+    let mut retries = Retries::new(opts.retries, &remaining);
     let mut pending = 0;
 
     let (tx, rx) = channel::<CompletedTest>();
-    let run_strategy = if opts.options.panic_abort && !opts.force_run_in_process {
+    let spawn_tests = opts.options.panic_abort || opts.isolate == Isolation::Process;
+    let run_strategy = if spawn_tests && !opts.force_run_in_process {
         RunStrategy::SpawnPrimary
     } else {
         RunStrategy::InProcess
     };
 
+    // The output of spawned tests is always captured at the fd level, by their parent. Tests
+    // that run in-process redirect the fds of the whole process, so they run one at a time.
+    let fd_capture_here = !opts.nocapture
+        && opts.capture == Capture::Fd
+        && matches!(run_strategy, RunStrategy::InProcess);
+    if fd_capture_here && !cfg!(unix) {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "--capture=fd is only supported on this platform when tests run in separate \
+             processes (with panic=abort or --isolate=process)",
+        ));
+    }
+    let concurrency = if fd_capture_here { 1 } else { concurrency };
+
+    // Fixtures are set up in the process that runs their tests: here for benchmarks and
+    // tests that run in-process, or in each child process for tests that are spawned.
+    let run_tests_here = matches!(run_strategy, RunStrategy::InProcess) && opts.run_tests;
+    let tests_here = remaining.iter().filter(|_| run_tests_here).chain(&filtered.benches);
+    let (active_fixtures, failures) = ActiveFixtures::set_up(
+        fixtures,
+        tests_here.map(|(_, test)| &test.desc).filter(|desc| !desc.ignore),
+    );
+    for failure in failures {
+        notify_about_test_event(TestEvent::TeFixtureFailed(failure))?;
+    }
+
+    // The tests in scope of a fixture whose setup failed can't run, so they fail right away.
+    let setup_failed = |(_, test): &(TestId, TestDescAndFn)| {
+        !test.desc.ignore && active_fixtures.failed_setup(test.desc.name.as_slice()).is_some()
+    };
+    let (failed_tests, tests): (VecDeque<_>, _) =
+        remaining.into_iter().partition(|test| run_tests_here && setup_failed(test));
+    let (failed_benches, benches) = filtered.benches.into_iter().partition(setup_failed);
+    remaining = tests;
+    filtered.benches = benches;
+    for (id, test) in failed_tests.into_iter().chain(failed_benches) {
+        let scope = active_fixtures.failed_setup(test.desc.name.as_slice()).unwrap();
+        let result = TrFailedMsg(format!("setup of fixture `{scope}` failed"));
+        notify_about_test_event(TestEvent::TeWait(test.desc.clone()))?;
+        let completed_test = CompletedTest::new(id, test.desc, result, None, Vec::new());
+        notify_about_test_event(TestEvent::TeResult(completed_test))?;
+    }
+
     let mut running_tests: TestMap = HashMap::default();
     let mut timeout_queue: VecDeque<TimeoutEntry> = VecDeque::new();
 
+    let hard_timeout_for = |desc: &TestDesc| match run_strategy {
+        RunStrategy::InProcess => time::get_hard_timeout(desc, opts.test_timeout).map(|timeout| {
+            HardTimeout { desc: desc.clone(), timeout, deadline: Instant::now() + timeout }
+        }),
+        RunStrategy::SpawnPrimary => None,
+    };
+
+    fn calc_hard_timeout(running_tests: &TestMap) -> Option<Duration> {
+        let next_deadline = running_tests
+            .values()
+            .filter_map(|running_test| running_test.hard_timeout.as_ref())
+            .map(|hard_timeout| hard_timeout.deadline)
+            .min()?;
+        Some(next_deadline.saturating_duration_since(Instant::now()))
+    }
+
+    fn get_hard_timed_out_test(running_tests: &TestMap) -> Option<TestId> {
+        let now = Instant::now();
+        running_tests.iter().find_map(|(&id, running_test)| {
+            running_test.hard_timeout.as_ref().filter(|t| now >= t.deadline).map(|_| id)
+        })
+    }
+
     fn get_timed_out_tests(
         running_tests: &TestMap,
         timeout_queue: &mut VecDeque<TimeoutEntry>,
@@ -374,107 +649,189 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
         })
     }
 
-    if concurrency == 1 {
-        while !remaining.is_empty() {
-            let (id, test) = remaining.pop_front().unwrap();
-            let event = TestEvent::TeWait(test.desc.clone());
-            notify_about_test_event(event)?;
-            let join_handle = run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone());
-            // Wait for the test to complete.
-            let mut completed_test = rx.recv().unwrap();
-            RunningTest { join_handle }.join(&mut completed_test);
+    // Stopping the run early breaks out of this block with whether tests may still be running.
+    let tests_still_running = 'run: {
+        if concurrency == 1 {
+            while !remaining.is_empty() {
+                let (id, test) = remaining.pop_front().unwrap();
+                if !retries.is_retry(id) {
+                    let event = TestEvent::TeWait(test.desc.clone());
+                    notify_about_test_event(event)?;
+                }
+                let hard_timeout = hard_timeout_for(&test.desc);
+                let join_handle =
+                    run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone());
+                // Wait for the test to complete.
+                let mut completed_test = match hard_timeout.as_ref().map(|t| t.deadline) {
+                    Some(deadline) => {
+                        match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                            Ok(completed_test) => completed_test,
+                            Err(_) => {
+                                // The timed out test can't finish its capture, so report
+                                // the timeout on the original stdout.
+                                fd_capture::restore();
+                                notify_about_test_event(hard_timeout.unwrap().into_event(id))?;
+                                // Prevent the timed out test thread from panicking
+                                std::mem::forget(rx);
+                                break 'run true;
+                            }
+                        }
+                    }
+                    None => rx.recv().unwrap(),
+                };
+                RunningTest { join_handle, hard_timeout }.join(&mut completed_test);
 
-            let fail_fast = match completed_test.result {
-                TrIgnored | TrOk | TrBench(_) => false,
-                TrFailed | TrFailedMsg(_) | TrTimedFail => opts.fail_fast,
-            };
+                if let Some(test) = retries.retry(&mut completed_test) {
+                    remaining.push_front(test);
+                    continue;
+                }
 
-            let event = TestEvent::TeResult(completed_test);
-            notify_about_test_event(event)?;
+                let fail_fast = match completed_test.result {
+                    TrIgnored | TrOk | TrFlaky(_) | TrBench(_) => false,
+                    TrFailed | TrFailedMsg(_) | TrTimedFail => opts.fail_fast,
+                };
 
-            if fail_fast {
-                return Ok(());
-            }
-        }
-    } else {
-        while pending > 0 || !remaining.is_empty() {
-            while pending < concurrency && !remaining.is_empty() {
-                let (id, test) = remaining.pop_front().unwrap();
-                let timeout = time::get_default_test_timeout();
-                let desc = test.desc.clone();
+                let event = TestEvent::TeResult(completed_test);
+                notify_about_test_event(event)?;
 
-                let event = TestEvent::TeWait(desc.clone());
-                notify_about_test_event(event)?; //here no pad
-                let join_handle =
-                    run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone());
-                running_tests.insert(id, RunningTest { join_handle });
-                timeout_queue.push_back(TimeoutEntry { id, desc, timeout });
-                pending += 1;
+                if fail_fast {
+                    break 'run false;
+                }
             }
-
-            let mut res;
-            loop {
-                if let Some(timeout) = calc_timeout(&timeout_queue) {
-                    res = rx.recv_timeout(timeout);
-                    for test in get_timed_out_tests(&running_tests, &mut timeout_queue) {
-                        let event = TestEvent::TeTimeout(test);
-                        notify_about_test_event(event)?;
+        } else {
+            while pending > 0 || !remaining.is_empty() {
+                while pending < concurrency && !remaining.is_empty() {
+                    let (id, test) = remaining.pop_front().unwrap();
+                    let timeout = time::get_default_test_timeout();
+                    let desc = test.desc.clone();
+
+                    if !retries.is_retry(id) {
+                        let event = TestEvent::TeWait(desc.clone());
+                        notify_about_test_event(event)?; //here no pad
                     }
+                    let hard_timeout = hard_timeout_for(&desc);
+                    let join_handle =
+                        run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone());
+                    running_tests.insert(id, RunningTest { join_handle, hard_timeout });
+                    timeout_queue.push_back(TimeoutEntry { id, desc, timeout });
+                    pending += 1;
+                }
 
-                    match res {
-                        Err(RecvTimeoutError::Timeout) => {
-                            // Result is not yet ready, continue waiting.
+                let mut res;
+                loop {
+                    let timeout =
+                        match (calc_timeout(&timeout_queue), calc_hard_timeout(&running_tests)) {
+                            (Some(warn), Some(hard)) => Some(warn.min(hard)),
+                            (warn, hard) => warn.or(hard),
+                        };
+                    if let Some(timeout) = timeout {
+                        res = rx.recv_timeout(timeout);
+                        for test in get_timed_out_tests(&running_tests, &mut timeout_queue) {
+                            let event = TestEvent::TeTimeout(test);
+                            notify_about_test_event(event)?;
+                        }
+
+                        if let Err(RecvTimeoutError::Timeout) = res {
+                            if let Some(id) = get_hard_timed_out_test(&running_tests) {
+                                let running_test = running_tests.remove(&id).unwrap();
+                                notify_about_test_event(
+                                    running_test.hard_timeout.unwrap().into_event(id),
+                                )?;
+                                // Prevent remaining test threads from panicking
+                                std::mem::forget(rx);
+                                break 'run true;
+                            }
                         }
-                        _ => {
-                            // We've got a result, stop the loop.
-                            break;
+
+                        match res {
+                            Err(RecvTimeoutError::Timeout) => {
+                                // Result is not yet ready, continue waiting.
+                            }
+                            _ => {
+                                // We've got a result, stop the loop.
+                                break;
+                            }
                         }
+                    } else {
+                        res = rx.recv().map_err(|_| RecvTimeoutError::Disconnected);
+                        break;
                     }
-                } else {
-                    res = rx.recv().map_err(|_| RecvTimeoutError::Disconnected);
-                    break;
                 }
-            }
 
-            let mut completed_test = res.unwrap();
-            let running_test = running_tests.remove(&completed_test.id).unwrap();
-            running_test.join(&mut completed_test);
+                let mut completed_test = res.unwrap();
+                let running_test = running_tests.remove(&completed_test.id).unwrap();
+                running_test.join(&mut completed_test);
+
+                if let Some(test) = retries.retry(&mut completed_test) {
+                    // The retry gets a fresh timeout once it starts running, so the first
+                    // attempt's must not fire for it.
+                    timeout_queue.retain(|entry| entry.id != completed_test.id);
+                    remaining.push_front(test);
+                    pending -= 1;
+                    continue;
+                }
 
-            let fail_fast = match completed_test.result {
-                TrIgnored | TrOk | TrBench(_) => false,
-                TrFailed | TrFailedMsg(_) | TrTimedFail => opts.fail_fast,
-            };
+                let fail_fast = match completed_test.result {
+                    TrIgnored | TrOk | TrFlaky(_) | TrBench(_) => false,
+                    TrFailed | TrFailedMsg(_) | TrTimedFail => opts.fail_fast,
+                };
 
-            let event = TestEvent::TeResult(completed_test);
-            notify_about_test_event(event)?;
-            pending -= 1;
+                let event = TestEvent::TeResult(completed_test);
+                notify_about_test_event(event)?;
+                pending -= 1;
 
-            if fail_fast {
-                // Prevent remaining test threads from panicking
-                std::mem::forget(rx);
-                return Ok(());
+                if fail_fast {
+                    // Prevent remaining test threads from panicking
+                    std::mem::forget(rx);
+                    break 'run pending > 0;
+                }
+            }
+        }
+
+        if opts.bench_benchmarks {
+            // All benchmarks run at the end, in serial.
+            for (id, b) in filtered.benches {
+                let event = TestEvent::TeWait(b.desc.clone());
+                notify_about_test_event(event)?;
+                let join_handle = run_test(opts, false, id, b, run_strategy, tx.clone());
+                // Wait for the test to complete.
+                let mut completed_test = rx.recv().unwrap();
+                RunningTest { join_handle, hard_timeout: None }.join(&mut completed_test);
+
+                let event = TestEvent::TeResult(completed_test);
+                notify_about_test_event(event)?;
             }
         }
-    }
 
-    if opts.bench_benchmarks {
-        // All benchmarks run at the end, in serial.
-        for (id, b) in filtered.benches {
-            let event = TestEvent::TeWait(b.desc.clone());
-            notify_about_test_event(event)?;
-            let join_handle = run_test(opts, false, id, b, run_strategy, tx.clone());
-            // Wait for the test to complete.
-            let mut completed_test = rx.recv().unwrap();
-            RunningTest { join_handle }.join(&mut completed_test);
+        false
+    };
 
-            let event = TestEvent::TeResult(completed_test);
-            notify_about_test_event(event)?;
+    // Tests that are still running might still use the fixtures, so leave them set up.
+    if !tests_still_running {
+        for failure in active_fixtures.tear_down() {
+            notify_about_test_event(TestEvent::TeFixtureFailed(failure))?;
         }
     }
     Ok(())
 }
 
 pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescAndFn> {
+    let mut filtered = filter_tests_unpartitioned(opts, tests);
+
+    // Keep only the selected shard. This has to come last, so that every machine
+    // splits the same set of tests.
+    if let Some(partition) = opts.partition {
+        partition_tests(partition, &mut filtered);
+    }
+
+    filtered
+}
+
+/// Like `filter_tests`, but keeps the tests of every shard of `--partition`.
+pub(crate) fn filter_tests_unpartitioned(
+    opts: &TestOpts,
+    tests: Vec<TestDescAndFn>,
+) -> Vec<TestDescAndFn> {
     let mut filtered = tests;
     let matches_filter = |test: &TestDescAndFn, filter: &str| {
         let test_name = test.desc.name.as_slice();
@@ -515,6 +872,26 @@ pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescA
     filtered
 }
 
+/// Reads the state of the last run from `--state-file`, if it's set.
+pub(crate) fn read_run_state(opts: &TestOpts) -> io::Result<RunState> {
+    match opts.state_file {
+        Some(ref path) => RunState::read(path),
+        None => Ok(RunState::new()),
+    }
+}
+
+/// With `--rerun-failed`, keeps only the tests that failed in the last run. This comes
+/// after `filter_tests`, so that each shard of `--partition` reruns its own failures.
+pub(crate) fn retain_failed_tests(
+    opts: &TestOpts,
+    run_state: &RunState,
+    tests: &mut Vec<TestDescAndFn>,
+) {
+    if opts.rerun_failed {
+        tests.retain(|test| run_state.failed(test.desc.name.as_slice()));
+    }
+}
+
 pub fn convert_benchmarks_to_tests(tests: Vec<TestDescAndFn>) -> Vec<TestDescAndFn> {
     // convert benchmarks to tests, if we're not benchmarking them
     tests
@@ -562,7 +939,9 @@ pub fn run_test(
 
             let name = desc.name.clone();
             let nocapture = opts.nocapture;
+            let capture = opts.capture;
             let time_options = opts.time_options;
+            let hard_timeout = time::get_hard_timeout(&desc, opts.test_timeout);
             let bench_benchmarks = opts.bench_benchmarks;
 
             let runtest = move || match strategy {
@@ -570,6 +949,7 @@ pub fn run_test(
                     id,
                     desc,
                     nocapture,
+                    capture,
                     time_options.is_some(),
                     runnable_test,
                     monitor_ch,
@@ -582,6 +962,7 @@ pub fn run_test(
                     time_options.is_some(),
                     monitor_ch,
                     time_options,
+                    hard_timeout,
                     bench_benchmarks,
                 ),
             };
@@ -613,7 +994,14 @@ pub fn run_test(
         }
         Runnable::Bench(runnable_bench) => {
             // Benchmarks aren't expected to panic, so we run them all in-process.
-            runnable_bench.run(id, &desc, &monitor_ch, opts.nocapture);
+            runnable_bench.run(
+                id,
+                &desc,
+                &monitor_ch,
+                opts.nocapture,
+                opts.capture,
+                opts.bench_options.warm_up_time,
+            );
             None
         }
     }
@@ -632,6 +1020,7 @@ fn run_test_in_process(
     id: TestId,
     desc: TestDesc,
     nocapture: bool,
+    capture: Capture,
     report_time: bool,
     runnable_test: RunnableTest,
     monitor_ch: Sender<CompletedTest>,
@@ -640,9 +1029,15 @@ fn run_test_in_process(
     // Buffer for capturing standard I/O
     let data = Arc::new(Mutex::new(Vec::new()));
 
-    if !nocapture {
-        io::set_output_capture(Some(data.clone()));
-    }
+    let fd_capture = match start_capture(nocapture, capture, &data) {
+        Ok(fd_capture) => fd_capture,
+        Err(e) => {
+            let result = TrFailedMsg(format!("failed to capture the output of the test: {e}"));
+            let message = CompletedTest::new(id, desc, result, None, Vec::new());
+            monitor_ch.send(message).unwrap();
+            return;
+        }
+    };
 
     let start = report_time.then(Instant::now);
     let result = fold_err(catch_unwind(AssertUnwindSafe(|| runnable_test.run())));
@@ -657,11 +1052,31 @@ fn run_test_in_process(
         Ok(()) => calc_result(&desc, Ok(()), &time_opts, &exec_time),
         Err(e) => calc_result(&desc, Err(e.as_ref()), &time_opts, &exec_time),
     };
-    let stdout = data.lock().unwrap_or_else(|e| e.into_inner()).to_vec();
+    let stdout = match fd_capture {
+        Some(fd_capture) => fd_capture.finish(),
+        None => data.lock().unwrap_or_else(|e| e.into_inner()).to_vec(),
+    };
     let message = CompletedTest::new(id, desc, test_result, exec_time, stdout);
     monitor_ch.send(message).unwrap();
 }
 
+/// Starts capturing the output of a test or benchmark that runs in this process, into `data`
+/// or, for `Capture::Fd`, into the returned capture.
+pub(crate) fn start_capture(
+    nocapture: bool,
+    capture: Capture,
+    data: &Arc<Mutex<Vec<u8>>>,
+) -> io::Result<Option<FdCapture>> {
+    match (nocapture, capture) {
+        (true, _) => Ok(None),
+        (false, Capture::Sys) => {
+            io::set_output_capture(Some(data.clone()));
+            Ok(None)
+        }
+        (false, Capture::Fd) => FdCapture::start().map(Some),
+    }
+}
+
 fn fold_err<T, E>(
     result: Result<Result<T, E>, Box<dyn Any + Send>>,
 ) -> Result<T, Box<dyn Any + Send>>
@@ -682,6 +1097,7 @@ fn spawn_test_subprocess(
     report_time: bool,
     monitor_ch: Sender<CompletedTest>,
     time_opts: Option<time::TestTimeOptions>,
+    hard_timeout: Option<Duration>,
     bench_benchmarks: bool,
 ) {
     let (result, test_output, exec_time) = (|| {
@@ -699,7 +1115,11 @@ fn spawn_test_subprocess(
         }
 
         let start = report_time.then(Instant::now);
-        let output = match command.output() {
+        let output = match hard_timeout {
+            Some(timeout) => output_with_timeout(&mut command, nocapture, timeout),
+            None => command.output().map(|out| (Some(out.status), out.stdout, out.stderr)),
+        };
+        let (status, stdout, stderr) = match output {
             Ok(out) => out,
             Err(e) => {
                 let err = format!("Failed to spawn {} as child for test: {:?}", args[0], e);
@@ -711,12 +1131,19 @@ fn spawn_test_subprocess(
             TestExecTime(duration)
         });
 
-        let std::process::Output { stdout, stderr, status } = output;
         let mut test_output = stdout;
         formatters::write_stderr_delimiter(&mut test_output, &desc.name);
         test_output.extend_from_slice(&stderr);
 
-        let result = get_result_from_exit_code(&desc, status, &time_opts, &exec_time);
+        let result = match status {
+            Some(status) => get_result_from_exit_code(&desc, status, &time_opts, &exec_time),
+            // The child was killed by `output_with_timeout`.
+            None => {
+                let message = time::hard_timeout_message(hard_timeout.unwrap_or_default());
+                test_output.extend_from_slice(message.as_bytes());
+                TrTimedFail
+            }
+        };
         (result, test_output, exec_time)
     })();
 
@@ -724,35 +1151,116 @@ fn spawn_test_subprocess(
     monitor_ch.send(message).unwrap();
 }
 
-fn run_test_in_spawned_subprocess(desc: TestDesc, runnable_test: RunnableTest) -> ! {
-    let builtin_panic_hook = panic::take_hook();
-    let record_result = Arc::new(move |panic_info: Option<&'_ PanicInfo<'_>>| {
-        let test_result = match panic_info {
-            Some(info) => calc_result(&desc, Err(info.payload()), &None, &None),
-            None => calc_result(&desc, Ok(()), &None, &None),
-        };
+/// Runs `command` to completion like `Command::output`, except that the child is killed if it
+/// is still running after `timeout`, in which case no exit status is returned.
+fn output_with_timeout(
+    command: &mut Command,
+    nocapture: bool,
+    timeout: Duration,
+) -> io::Result<(Option<process::ExitStatus>, Vec<u8>, Vec<u8>)> {
+    use std::io::Read;
+
+    // Drain the pipes on separate threads, so that a child filling one of them can't block.
+    fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+        thread::spawn(move || {
+            let mut output = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut output);
+            }
+            output
+        })
+    }
 
-        // We don't support serializing TrFailedMsg, so just
-        // print the message out to stderr.
-        if let TrFailedMsg(msg) = &test_result {
-            eprintln!("{msg}");
+    command.stdin(process::Stdio::null());
+    if !nocapture {
+        command.stdout(process::Stdio::piped());
+        command.stderr(process::Stdio::piped());
+    }
+    let mut child = command.spawn()?;
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let mut poll_interval = Duration::from_millis(1);
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
         }
-
-        if let Some(info) = panic_info {
-            builtin_panic_hook(info);
+        let now = Instant::now();
+        if now >= deadline {
+            // Killing fails if the child has exited in the meantime, which is fine.
+            let _ = child.kill();
+            child.wait()?;
+            break None;
         }
+        thread::sleep(poll_interval.min(deadline - now));
+        poll_interval = (poll_interval * 2).min(Duration::from_millis(100));
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    Ok((status, stdout, stderr))
+}
+
+fn run_test_in_spawned_subprocess(
+    desc: TestDesc,
+    runnable_test: RunnableTest,
+    fixtures: ActiveFixtures,
+) -> ! {
+    let builtin_panic_hook = Arc::new(panic::take_hook());
+    let builtin_panic_hook2 = builtin_panic_hook.clone();
+    let fixtures = Mutex::new(Some(fixtures));
+    let record_result = Arc::new(
+        move |panic_payload: Option<&(dyn Any + Send)>, panic_info: Option<&'_ PanicInfo<'_>>| {
+            let mut test_result = match panic_payload {
+                Some(payload) => calc_result(&desc, Err(payload), &None, &None),
+                None => calc_result(&desc, Ok(()), &None, &None),
+            };
+
+            let fixtures = fixtures.lock().unwrap_or_else(|e| e.into_inner()).take();
+            let failures = fixtures.map(ActiveFixtures::tear_down).unwrap_or_default();
+            if let Some(failure) = failures.first() {
+                eprintln!("{failure}");
+                if let TrOk = test_result {
+                    test_result = TrFailed;
+                }
+            }
+
+            // We don't support serializing TrFailedMsg, so just
+            // print the message out to stderr.
+            if let TrFailedMsg(msg) = &test_result {
+                eprintln!("{msg}");
+            }
 
-        if let TrOk = test_result {
-            process::exit(test_result::TR_OK);
+            if let Some(info) = panic_info {
+                builtin_panic_hook2(info);
+            }
+
+            if let TrOk = test_result {
+                process::exit(test_result::TR_OK);
+            } else {
+                process::abort();
+            }
+        },
+    );
+    let record_result2 = record_result.clone();
+    panic::set_hook(Box::new(move |info| {
+        if info.can_unwind() {
+            // With panic=unwind (`--isolate=process`) the test may still catch this panic,
+            // so the result is only recorded once the panic has unwound out of the test.
+            builtin_panic_hook(info);
         } else {
-            process::abort();
+            record_result2(Some(info.payload()), Some(info));
         }
-    });
-    let record_result2 = record_result.clone();
-    panic::set_hook(Box::new(move |info| record_result2(Some(info))));
-    if let Err(message) = runnable_test.run() {
-        panic!("{}", message);
+    }));
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        if let Err(message) = runnable_test.run() {
+            panic!("{}", message);
+        }
+    }));
+    match result {
+        Ok(()) => record_result(None, None),
+        Err(payload) => record_result(Some(&*payload), None),
     }
-    record_result(None);
-    unreachable!("panic=abort callback should have exited the process")
+    unreachable!("record_result should have exited the process")
 }
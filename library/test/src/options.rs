@@ -36,6 +36,8 @@ pub enum OutputFormat {
     Json,
     /// JUnit output
     Junit,
+    /// TAP (Test Anything Protocol) version 13 output
+    Tap,
 }
 
 /// Whether ignored test should be run or not
@@ -47,6 +49,54 @@ pub enum RunIgnored {
     Only,
 }
 
+/// How tests are isolated from each other while they run
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Isolation {
+    /// Each test runs on its own thread of the test binary's process
+    #[default]
+    Thread,
+    /// Each test runs in its own child process, like with panic=abort
+    Process,
+}
+
+/// How the output of tests is captured, unless `--nocapture` is passed
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Capture {
+    /// `print!` and `eprint!` from the test thread and the threads it spawns are captured
+    #[default]
+    Sys,
+    /// The stdout and stderr file descriptors are redirected, so all output is captured,
+    /// including raw writes and output of C code
+    Fd,
+}
+
+/// How `--order` sorts tests, based on the last run saved in `--state-file`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TestOrder {
+    /// Tests that failed last time first, then flaky ones, then the others
+    FailedFirst,
+    /// The slowest tests first, after the tests that haven't been run before
+    SlowestFirst,
+}
+
+/// How `--partition` assigns tests to shards
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PartitionStrategy {
+    /// By a stable hash of the test name
+    Hash,
+    /// Round-robin over the tests, ordered by name
+    Count,
+}
+
+/// Selects one shard of the test suite, so that it can be split across machines
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Partition {
+    pub strategy: PartitionStrategy,
+    /// The selected shard, between 1 and `total`
+    pub shard: usize,
+    pub total: usize,
+}
+
 #[derive(Clone, Copy)]
 pub enum RunStrategy {
     /// Runs the test in the current process, and sends the result back over the
@@ -300,3 +300,55 @@ pub fn winsorize(samples: &mut [f64], pct: f64) {
         }
     }
 }
+
+/// Counts of the samples that lie outside the Tukey fences of a sample set.
+///
+/// Samples further than 1.5 inter-quartile ranges below the 1st or above the
+/// 3rd quartile are mild outliers, and those further than 3 inter-quartile
+/// ranges are severe outliers.
+///
+/// See: <https://en.wikipedia.org/wiki/Outlier#Tukey's_fences>
+#[derive(Debug, Clone, PartialEq, Copy, Default)]
+#[allow(missing_docs)]
+pub struct Outliers {
+    pub low_severe: usize,
+    pub low_mild: usize,
+    pub high_mild: usize,
+    pub high_severe: usize,
+}
+
+impl Outliers {
+    /// Classify the samples of a sample set against its Tukey fences.
+    pub fn new(samples: &[f64]) -> Outliers {
+        let (q1, _, q3) = samples.quartiles();
+        let iqr = q3 - q1;
+        let mut outliers = Outliers::default();
+        for &samp in samples {
+            if samp < q1 - 3.0 * iqr {
+                outliers.low_severe += 1;
+            } else if samp < q1 - 1.5 * iqr {
+                outliers.low_mild += 1;
+            } else if samp > q3 + 3.0 * iqr {
+                outliers.high_severe += 1;
+            } else if samp > q3 + 1.5 * iqr {
+                outliers.high_mild += 1;
+            }
+        }
+        outliers
+    }
+
+    /// Number of mild outliers, below or above the sample set.
+    pub fn mild(&self) -> usize {
+        self.low_mild + self.high_mild
+    }
+
+    /// Number of severe outliers, below or above the sample set.
+    pub fn severe(&self) -> usize {
+        self.low_severe + self.high_severe
+    }
+
+    /// Total number of outliers.
+    pub fn total(&self) -> usize {
+        self.mild() + self.severe()
+    }
+}
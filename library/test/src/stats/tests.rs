@@ -571,6 +571,16 @@ fn test_sum_f64_between_ints_that_sum_to_0() {
     assert_eq!([1e30f64, 1.2f64, -1e30f64].sum(), 1.2);
 }
 
+#[test]
+fn test_outliers() {
+    let samples = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, -20.0, -8.0, 19.0, 40.0];
+    let outliers = Outliers::new(&samples);
+    assert_eq!(outliers, Outliers { low_severe: 1, low_mild: 1, high_mild: 1, high_severe: 1 });
+    assert_eq!((outliers.mild(), outliers.severe(), outliers.total()), (2, 2, 4));
+
+    assert_eq!(Outliers::new(&[1.0, 2.0, 3.0, 4.0]).total(), 0);
+}
+
 #[bench]
 pub fn sum_three_items(b: &mut Bencher) {
     b.iter(|| {
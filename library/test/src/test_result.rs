@@ -29,6 +29,9 @@ pub enum TestResult {
     TrIgnored,
     TrBench(BenchSamples),
     TrTimedFail,
+    /// The test failed, but passed when it was retried. Holds the number of
+    /// attempts it took, including the first one.
+    TrFlaky(usize),
 }
 
 /// Creates a `TestResult` depending on the raw result of test execution
@@ -2,7 +2,13 @@
 
 use crate::{
     console::OutputLocation,
-    formatters::PrettyFormatter,
+    fixtures::{FixtureFailure, FixtureStage},
+    formatters::{OutputFormatter, PrettyFormatter, TapFormatter},
+    helpers::{
+        baseline::Baseline,
+        run_state::{RunState, TestOutcome},
+    },
+    stats::Summary,
     test::{
         parse_opts,
         MetricMap,
@@ -12,6 +18,9 @@
     },
     time::{TestTimeOptions, TimeThreshold},
 };
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 impl TestOpts {
     fn new() -> TestOpts {
@@ -26,13 +35,22 @@ fn new() -> TestOpts {
             bench_benchmarks: false,
             logfile: None,
             nocapture: false,
+            capture: Capture::Sys,
             color: AutoColor,
             format: OutputFormat::Pretty,
             shuffle: false,
             shuffle_seed: None,
             test_threads: None,
             skip: vec![],
+            partition: None,
             time_options: None,
+            test_timeout: None,
+            retries: 0,
+            isolate: Isolation::Thread,
+            bench_options: BenchOptions::default(),
+            state_file: None,
+            rerun_failed: false,
+            order: None,
             options: Options::new(),
             fail_fast: false,
         }
@@ -52,6 +70,7 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
                 end_line: 0,
                 end_col: 0,
                 should_panic: ShouldPanic::No,
+                timeout: None,
                 compile_fail: false,
                 no_run: false,
                 test_type: TestType::Unknown,
@@ -69,6 +88,7 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
                 end_line: 0,
                 end_col: 0,
                 should_panic: ShouldPanic::No,
+                timeout: None,
                 compile_fail: false,
                 no_run: false,
                 test_type: TestType::Unknown,
@@ -94,6 +114,7 @@ fn f() -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::No,
+            timeout: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -122,6 +143,7 @@ fn f() -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::No,
+            timeout: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -153,6 +175,7 @@ fn f() -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::Yes,
+            timeout: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -184,6 +207,7 @@ fn f() -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::YesWithMessage("error message"),
+            timeout: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -220,6 +244,7 @@ fn f() -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::YesWithMessage(expected),
+            timeout: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -260,6 +285,7 @@ fn f() -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::YesWithMessage(expected),
+            timeout: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -294,6 +320,7 @@ fn f() -> Result<(), String> {
                 end_line: 0,
                 end_col: 0,
                 should_panic,
+                timeout: None,
                 compile_fail: false,
                 no_run: false,
                 test_type: TestType::Unknown,
@@ -327,6 +354,7 @@ fn f() -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::No,
+            timeout: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -369,6 +397,7 @@ fn f() -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::No,
+            timeout: None,
             compile_fail: false,
             no_run: false,
             test_type,
@@ -402,6 +431,340 @@ fn test_error_on_exceed() {
     assert_eq!(result, TestResult::TrOk);
 }
 
+/// Runs `tests` with `fixtures`, returning the test results and fixture failures in the
+/// order they were reported.
+fn run_template(
+    opts: &TestOpts,
+    tests: Vec<TestDescAndFn>,
+    fixtures: &[TestFixture],
+) -> Vec<TestEvent> {
+    let mut events = Vec::new();
+    run_tests_with_fixtures(opts, tests, fixtures, |event| {
+        match event {
+            TestEvent::TeResult(..) | TestEvent::TeFixtureFailed(..) => events.push(event),
+            _ => {}
+        }
+        Ok(())
+    })
+    .unwrap();
+    events
+}
+
+/// Like `run_template`, without fixtures, returning only the test results.
+fn run_results_template(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<CompletedTest> {
+    run_template(opts, tests, &[])
+        .into_iter()
+        .filter_map(|event| match event {
+            TestEvent::TeResult(result) => Some(result),
+            _ => None,
+        })
+        .collect()
+}
+
+fn hard_timeout_template(test_threads: usize) -> Vec<CompletedTest> {
+    // Held while the tests run, so that `hangs` only finishes once they timed out.
+    static HANG: Mutex<()> = Mutex::new(());
+    fn passes() -> Result<(), String> {
+        Ok(())
+    }
+    fn hangs() -> Result<(), String> {
+        drop(HANG.lock());
+        Ok(())
+    }
+    let tests = vec![
+        TestDescAndFn {
+            desc: TestDesc { name: StaticTestName("passes"), ..typed_test_desc(TestType::Unknown) },
+            testfn: DynTestFn(Box::new(passes)),
+        },
+        TestDescAndFn {
+            desc: TestDesc { name: StaticTestName("hangs"), ..typed_test_desc(TestType::Unknown) },
+            testfn: DynTestFn(Box::new(hangs)),
+        },
+    ];
+    let test_opts = TestOpts {
+        run_tests: true,
+        test_threads: Some(test_threads),
+        test_timeout: Some(Duration::from_millis(100)),
+        ..TestOpts::new()
+    };
+    let _hang = HANG.lock().unwrap();
+    run_results_template(&test_opts, tests)
+}
+
+#[test]
+#[cfg(not(target_os = "emscripten"))]
+fn test_hard_timeout_in_process() {
+    for test_threads in [1, 2] {
+        let results = hard_timeout_template(test_threads);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].desc.name.as_slice(), "passes");
+        assert_eq!(results[0].result, TrOk);
+        assert_eq!(results[1].desc.name.as_slice(), "hangs");
+        assert_eq!(results[1].result, TrTimedFail);
+        let output = String::from_utf8_lossy(&results[1].stdout);
+        assert!(output.contains("time limit of 100ms"), "{output}");
+    }
+}
+
+fn retries_template(test_threads: usize, retries: usize) -> Vec<CompletedTest> {
+    static FLAKY_RUNS: AtomicUsize = AtomicUsize::new(0);
+    fn passes_third_time() -> Result<(), String> {
+        if FLAKY_RUNS.fetch_add(1, Ordering::SeqCst) < 2 { Err("not yet".into()) } else { Ok(()) }
+    }
+    fn always_fails() -> Result<(), String> {
+        Err("broken".into())
+    }
+    FLAKY_RUNS.store(0, Ordering::SeqCst);
+
+    let tests = vec![
+        TestDescAndFn {
+            desc: TestDesc { name: StaticTestName("flaky"), ..typed_test_desc(TestType::Unknown) },
+            testfn: StaticTestFn(passes_third_time),
+        },
+        TestDescAndFn {
+            desc: TestDesc { name: StaticTestName("broken"), ..typed_test_desc(TestType::Unknown) },
+            testfn: StaticTestFn(always_fails),
+        },
+        TestDescAndFn {
+            desc: TestDesc {
+                name: StaticTestName("dynamic"),
+                ..typed_test_desc(TestType::Unknown)
+            },
+            testfn: DynTestFn(Box::new(always_fails)),
+        },
+    ];
+    let test_opts =
+        TestOpts { run_tests: true, test_threads: Some(test_threads), retries, ..TestOpts::new() };
+    let mut results = run_results_template(&test_opts, tests);
+    results.sort_by(|a, b| a.desc.name.as_slice().cmp(b.desc.name.as_slice()));
+    results
+}
+
+#[test]
+fn test_retries() {
+    for test_threads in [1, 2] {
+        let results = retries_template(test_threads, 2);
+        // Every test is reported exactly once.
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].desc.name.as_slice(), "broken");
+        assert_eq!(results[0].result, TrFailed);
+        // Dynamic tests can't be run again.
+        assert_eq!(results[1].desc.name.as_slice(), "dynamic");
+        assert_eq!(results[1].result, TrFailed);
+        assert_eq!(results[2].desc.name.as_slice(), "flaky");
+        assert_eq!(results[2].result, TrFlaky(3));
+        // The output of the failed attempts is kept.
+        let output = String::from_utf8_lossy(&results[2].stdout);
+        assert!(output.contains("---- attempt 1 failed ----"), "{output}");
+        assert!(output.contains("---- attempt 2 failed ----"), "{output}");
+        assert!(output.contains("---- attempt 3 ----"), "{output}");
+
+        // Not enough retries for the flaky test to pass.
+        let results = retries_template(test_threads, 1);
+        assert_eq!(results[2].result, TrFailed);
+
+        let results = retries_template(test_threads, 0);
+        assert_eq!(results[2].result, TrFailed);
+    }
+}
+
+#[test]
+fn parse_retries_option() {
+    let args =
+        vec!["progname".to_string(), "-Zunstable-options".to_string(), "--retries=3".to_string()];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.retries, 3);
+
+    let args = vec!["progname".to_string(), "--retries=3".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    let args =
+        vec!["progname".to_string(), "-Zunstable-options".to_string(), "--retries=x".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_bench_options() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--bench-warm-up=0.5".to_string(),
+        "--bench-save=new.txt".to_string(),
+        "--bench-compare=old.txt".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(
+        opts.bench_options,
+        BenchOptions {
+            warm_up_time: Some(Duration::from_millis(500)),
+            save_baseline: Some("new.txt".into()),
+            compare_baseline: Some("old.txt".into()),
+        }
+    );
+
+    let args = vec!["progname".to_string(), "--bench-compare=old.txt".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    for warm_up in ["x", "-1"] {
+        let args = vec![
+            "progname".to_string(),
+            "-Zunstable-options".to_string(),
+            format!("--bench-warm-up={warm_up}"),
+        ];
+        assert!(parse_opts(&args).unwrap().is_err());
+    }
+}
+
+fn fixtures_template(fixtures: &[TestFixture], names: &[&'static str]) -> Vec<TestEvent> {
+    let tests = names
+        .iter()
+        .map(|&name| TestDescAndFn {
+            desc: TestDesc { name: StaticTestName(name), ..typed_test_desc(TestType::Unknown) },
+            testfn: StaticTestFn(|| Ok(())),
+        })
+        .collect();
+    let test_opts = TestOpts { run_tests: true, test_threads: Some(1), ..TestOpts::new() };
+    run_template(&test_opts, tests, fixtures)
+}
+
+#[test]
+fn test_fixture_scope() {
+    let fixture = TestFixture { module_path: "net::server", setup: None, teardown: None };
+    assert!(fixture.applies_to("net::server::accepts"));
+    assert!(fixture.applies_to("net::server::tls::handshake"));
+    assert!(!fixture.applies_to("net::server_config"));
+    assert!(!fixture.applies_to("net::client::connects"));
+
+    let fixture = TestFixture { module_path: "", ..fixture };
+    assert!(fixture.applies_to("net::client::connects"));
+    assert!(fixture.applies_to("top_level"));
+    assert_eq!(fixture.scope(), "crate");
+}
+
+#[test]
+fn test_fixtures_run_once_around_tests() {
+    static LOG: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+    fn log(entry: &'static str) -> Result<(), String> {
+        LOG.lock().unwrap().push(entry);
+        Ok(())
+    }
+
+    let fixtures = [
+        TestFixture {
+            module_path: "a::b",
+            setup: Some(|| log("setup a::b")),
+            teardown: Some(|| log("teardown a::b")),
+        },
+        TestFixture {
+            module_path: "a",
+            setup: Some(|| log("setup a")),
+            teardown: Some(|| log("teardown a")),
+        },
+        // No test is in scope, so this fixture isn't set up.
+        TestFixture { module_path: "c", setup: Some(|| log("setup c")), teardown: None },
+    ];
+    let events = fixtures_template(&fixtures, &["a::b::one", "a::b::two", "a::three", "d::four"]);
+
+    assert_eq!(events.len(), 4);
+    assert_eq!(*LOG.lock().unwrap(), ["setup a", "setup a::b", "teardown a::b", "teardown a"]);
+}
+
+#[test]
+fn test_fixture_failures() {
+    let fixtures = [
+        TestFixture { module_path: "a", setup: Some(|| Err("no database".into())), teardown: None },
+        TestFixture { module_path: "a::b", setup: Some(|| panic!("not reached")), teardown: None },
+        TestFixture { module_path: "", setup: None, teardown: Some(|| panic!("still running")) },
+    ];
+    let events = fixtures_template(&fixtures, &["a::b::one", "c::two"]);
+
+    let results: Vec<_> = events
+        .iter()
+        .map(|event| match event {
+            TestEvent::TeResult(completed) => {
+                format!("{}: {:?}", completed.desc.name, completed.result)
+            }
+            TestEvent::TeFixtureFailed(failure) => failure.to_string(),
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(
+        results,
+        [
+            "setup of fixture `a` failed: no database",
+            "a::b::one: TrFailedMsg(\"setup of fixture `a` failed\")",
+            "c::two: TrOk",
+            "teardown of fixture `crate` failed: panicked: still running",
+        ]
+    );
+
+    match &events[0] {
+        TestEvent::TeFixtureFailed(failure) => assert_eq!(
+            *failure,
+            FixtureFailure {
+                module_path: "a",
+                stage: FixtureStage::Setup,
+                message: "no database".to_string()
+            }
+        ),
+        event => panic!("unexpected event: {event:?}"),
+    }
+}
+
+#[test]
+fn test_fixtures_not_torn_down_while_tests_run() {
+    static RELEASED: AtomicBool = AtomicBool::new(false);
+    static TORN_DOWN: AtomicBool = AtomicBool::new(false);
+
+    let fixtures = [TestFixture {
+        module_path: "",
+        setup: None,
+        teardown: Some(|| {
+            TORN_DOWN.store(true, Ordering::SeqCst);
+            Ok(())
+        }),
+    }];
+    let tests = vec![
+        TestDescAndFn {
+            desc: TestDesc { name: StaticTestName("slow"), ..typed_test_desc(TestType::Unknown) },
+            testfn: StaticTestFn(|| {
+                while !RELEASED.load(Ordering::SeqCst) {
+                    thread::sleep(Duration::from_millis(1));
+                }
+                Ok(())
+            }),
+        },
+        TestDescAndFn {
+            desc: TestDesc { name: StaticTestName("fails"), ..typed_test_desc(TestType::Unknown) },
+            testfn: StaticTestFn(|| Err("failed".into())),
+        },
+    ];
+    let test_opts =
+        TestOpts { run_tests: true, test_threads: Some(2), fail_fast: true, ..TestOpts::new() };
+    run_tests_with_fixtures(&test_opts, tests, &fixtures, |_| Ok(())).unwrap();
+
+    // `slow` was still running when `fails` stopped the run.
+    assert!(!TORN_DOWN.load(Ordering::SeqCst));
+    RELEASED.store(true, Ordering::SeqCst);
+}
+
+#[test]
+fn test_hard_timeout_precedence() {
+    let mut desc = typed_test_desc(TestType::UnitTest);
+    assert_eq!(time::get_hard_timeout(&desc, None), None);
+    assert_eq!(
+        time::get_hard_timeout(&desc, Some(Duration::from_secs(5))),
+        Some(Duration::from_secs(5))
+    );
+
+    desc.timeout = Some(30);
+    assert_eq!(time::get_hard_timeout(&desc, None), Some(Duration::from_secs(30)));
+    assert_eq!(
+        time::get_hard_timeout(&desc, Some(Duration::from_secs(5))),
+        Some(Duration::from_secs(30))
+    );
+}
+
 fn typed_test_desc(test_type: TestType) -> TestDesc {
     TestDesc {
         name: StaticTestName("whatever"),
@@ -413,6 +776,7 @@ fn typed_test_desc(test_type: TestType) -> TestDesc {
         end_line: 0,
         end_col: 0,
         should_panic: ShouldPanic::No,
+        timeout: None,
         compile_fail: false,
         no_run: false,
         test_type,
@@ -471,6 +835,140 @@ fn parse_show_output_flag() {
     assert!(opts.options.display_output);
 }
 
+#[test]
+fn parse_test_timeout_option() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--test-timeout=5".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.test_timeout, Some(Duration::from_secs(5)));
+
+    let args = vec!["progname".to_string(), "--test-timeout=5".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--test-timeout=0".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_isolate_option() {
+    let args = vec!["progname".to_string()];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.isolate, Isolation::Thread);
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--isolate=process".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.isolate, Isolation::Process);
+
+    let args = vec!["progname".to_string(), "--isolate=process".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--isolate=fork".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--isolate=process".to_string(),
+        "--force-run-in-process".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn isolate_process_requires_static_harness() {
+    let opts = TestOpts { isolate: Isolation::Process, ..TestOpts::new() };
+    assert!(check_isolation(&opts, true).is_ok());
+    assert!(check_isolation(&opts, false).is_err());
+
+    let opts = TestOpts::new();
+    assert!(check_isolation(&opts, false).is_ok());
+}
+
+#[test]
+fn parse_capture_option() {
+    let args = vec!["progname".to_string()];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.capture, Capture::Sys);
+    assert!(!opts.nocapture);
+
+    let args =
+        vec!["progname".to_string(), "-Zunstable-options".to_string(), "--capture=fd".to_string()];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.capture, Capture::Fd);
+    assert!(!opts.nocapture);
+
+    let args =
+        vec!["progname".to_string(), "-Zunstable-options".to_string(), "--capture=no".to_string()];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert!(opts.nocapture);
+
+    let args = vec!["progname".to_string(), "--capture=fd".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--capture=tee-sys".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_state_options() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--state-file=state".to_string(),
+        "--rerun-failed".to_string(),
+        "--order=slowest-first".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.state_file, Some(std::path::PathBuf::from("state")));
+    assert!(opts.rerun_failed);
+    assert_eq!(opts.order, Some(TestOrder::SlowestFirst));
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--state-file=state".to_string(),
+        "--order=failed-first".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert!(!opts.rerun_failed);
+    assert_eq!(opts.order, Some(TestOrder::FailedFirst));
+
+    let args = vec!["progname".to_string(), "--state-file=state".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    for flag in ["--rerun-failed", "--order=failed-first"] {
+        let args = vec!["progname".to_string(), "-Zunstable-options".to_string(), flag.to_string()];
+        assert!(parse_opts(&args).unwrap().is_err());
+    }
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--state-file=state".to_string(),
+        "--order=fastest-first".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
 #[test]
 fn parse_include_ignored_flag() {
     let args = vec!["progname".to_string(), "filter".to_string(), "--include-ignored".to_string()];
@@ -530,6 +1028,7 @@ pub fn exclude_should_panic_option() {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::Yes,
+            timeout: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -559,6 +1058,7 @@ fn tests() -> Vec<TestDescAndFn> {
                     end_line: 0,
                     end_col: 0,
                     should_panic: ShouldPanic::No,
+                    timeout: None,
                     compile_fail: false,
                     no_run: false,
                     test_type: TestType::Unknown,
@@ -625,6 +1125,89 @@ fn tests() -> Vec<TestDescAndFn> {
     assert_eq!(exact.len(), 2);
 }
 
+#[test]
+pub fn partition_option() {
+    fn tests() -> Vec<TestDescAndFn> {
+        (0..100)
+            .map(|i| TestDescAndFn {
+                desc: TestDesc {
+                    name: DynTestName(format!("test_{i}")),
+                    ignore: false,
+                    ignore_message: None,
+                    source_file: "",
+                    start_line: 0,
+                    start_col: 0,
+                    end_line: 0,
+                    end_col: 0,
+                    should_panic: ShouldPanic::No,
+                    timeout: None,
+                    compile_fail: false,
+                    no_run: false,
+                    test_type: TestType::Unknown,
+                },
+                testfn: DynTestFn(Box::new(move || Ok(()))),
+            })
+            .collect()
+    }
+
+    for strategy in [PartitionStrategy::Hash, PartitionStrategy::Count] {
+        let mut seen = Vec::new();
+        for shard in 1..=3 {
+            let partition = Some(Partition { strategy, shard, total: 3 });
+            let filtered = filter_tests(&TestOpts { partition, ..TestOpts::new() }, tests());
+            if strategy == PartitionStrategy::Count {
+                assert!(filtered.len() == 33 || filtered.len() == 34);
+            }
+            // Partitioning is deterministic.
+            let again = filter_tests(&TestOpts { partition, ..TestOpts::new() }, tests());
+            assert!(filtered.iter().zip(&again).all(|(a, b)| a.desc.name == b.desc.name));
+            seen.extend(filtered.into_iter().map(|test| test.desc.name.to_string()));
+        }
+
+        // Every test is in exactly one shard.
+        seen.sort();
+        let mut all: Vec<_> = tests().into_iter().map(|test| test.desc.name.to_string()).collect();
+        all.sort();
+        assert_eq!(seen, all);
+    }
+
+    // Partitioning happens after filtering.
+    let opts = TestOpts {
+        filters: vec!["test_1".into()],
+        partition: Some(Partition { strategy: PartitionStrategy::Count, shard: 2, total: 11 }),
+        ..TestOpts::new()
+    };
+    let filtered = filter_tests(&opts, tests());
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0].desc.name.to_string(), "test_10");
+}
+
+#[test]
+fn parse_partition_option() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--partition=hash:2/8".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(
+        opts.partition,
+        Some(Partition { strategy: PartitionStrategy::Hash, shard: 2, total: 8 })
+    );
+
+    let args = vec!["progname".to_string(), "--partition=count:1/2".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    for invalid in ["count:0/2", "count:3/2", "count:1", "random:1/2", "hash:a/b"] {
+        let args = vec![
+            "progname".to_string(),
+            "-Zunstable-options".to_string(),
+            format!("--partition={invalid}"),
+        ];
+        assert!(parse_opts(&args).unwrap().is_err(), "{invalid}");
+    }
+}
+
 fn sample_tests() -> Vec<TestDescAndFn> {
     let names = vec![
         "sha1::test".to_string(),
@@ -655,6 +1238,7 @@ fn testfn() -> Result<(), String> {
                 end_line: 0,
                 end_col: 0,
                 should_panic: ShouldPanic::No,
+                timeout: None,
                 compile_fail: false,
                 no_run: false,
                 test_type: TestType::Unknown,
@@ -789,12 +1373,13 @@ fn f(_: &mut Bencher) -> Result<(), String> {
         end_line: 0,
         end_col: 0,
         should_panic: ShouldPanic::No,
+        timeout: None,
         compile_fail: false,
         no_run: false,
         test_type: TestType::Unknown,
     };
 
-    crate::bench::benchmark(TestId(0), desc, tx, true, f);
+    crate::bench::benchmark(TestId(0), desc, tx, true, Capture::Sys, None, f);
     rx.recv().unwrap();
 }
 
@@ -817,15 +1402,249 @@ fn f(b: &mut Bencher) -> Result<(), String> {
         end_line: 0,
         end_col: 0,
         should_panic: ShouldPanic::No,
+        timeout: None,
         compile_fail: false,
         no_run: false,
         test_type: TestType::Unknown,
     };
 
-    crate::bench::benchmark(TestId(0), desc, tx, true, f);
+    crate::bench::benchmark(TestId(0), desc, tx, true, Capture::Sys, None, f);
     rx.recv().unwrap();
 }
 
+#[test]
+pub fn test_bench_warm_up() {
+    fn f(b: &mut Bencher) -> Result<(), String> {
+        b.bytes = 8;
+        b.iter(|| [0u8; 8]);
+        Ok(())
+    }
+
+    let (tx, rx) = channel();
+    let desc = typed_test_desc(TestType::Unknown);
+    let warm_up = Some(Duration::from_millis(10));
+    crate::bench::benchmark(TestId(0), desc, tx, true, Capture::Sys, warm_up, f);
+    match rx.recv().unwrap().result {
+        TrBench(bs) => {
+            assert_eq!(bs.bytes, 8);
+            assert_eq!(bs.change, None);
+        }
+        result => panic!("unexpected result: {result:?}"),
+    }
+}
+
+#[test]
+#[cfg(unix)]
+pub fn test_bench_capture_fd() {
+    fn f(b: &mut Bencher) -> Result<(), String> {
+        io::Write::write_all(&mut io::stdout(), b"raw stdout\n").unwrap();
+        b.iter(|| {});
+        Ok(())
+    }
+
+    let (tx, rx) = channel();
+    let desc = typed_test_desc(TestType::Unknown);
+    crate::bench::benchmark(TestId(0), desc, tx, false, Capture::Fd, None, f);
+    let completed_test = rx.recv().unwrap();
+    assert!(matches!(completed_test.result, TrBench(_)));
+    assert_eq!(completed_test.stdout, b"raw stdout\n");
+}
+
+#[test]
+fn bench_baseline_round_trip() {
+    let samples = [100.0, 102.0, 98.0, 101.0, 99.0];
+    let faster = samples.map(|s| s * 0.9);
+
+    let mut baseline = Baseline::new();
+    baseline.insert("a", &Summary::new(&samples));
+    baseline.insert("b\tc", &Summary::new(&samples));
+
+    let path = env::temp_dir().join(format!("libtest-baseline-{}", process::id()));
+    baseline.write(&path).unwrap();
+    let read = Baseline::read(&path);
+    std::fs::write(&path, "a\t1\t2\n").unwrap();
+    let malformed = Baseline::read(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let baseline = read.unwrap();
+    assert!(malformed.is_err());
+
+    let same = baseline.compare("b\tc", &Summary::new(&samples)).unwrap();
+    assert_eq!(same.pct, 0.0);
+    assert!(same.ci_low < 0.0 && same.ci_high > 0.0);
+    assert_eq!(same.ci_low, -same.ci_high);
+
+    let change = baseline.compare("a", &Summary::new(&faster)).unwrap();
+    assert!((change.pct + 10.0).abs() < 1e-9);
+    assert!(change.ci_low < change.pct && change.pct < change.ci_high && change.ci_high < 0.0);
+
+    assert_eq!(baseline.compare("d", &Summary::new(&samples)), None);
+}
+
+#[test]
+fn bench_compare_console() {
+    fn f(b: &mut Bencher) -> Result<(), String> {
+        b.iter(|| black_box(1 + 1));
+        Ok(())
+    }
+    let tests = || {
+        vec![TestDescAndFn {
+            desc: TestDesc { name: StaticTestName("bench"), ..typed_test_desc(TestType::Unknown) },
+            testfn: StaticBenchFn(f),
+        }]
+    };
+
+    let baseline = env::temp_dir().join(format!("libtest-bench-compare-{}", process::id()));
+    let logfile = env::temp_dir().join(format!("libtest-bench-compare-log-{}", process::id()));
+    let opts = TestOpts {
+        bench_benchmarks: true,
+        format: OutputFormat::Terse,
+        logfile: Some(logfile.clone()),
+        bench_options: BenchOptions { save_baseline: Some(baseline.clone()), ..Default::default() },
+        ..TestOpts::new()
+    };
+    assert!(run_tests_console(&opts, tests()).unwrap());
+    let saved = Baseline::read(&baseline);
+
+    let opts = TestOpts {
+        bench_options: BenchOptions {
+            compare_baseline: Some(baseline.clone()),
+            ..Default::default()
+        },
+        ..opts
+    };
+    assert!(run_tests_console(&opts, tests()).unwrap());
+    let log = std::fs::read_to_string(&logfile);
+    std::fs::remove_file(&baseline).unwrap();
+    std::fs::remove_file(&logfile).unwrap();
+
+    assert!(saved.unwrap().compare("bench", &Summary::new(&[1.0])).is_some());
+    let log = log.unwrap();
+    assert!(log.contains("; change: ") && log.ends_with("bench\n"), "{log}");
+}
+
+#[test]
+fn run_state_round_trip() {
+    let mut state = RunState::new();
+    state.record("a", TestOutcome::Passed, Duration::from_millis(1500));
+    state.record("b\tc", TestOutcome::Failed, Duration::ZERO);
+    state.record("d", TestOutcome::Failed, Duration::from_secs(1));
+    state.record("d", TestOutcome::Flaky, Duration::from_secs(2));
+    state.record("f\n\\n\r", TestOutcome::Failed, Duration::ZERO);
+
+    let path = env::temp_dir().join(format!("libtest-run-state-{}", process::id()));
+    let missing = RunState::read(&path);
+    state.write(&path).unwrap();
+    let read = RunState::read(&path);
+    std::fs::write(&path, "# libtest run state v1\npassed\tslow\ta\n").unwrap();
+    let malformed = RunState::read(&path);
+    std::fs::write(&path, "passed\t1\ta\n").unwrap();
+    let headerless = RunState::read(&path);
+    std::fs::write(&path, "# libtest run state v1\npassed\t1\ta\\\n").unwrap();
+    let bad_escape = RunState::read(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(missing.unwrap(), RunState::new());
+    assert_eq!(read.unwrap(), state);
+    assert!(malformed.is_err());
+    assert!(headerless.is_err());
+    assert!(bad_escape.is_err());
+
+    assert!(!state.failed("a"));
+    assert!(state.failed("b\tc"));
+    assert!(!state.failed("d"));
+    assert!(!state.failed("e"));
+    assert!(state.failed("f\n\\n\r"));
+}
+
+#[test]
+fn run_state_orders_tests() {
+    let mut state = RunState::new();
+    state.record("a", TestOutcome::Passed, Duration::from_secs(3));
+    state.record("b", TestOutcome::Failed, Duration::from_secs(1));
+    state.record("c", TestOutcome::Flaky, Duration::from_secs(2));
+    state.record("d", TestOutcome::Failed, Duration::from_secs(4));
+
+    let tests = || {
+        ["a", "b", "c", "d", "e"]
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let desc =
+                    TestDesc { name: StaticTestName(name), ..typed_test_desc(TestType::Unknown) };
+                (TestId(i), TestDescAndFn { desc, testfn: DynTestFn(Box::new(|| Ok(()))) })
+            })
+            .collect::<Vec<_>>()
+    };
+    fn names(tests: &[(TestId, TestDescAndFn)]) -> Vec<&str> {
+        tests.iter().map(|test| test.1.desc.name.as_slice()).collect()
+    }
+
+    let mut failed_first = tests();
+    state.order_tests(TestOrder::FailedFirst, &mut failed_first);
+    assert_eq!(names(&failed_first), ["b", "d", "c", "a", "e"]);
+
+    let mut slowest_first = tests();
+    state.order_tests(TestOrder::SlowestFirst, &mut slowest_first);
+    assert_eq!(names(&slowest_first), ["e", "d", "a", "c", "b"]);
+}
+
+#[test]
+fn rerun_failed_tests() {
+    let path = env::temp_dir().join(format!("libtest-rerun-failed-{}", process::id()));
+    let mut state = RunState::new();
+    state.record("isize::test_pow", TestOutcome::Failed, Duration::ZERO);
+    state.record("sha1::test", TestOutcome::Passed, Duration::ZERO);
+    state.write(&path).unwrap();
+
+    let opts = TestOpts { state_file: Some(path.clone()), rerun_failed: true, ..TestOpts::new() };
+    let run_state = read_run_state(&opts);
+    std::fs::remove_file(&path).unwrap();
+
+    let mut tests = filter_tests(&opts, sample_tests());
+    retain_failed_tests(&opts, &run_state.unwrap(), &mut tests);
+    assert_eq!(tests.len(), 1);
+    assert_eq!(tests[0].desc.name.as_slice(), "isize::test_pow");
+}
+
+#[test]
+fn tap_formatter_output() {
+    let desc = |name| TestDesc { name: StaticTestName(name), ..typed_test_desc(TestType::Unknown) };
+    let st = console::ConsoleTestState::new(&TestOpts::new()).unwrap();
+
+    let mut out = TapFormatter::new(OutputLocation::Raw(Vec::new()));
+    out.write_run_start(4, None).unwrap();
+    out.write_result(&desc("ok"), &TrOk, None, b"ignored output", &st).unwrap();
+    out.write_result(&desc("flaky"), &TrFlaky(2), None, b"", &st).unwrap();
+    let failed = TrFailedMsg("first\nsecond".to_string());
+    out.write_result(&desc("fails #1"), &failed, None, b"out\n\nput\n", &st).unwrap();
+    let ignored = TestDesc { ignore_message: Some("slow"), ..desc("ignored") };
+    out.write_result(&ignored, &TrIgnored, None, b"", &st).unwrap();
+
+    let OutputLocation::Raw(output) = out.output_location() else { unreachable!() };
+    let expected = "\
+TAP version 13
+1..4
+ok 1 - ok
+ok 2 - flaky
+  ---
+  attempts: 2
+  ...
+not ok 3 - fails \\#1
+  ---
+  message: |-
+    first
+    second
+  output: |-
+    out
+
+    put
+  ...
+ok 4 - ignored # SKIP slow
+";
+    assert_eq!(String::from_utf8_lossy(output), expected);
+}
+
 #[test]
 fn should_sort_failures_before_printing_them() {
     let test_a = TestDesc {
@@ -838,6 +1657,7 @@ fn should_sort_failures_before_printing_them() {
         end_line: 0,
         end_col: 0,
         should_panic: ShouldPanic::No,
+        timeout: None,
         compile_fail: false,
         no_run: false,
         test_type: TestType::Unknown,
@@ -853,6 +1673,7 @@ fn should_sort_failures_before_printing_them() {
         end_line: 0,
         end_col: 0,
         should_panic: ShouldPanic::No,
+        timeout: None,
         compile_fail: false,
         no_run: false,
         test_type: TestType::Unknown,
@@ -875,6 +1696,12 @@ fn should_sort_failures_before_printing_them() {
         not_failures: Vec::new(),
         ignores: Vec::new(),
         time_failures: Vec::new(),
+        flaky: Vec::new(),
+        fixture_failures: Vec::new(),
+        compare_baseline: None,
+        new_baseline: Baseline::new(),
+        run_state: None,
+        test_starts: HashMap::new(),
     };
 
     out.write_failures(&st).unwrap();
@@ -905,6 +1732,7 @@ fn f(_: &mut Bencher) -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::No,
+            timeout: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -61,6 +61,17 @@ pub fn get_default_test_timeout() -> Instant {
     Instant::now() + Duration::from_secs(TEST_WARN_TIMEOUT_S)
 }
 
+/// Returns the hard limit on the execution time of a test: the one set on the test itself
+/// with `#[test_timeout]`, or otherwise the one given with `--test-timeout`.
+pub fn get_hard_timeout(desc: &TestDesc, test_timeout: Option<Duration>) -> Option<Duration> {
+    desc.timeout.map(Duration::from_secs).or(test_timeout)
+}
+
+/// Returns the message reported for a test that was stopped after exceeding its hard time limit.
+pub fn hard_timeout_message(timeout: Duration) -> String {
+    format!("test did not finish within its time limit of {timeout:?}\n")
+}
+
 /// The measured execution time of a unit test.
 #[derive(Debug, Clone, PartialEq)]
 pub struct TestExecTime(pub Duration);
@@ -3,6 +3,7 @@
 use std::borrow::Cow;
 use std::fmt;
 use std::sync::mpsc::Sender;
+use std::time::Duration;
 
 use super::__rust_begin_short_backtrace;
 use super::bench::Bencher;
@@ -176,14 +177,28 @@ pub(crate) fn run(
         desc: &TestDesc,
         monitor_ch: &Sender<CompletedTest>,
         nocapture: bool,
+        capture: options::Capture,
+        warm_up: Option<Duration>,
     ) {
         match self {
-            RunnableBench::Static(f) => {
-                crate::bench::benchmark(id, desc.clone(), monitor_ch.clone(), nocapture, f)
-            }
-            RunnableBench::Dynamic(f) => {
-                crate::bench::benchmark(id, desc.clone(), monitor_ch.clone(), nocapture, f)
-            }
+            RunnableBench::Static(f) => crate::bench::benchmark(
+                id,
+                desc.clone(),
+                monitor_ch.clone(),
+                nocapture,
+                capture,
+                warm_up,
+                f,
+            ),
+            RunnableBench::Dynamic(f) => crate::bench::benchmark(
+                id,
+                desc.clone(),
+                monitor_ch.clone(),
+                nocapture,
+                capture,
+                warm_up,
+                f,
+            ),
         }
     }
 }
@@ -205,6 +220,9 @@ pub struct TestDesc {
     pub end_line: usize,
     pub end_col: usize,
     pub should_panic: options::ShouldPanic,
+    /// Hard limit on the execution time of the test, in seconds, set with
+    /// `#[test_timeout]`. Takes precedence over `--test-timeout`.
+    pub timeout: Option<u64>,
     pub compile_fail: bool,
     pub no_run: bool,
     pub test_type: TestType,
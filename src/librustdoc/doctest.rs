@@ -717,6 +717,7 @@ fn add_test(&mut self, test: ScrapedDoctest) {
                 end_col: 0,
                 // compiler failures are test failures
                 should_panic: test::ShouldPanic::No,
+                timeout: None,
                 compile_fail: test.langstr.compile_fail,
                 no_run: test.no_run(&rustdoc_options),
                 test_type: test::TestType::DocTest,
@@ -545,10 +545,28 @@ pub fn test_opts(config: &Config) -> test::TestOpts {
         shuffle_seed: None,
         test_threads: None,
         skip: config.skip.clone(),
+        #[cfg(not(bootstrap))]
+        partition: None,
         list: false,
         options: test::Options::new(),
         time_options: None,
         force_run_in_process: false,
+        #[cfg(not(bootstrap))]
+        test_timeout: None,
+        #[cfg(not(bootstrap))]
+        retries: 0,
+        #[cfg(not(bootstrap))]
+        isolate: test::Isolation::Thread,
+        #[cfg(not(bootstrap))]
+        capture: test::Capture::Sys,
+        #[cfg(not(bootstrap))]
+        bench_options: test::BenchOptions::default(),
+        #[cfg(not(bootstrap))]
+        state_file: None,
+        #[cfg(not(bootstrap))]
+        rerun_failed: false,
+        #[cfg(not(bootstrap))]
+        order: None,
         fail_fast: std::env::var_os("RUSTC_TEST_FAIL_FAST").is_some(),
     }
 }
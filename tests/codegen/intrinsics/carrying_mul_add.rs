@@ -0,0 +1,44 @@
+//@ compile-flags: -C opt-level=1 -C no-prepopulate-passes
+//@ only-64bit (so that i128 is passed by value)
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+
+use std::intrinsics::carrying_mul_add;
+
+// The whole operation is a single multiplication and two additions at twice the width,
+// none of which can overflow.
+
+// CHECK-LABEL: @carrying_mul_add_u128
+#[no_mangle]
+pub fn carrying_mul_add_u128(a: u128, b: u128, c: u128, d: u128) -> (u128, u128) {
+    // CHECK: [[A:%.+]] = zext i128 %a to i256
+    // CHECK: [[B:%.+]] = zext i128 %b to i256
+    // CHECK: [[C:%.+]] = zext i128 %c to i256
+    // CHECK: [[D:%.+]] = zext i128 %d to i256
+    // CHECK: [[MUL:%.+]] = mul nuw i256 [[A]], [[B]]
+    // CHECK: [[ADD:%.+]] = add nuw i256 [[MUL]], [[C]]
+    // CHECK: [[WIDE:%.+]] = add nuw i256 [[ADD]], [[D]]
+    // CHECK: trunc i256 [[WIDE]] to i128
+    // CHECK: [[HIGH:%.+]] = lshr i256 [[WIDE]], 128
+    // CHECK: trunc i256 [[HIGH]] to i128
+    // CHECK-NOT: = mul
+    carrying_mul_add(a, b, c, d)
+}
+
+// CHECK-LABEL: @carrying_mul_add_i128
+#[no_mangle]
+pub fn carrying_mul_add_i128(a: i128, b: i128, c: i128, d: i128) -> (u128, i128) {
+    // CHECK: [[A:%.+]] = sext i128 %a to i256
+    // CHECK: [[B:%.+]] = sext i128 %b to i256
+    // CHECK: [[C:%.+]] = sext i128 %c to i256
+    // CHECK: [[D:%.+]] = sext i128 %d to i256
+    // CHECK: [[MUL:%.+]] = mul nsw i256 [[A]], [[B]]
+    // CHECK: [[ADD:%.+]] = add nsw i256 [[MUL]], [[C]]
+    // CHECK: [[WIDE:%.+]] = add nsw i256 [[ADD]], [[D]]
+    // CHECK: trunc i256 [[WIDE]] to i128
+    // CHECK: [[HIGH:%.+]] = lshr i256 [[WIDE]], 128
+    // CHECK: trunc i256 [[HIGH]] to i128
+    // CHECK-NOT: = mul
+    carrying_mul_add(a, b, c, d)
+}
@@ -0,0 +1,5 @@
+//@ compile-flags: --test
+
+#[test]
+#[test_timeout = "5"] //~ ERROR the `#[test_timeout]` attribute is an experimental feature
+fn slow() {}
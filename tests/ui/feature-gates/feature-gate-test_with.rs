@@ -0,0 +1,7 @@
+//@ compile-flags: --test
+
+#[test]
+#[test_with(1)] //~ ERROR the `#[test_with]` attribute is an experimental feature
+fn one(n: u8) {
+    assert_eq!(n, 1);
+}
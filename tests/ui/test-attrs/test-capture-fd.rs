@@ -0,0 +1,30 @@
+//@ compile-flags: --test
+//@ run-fail
+//@ run-flags: -Zunstable-options --capture=fd
+//@ check-run-results
+//@ exec-env:RUST_BACKTRACE=0
+//@ normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+//@ only-unix
+//@ only-nightly
+//@ needs-threads
+//@ needs-unwind
+
+use std::io::Write;
+
+#[test]
+fn raw_writes_pass() {
+    std::io::stdout().write_all(b"not shown\n").unwrap();
+}
+
+#[test]
+fn raw_writes_fail() {
+    println!("print");
+    std::io::stdout().write_all(b"raw stdout\n").unwrap();
+    std::io::stderr().write_all(b"raw stderr\n").unwrap();
+    std::thread::spawn(|| {
+        std::io::stdout().write_all(b"raw stdout from a thread\n").unwrap();
+    })
+    .join()
+    .unwrap();
+    panic!();
+}
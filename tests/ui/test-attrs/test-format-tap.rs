@@ -0,0 +1,39 @@
+//@ compile-flags: --test
+//@ run-flags: -Zunstable-options --format=tap --test-threads=1
+//@ run-fail
+//@ check-run-results
+//@ exec-env:RUST_BACKTRACE=0
+//@ normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+//@ needs-unwind
+//@ only-nightly
+
+// Checks the output of libtest's TAP formatter.
+
+#[test]
+fn a() {
+    println!("print from successful test");
+}
+
+#[test]
+fn b() {
+    println!("print from failing test");
+    assert!(false);
+}
+
+#[test]
+#[should_panic]
+fn c() {
+    assert!(false);
+}
+
+#[test]
+#[ignore = "msg"]
+fn d() {
+    assert!(false);
+}
+
+#[test]
+#[should_panic(expected = "bang")]
+fn e() {
+    panic!("boom");
+}
@@ -0,0 +1,36 @@
+//@ run-fail
+//@ check-run-results
+//@ run-flags: -Zunstable-options --isolate=process
+//@ only-nightly
+
+// Checks that `--isolate=process` is rejected by harnesses that call `test_main` themselves,
+// since their child processes would run the whole harness again instead of a single test.
+
+#![feature(test)]
+
+extern crate test;
+
+use test::{DynTestFn, DynTestName, ShouldPanic, TestDesc, TestDescAndFn, TestType};
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let test = TestDescAndFn {
+        desc: TestDesc {
+            name: DynTestName("dynamic".to_string()),
+            ignore: false,
+            ignore_message: None,
+            source_file: file!(),
+            start_line: line!() as usize,
+            start_col: 0,
+            end_line: line!() as usize,
+            end_col: 0,
+            should_panic: ShouldPanic::No,
+            timeout: None,
+            compile_fail: false,
+            no_run: false,
+            test_type: TestType::Unknown,
+        },
+        testfn: DynTestFn(Box::new(|| Ok(()))),
+    };
+    test::test_main(&args, vec![test], None);
+}
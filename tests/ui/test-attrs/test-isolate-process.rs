@@ -0,0 +1,55 @@
+//@ run-pass
+//@ compile-flags: --test
+//@ run-flags: -Zunstable-options --isolate=process --test-threads=1
+//@ needs-unwind
+//@ only-nightly
+
+//@ ignore-wasm no subprocess support
+//@ ignore-emscripten no subprocess support
+//@ ignore-sgx no subprocess support
+
+// Tests that `--isolate=process` runs every test in its own process with panic=unwind:
+// changes to global state don't leak between tests, and panics caught by the test itself
+// don't fail it.
+
+#![cfg(test)]
+
+use std::env;
+
+const VAR: &str = "TEST_ISOLATE_PROCESS_VAR";
+
+fn set_var_once() {
+    assert!(env::var_os(VAR).is_none());
+    env::set_var(VAR, "1");
+}
+
+#[test]
+fn first_sets_var() {
+    set_var_once();
+}
+
+#[test]
+fn second_sets_var() {
+    set_var_once();
+}
+
+#[test]
+fn no_residual_environment() {
+    for (key, _) in env::vars() {
+        // Look for keys like __RUST_TEST_INVOKE.
+        if key.contains("TEST_INVOKE") {
+            panic!("shouldn't have '{}' in environment", key);
+        }
+    }
+}
+
+#[test]
+fn caught_panic() {
+    assert!(std::panic::catch_unwind(|| panic!("caught")).is_err());
+}
+
+#[test]
+#[should_panic(expected = "expected")]
+fn it_panics() {
+    panic!("expected");
+}
@@ -0,0 +1,13 @@
+//@ compile-flags: --test
+
+#![feature(test_timeout)]
+
+#[test]
+#[test_timeout = "soon"]
+//~^ ERROR `#[test_timeout]` expects a positive whole number of seconds
+fn not_a_number() {}
+
+#[test]
+#[test_timeout = "0"]
+//~^ ERROR `#[test_timeout]` expects a positive whole number of seconds
+fn zero() {}
@@ -0,0 +1,28 @@
+//@ no-prefer-dynamic
+//@ compile-flags: --test -Cpanic=abort -Zpanic_abort_tests
+//@ run-flags: --test-threads=1
+//@ run-fail
+//@ check-run-results
+//@ normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+
+//@ ignore-wasm no subprocess support
+//@ ignore-emscripten no subprocess support
+//@ ignore-sgx no subprocess support
+
+#![cfg(test)]
+#![feature(test_timeout)]
+
+#[test]
+#[test_timeout = "1"]
+fn it_hangs() {
+    println!("about to hang");
+    loop {
+        std::thread::park();
+    }
+}
+
+#[test]
+#[test_timeout = "1"]
+fn it_works() {
+    assert_eq!(1 + 1, 2);
+}
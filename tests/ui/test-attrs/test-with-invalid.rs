@@ -0,0 +1,29 @@
+//@ compile-flags: --test
+
+#![feature(test, test_with)]
+
+#[test]
+#[test_with(1)]
+#[test_with(1, 2)]
+//~^ ERROR this test case has 2 arguments but the test function takes 1
+fn too_many(a: i32) {}
+
+#[test]
+#[test_with()]
+//~^ ERROR this test case has 0 arguments but the test function takes 1
+fn too_few(a: i32) {}
+
+#[test]
+#[test_with(1 2)]
+//~^ ERROR expected token: `,`
+fn missing_comma(a: i32) {}
+
+#[test]
+#[test_with = "1"]
+//~^ ERROR `#[test_with]` expects a parenthesized list of arguments
+fn name_value(a: i32) {}
+
+#[bench]
+#[test_with(1)]
+//~^ ERROR `#[test_with]` cannot be used on benchmarks
+fn bench(b: &mut test::Bencher) {}
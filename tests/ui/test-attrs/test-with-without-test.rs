@@ -0,0 +1,12 @@
+//@ compile-flags: --test
+
+#![feature(test_with)]
+#![allow(dead_code)]
+
+#[test_with(1)]
+//~^ ERROR `#[test_with]` can only be used on `#[test]` functions
+fn not_a_test(_a: i32) {}
+
+#[test]
+#[test_with(1)]
+fn a_test(_a: i32) {}
@@ -0,0 +1,39 @@
+//@ run-pass
+//@ compile-flags: --test
+//@ run-flags: --test-threads=1 --skip add::case_2
+//@ check-run-results
+//@ normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+//@ needs-threads
+//@ needs-unwind
+
+#![feature(test_with)]
+
+#[test]
+#[test_with(1, 2, 3)]
+#[test_with(-4, 4, 0)]
+#[test_with(i32::MAX, 1, i32::MIN)]
+fn add(a: i32, b: i32, sum: i32) {
+    assert_eq!(a + b, sum);
+}
+
+#[test]
+#[test_with("abc", vec!['a', 'b', 'c'])]
+#[test_with("", Vec::new())]
+fn chars(s: &str, expected: Vec<char>) {
+    assert_eq!(s.chars().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+#[test_with(0)]
+#[should_panic(expected = "attempt to divide by zero")]
+fn divide_by(divisor: i32) {
+    let _ = 1 / divisor;
+}
+
+mod nested {
+    #[test]
+    #[test_with(Ok(()))]
+    fn returns(result: Result<(), String>) -> Result<(), String> {
+        result
+    }
+}
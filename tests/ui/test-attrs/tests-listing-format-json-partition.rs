@@ -0,0 +1,21 @@
+//@ no-prefer-dynamic
+//@ compile-flags: --test
+//@ run-flags: --list --format json --partition count:2/2 -Zunstable-options
+//@ run-pass
+//@ check-run-results
+//@ only-nightly
+//@ normalize-stdout-test: "fake-test-src-base/test-attrs/" -> "$$DIR/"
+//@ normalize-stdout-test: "fake-test-src-base\\test-attrs\\" -> "$$DIR/"
+
+// Checks that --format json lists the tests of every shard with their shard.
+
+#![cfg(test)]
+#[test]
+fn m_test() {}
+
+#[test]
+#[ignore = "not yet implemented"]
+fn z_test() {}
+
+#[test]
+fn a_test() {}